@@ -0,0 +1,443 @@
+//! `terra` — headless planet generation and export.
+//!
+//! Runs the same pipelines as the browser front end and writes every field,
+//! the realism score / planet metrics and the resolved parameters to an
+//! output directory:
+//!
+//!   terra generate --params world.toml --format geotiff,raw -o out/
+//!   terra overview --seed 7 --count 100 -o batch/
+//!   terra overview --preset arid-mars --seed 3 -o mars/
+//!   terra location --lat -12.5 --lon 130 --glaciation 0.1
+//!   terra overview --overview-size 4096x2048 -o print/
+//!
+//! With `--count N` the seeds `seed..seed+N` are generated into
+//! `<output>/seed-<seed>/` subdirectories.
+//!
+//! Each run's `manifest.json` embeds a reproducibility manifest (crate and
+//! seed-scheme versions, params, per-field hashes).  `terra verify` reruns it
+//! with the current build and exits non-zero if any field diverged:
+//!
+//!   terra verify out/manifest.json
+//!
+//! The default `threading` feature runs the heavy per-pixel stages on all
+//! cores (rayon); set `RAYON_NUM_THREADS` to cap it.  Output is identical to a
+//! `--no-default-features` build.
+
+mod output;
+mod params;
+
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{bail, Context, Result};
+use clap::{Args, Parser, Subcommand};
+use serde_json::json;
+use terra_core::generator::{
+    try_generate_at_location, GenerationConfig, GlobalParams, PlanetGenerator,
+};
+use terra_core::manifest::Manifest;
+use terra_core::planet::generate_planet_overview_with_config;
+
+use output::{write_fields, write_json, Field, FieldData, Format};
+use params::ParamArgs;
+
+// ── CLI ──────────────────────────────────────────────────────────────────────
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "terra",
+    about = "Generate planets headlessly and export their fields"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Full-globe tile pipeline (PlanetGenerator::generate)
+    Generate(RunArgs),
+    /// Planet overview fields and planet metrics (generate_planet_overview)
+    Overview(RunArgs),
+    /// Drill-down tile at a location (generate_at_location)
+    Location {
+        /// Latitude in degrees
+        #[arg(long, allow_hyphen_values = true)]
+        lat: f32,
+        /// Longitude in degrees
+        #[arg(long, allow_hyphen_values = true)]
+        lon: f32,
+        #[command(flatten)]
+        run: RunArgs,
+    },
+    /// Regenerate a run from its manifest and report diverged fields
+    Verify {
+        /// A run's manifest.json, or a bare reproducibility manifest
+        manifest: PathBuf,
+    },
+}
+
+#[derive(Args, Debug)]
+struct RunArgs {
+    #[command(flatten)]
+    params: ParamArgs,
+
+    /// Output directory (created if absent)
+    #[arg(short, long, default_value = "out")]
+    output: PathBuf,
+
+    /// Raster formats to write (comma-separated)
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_values_t = [Format::Geotiff, Format::Json]
+    )]
+    format: Vec<Format>,
+
+    /// Number of worlds to generate, with consecutive seeds
+    #[arg(long, default_value_t = 1)]
+    count: u64,
+
+    /// Planet overview grid, WIDTHxHEIGHT (default 1024x512)
+    #[arg(long, value_name = "WxH", value_parser = parse_size)]
+    overview_size: Option<(usize, usize)>,
+
+    /// Tile grid of `generate` and `location`, WIDTHxHEIGHT (default 512x256)
+    #[arg(long, value_name = "WxH", value_parser = parse_size)]
+    tile_size: Option<(usize, usize)>,
+}
+
+impl RunArgs {
+    fn config(&self) -> GenerationConfig {
+        let mut config = GenerationConfig::default();
+        if let Some((w, h)) = self.overview_size {
+            (config.overview_width, config.overview_height) = (w, h);
+        }
+        if let Some((w, h)) = self.tile_size {
+            (config.tile_width, config.tile_height) = (w, h);
+        }
+        config
+    }
+}
+
+/// Parse a `WIDTHxHEIGHT` grid size.
+fn parse_size(s: &str) -> Result<(usize, usize)> {
+    let (w, h) = s
+        .split_once(['x', 'X'])
+        .context("expected WIDTHxHEIGHT, e.g. 4096x2048")?;
+    let (w, h): (usize, usize) = (w.trim().parse()?, h.trim().parse()?);
+    if w < 16 || h < 8 {
+        bail!("grid {w}x{h} is too small (minimum 16x8)");
+    }
+    Ok((w, h))
+}
+
+// ── Runs ─────────────────────────────────────────────────────────────────────
+
+/// One pipeline run's products, ready to be written.
+struct RunOutput {
+    fields: Vec<Field>,
+    /// Named JSON documents (score, planet metrics, location sample).
+    documents: Vec<(&'static str, serde_json::Value)>,
+    manifest: Manifest,
+}
+
+fn run_generate(params: &GlobalParams, config: GenerationConfig) -> Result<RunOutput> {
+    let result = PlanetGenerator::with_config(config).generate(params);
+    let manifest = Manifest::for_result(params, &config, &result);
+    let hf = &result.heightfield;
+    // Plate and climate fields share the globe grid but are stored north-up.
+    let fields = vec![
+        Field::heightfield("heightfield", hf),
+        Field::global(
+            "regime",
+            hf.width,
+            hf.height,
+            FieldData::U8(result.regime_field.iter().map(|&r| r as u8).collect()),
+        ),
+        Field::global(
+            "map_mm",
+            hf.width,
+            hf.height,
+            FieldData::F32(result.map_field),
+        ),
+    ];
+    Ok(RunOutput {
+        fields,
+        documents: vec![("score", serde_json::to_value(&result.score)?)],
+        manifest,
+    })
+}
+
+fn run_overview(params: &GlobalParams, config: &GenerationConfig) -> Result<RunOutput> {
+    let ov = generate_planet_overview_with_config(params, config);
+    let manifest = Manifest::for_overview(params, config, &ov);
+    let (w, h) = (ov.width, ov.height);
+    let fields = vec![
+        Field::global("elevation_norm", w, h, FieldData::F32(ov.elevations)),
+        Field::global("elevation_km", w, h, FieldData::F32(ov.physical_elevations)),
+        Field::global(
+            "ocean_mask",
+            w,
+            h,
+            FieldData::U8(ov.ocean_mask.iter().map(|&o| u8::from(o)).collect()),
+        ),
+        Field::global(
+            "regime",
+            w,
+            h,
+            FieldData::U8(ov.regimes.iter().map(|&r| r as u8).collect()),
+        ),
+        Field::global("map_mm", w, h, FieldData::F32(ov.map_field)),
+        Field::global("erodibility", w, h, FieldData::F32(ov.erodibility_field)),
+        Field::global(
+            "glaciation",
+            w,
+            h,
+            FieldData::U8(ov.glaciation.iter().map(|&g| g as u8).collect()),
+        ),
+    ];
+    Ok(RunOutput {
+        fields,
+        documents: vec![
+            ("planet_metrics", serde_json::to_value(&ov.planet_metrics)?),
+            ("sea_level", json!({ "sea_level_km": ov.sea_level_km })),
+        ],
+        manifest,
+    })
+}
+
+fn run_location(
+    params: &GlobalParams,
+    config: &GenerationConfig,
+    lat: f32,
+    lon: f32,
+) -> Result<RunOutput> {
+    let tile = try_generate_at_location(params, config, lat, lon)?;
+    let manifest = Manifest::for_location(params, config, &tile);
+    let hf = &tile.heightfield;
+    let regime = tile.regime_field.iter().map(|&r| r as u8).collect();
+    let fields = vec![
+        Field::heightfield("heightfield", hf),
+        Field::on_heightfield_grid("regime", hf, FieldData::U8(regime)),
+        Field::on_heightfield_grid("map_mm", hf, FieldData::F32(tile.map_field)),
+    ];
+    let location = json!({
+        "lat": tile.lat,
+        "lon": tile.lon,
+        "terrain_class": tile.terrain_class,
+        "regime": tile.local_regime,
+        "map_mm": tile.local_map_mm,
+        "erodibility": tile.local_erodibility,
+        "grain_angle": tile.local_grain_angle,
+        "grain_intensity": tile.local_grain_intensity,
+        "glaciation": tile.local_glaciation,
+    });
+    Ok(RunOutput {
+        fields,
+        documents: vec![
+            ("score", serde_json::to_value(&tile.score)?),
+            ("location", location),
+        ],
+        manifest,
+    })
+}
+
+/// Write one run's products plus `params.json` and `manifest.json` to `dir`.
+fn write_run(
+    dir: &Path,
+    command: &str,
+    params: &GlobalParams,
+    config: &GenerationConfig,
+    run: RunOutput,
+    formats: &[Format],
+    generation_time_ms: u64,
+) -> Result<()> {
+    let fields = write_fields(dir, &run.fields, formats)?;
+    write_json(&dir.join("params.json"), params)?;
+    let mut documents = Vec::with_capacity(run.documents.len());
+    for (name, value) in &run.documents {
+        let file_name = format!("{name}.json");
+        write_json(&dir.join(&file_name), value)?;
+        documents.push(file_name);
+    }
+    write_json(
+        &dir.join("manifest.json"),
+        &json!({
+            "command": command,
+            "seed": params.seed,
+            "config": config,
+            "generation_time_ms": generation_time_ms,
+            "fields": fields,
+            "documents": documents,
+            "reproducibility": run.manifest,
+        }),
+    )
+}
+
+/// Read the reproducibility manifest from a run's `manifest.json` or a bare
+/// manifest file such as the terra-core goldens.
+fn read_manifest(path: &Path) -> Result<Manifest> {
+    let text =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let mut value: serde_json::Value = serde_json::from_str(&text)?;
+    if let Some(embedded) = value.get_mut("reproducibility") {
+        value = embedded.take();
+    }
+    serde_json::from_value(value)
+        .with_context(|| format!("{} is not a reproducibility manifest", path.display()))
+}
+
+fn run_verify(path: &Path) -> Result<()> {
+    let manifest = read_manifest(path)?;
+    let start = Instant::now();
+    let report = manifest.verify();
+    println!(
+        "{}: {report} ({} ms)",
+        path.display(),
+        start.elapsed().as_millis()
+    );
+    if !report.reproduced() {
+        bail!("{} no longer reproduces", path.display());
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let (command, run_args) = match &cli.command {
+        Command::Generate(run) => ("generate", run),
+        Command::Overview(run) => ("overview", run),
+        Command::Location { run, .. } => ("location", run),
+        Command::Verify { manifest } => return run_verify(manifest),
+    };
+
+    let base = run_args.params.resolve()?;
+    let config = run_args.config();
+    for k in 0..run_args.count {
+        let params = GlobalParams {
+            seed: base.seed.wrapping_add(k),
+            ..base.clone()
+        };
+        let dir = if run_args.count > 1 {
+            run_args.output.join(format!("seed-{}", params.seed))
+        } else {
+            run_args.output.clone()
+        };
+
+        let start = Instant::now();
+        let run = match &cli.command {
+            Command::Generate(_) => run_generate(&params, config)?,
+            Command::Overview(_) => run_overview(&params, &config)?,
+            Command::Location { lat, lon, .. } => run_location(&params, &config, *lat, *lon)?,
+            Command::Verify { .. } => unreachable!("handled above"),
+        };
+        let elapsed_ms = start.elapsed().as_millis() as u64;
+
+        write_run(
+            &dir,
+            command,
+            &params,
+            &config,
+            run,
+            &run_args.format,
+            elapsed_ms,
+        )?;
+        eprintln!(
+            "[{}/{}] {command} seed={} → {} ({elapsed_ms} ms)",
+            k + 1,
+            run_args.count,
+            params.seed,
+            dir.display()
+        );
+    }
+    Ok(())
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_definition_is_consistent() {
+        use clap::CommandFactory;
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn location_accepts_negative_coordinates_and_formats() {
+        let cli = Cli::try_parse_from([
+            "terra",
+            "location",
+            "--lat",
+            "-12.5",
+            "--lon",
+            "-130",
+            "--format",
+            "raw,geotiff",
+            "--seed",
+            "9",
+        ])
+        .unwrap();
+        let Command::Location { lat, lon, run } = cli.command else {
+            panic!("expected location subcommand");
+        };
+        assert_eq!((lat, lon), (-12.5, -130.0));
+        assert_eq!(run.format, vec![Format::Raw, Format::Geotiff]);
+        assert_eq!(run.params.resolve().unwrap().seed, 9);
+    }
+
+    #[test]
+    fn location_off_the_globe_is_an_error() {
+        let err = run_location(
+            &GlobalParams::default(),
+            &GenerationConfig::default(),
+            91.0,
+            0.0,
+        )
+        .err()
+        .expect("latitude 91 must be rejected");
+        assert!(err.to_string().contains("not a latitude/longitude"));
+    }
+
+    #[test]
+    fn grid_sizes_build_generation_config() {
+        let cli =
+            Cli::try_parse_from(["terra", "overview", "--overview-size", "4096x2048"]).unwrap();
+        let Command::Overview(run) = cli.command else {
+            panic!("expected overview subcommand");
+        };
+        let config = run.config();
+        assert_eq!(
+            (config.overview_width, config.overview_height),
+            (4096, 2048)
+        );
+        assert_eq!(config.tile_width, GenerationConfig::default().tile_width);
+
+        assert!(parse_size("128x64").is_ok());
+        assert!(parse_size("128").is_err());
+        assert!(parse_size("4x2").is_err());
+    }
+
+    #[test]
+    fn written_manifest_verifies() {
+        let dir = std::env::temp_dir().join(format!("terra-verify-{}", std::process::id()));
+        let params = GlobalParams::default();
+        let config = GenerationConfig {
+            overview_width: 64,
+            overview_height: 32,
+            ..GenerationConfig::default()
+        };
+        let run = run_overview(&params, &config).unwrap();
+        write_run(&dir, "overview", &params, &config, run, &[Format::Json], 0).unwrap();
+
+        let path = dir.join("manifest.json");
+        let manifest = read_manifest(&path).unwrap();
+        assert!(manifest.fields.contains_key("map_field"));
+        assert!(run_verify(&path).is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
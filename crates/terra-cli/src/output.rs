@@ -0,0 +1,225 @@
+//! Raster field writers and the per-run manifest.
+//!
+//! Every field is held north-up (row 0 at `max_lat`) so that all formats
+//! share one layout; `HeightField`-ordered data is flipped on the way in.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::json;
+use terra_core::export::geotiff::{write_f32_field, write_u8_field, HEIGHTFIELD_NODATA};
+use terra_core::export::GeoBounds;
+use terra_core::heightfield::HeightField;
+
+/// Raster output format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Single-band GeoTIFF (EPSG:4326), `<name>.tif`
+    Geotiff,
+    /// JSON object with dimensions, bounds and a flat `data` array, `<name>.json`
+    Json,
+    /// Headerless little-endian samples, `<name>.f32` / `<name>.u8`
+    Raw,
+}
+
+/// Sample storage of a field.
+pub enum FieldData {
+    F32(Vec<f32>),
+    U8(Vec<u8>),
+}
+
+impl FieldData {
+    fn dtype(&self) -> &'static str {
+        match self {
+            FieldData::F32(_) => "f32",
+            FieldData::U8(_) => "u8",
+        }
+    }
+}
+
+/// One named, georeferenced, north-up raster.
+pub struct Field {
+    pub name: &'static str,
+    pub width: usize,
+    pub height: usize,
+    pub bounds: GeoBounds,
+    pub data: FieldData,
+    pub nodata: Option<f32>,
+}
+
+impl Field {
+    /// Heights of `hf`, flipped to north-up.
+    pub fn heightfield(name: &'static str, hf: &HeightField) -> Self {
+        let mut field = Self::on_heightfield_grid(name, hf, FieldData::F32(hf.data.clone()));
+        field.nodata = Some(HEIGHTFIELD_NODATA);
+        field
+    }
+
+    /// An auxiliary field stored in `HeightField` row order on the grid of `hf`.
+    pub fn on_heightfield_grid(name: &'static str, hf: &HeightField, data: FieldData) -> Self {
+        let data = match data {
+            FieldData::F32(v) => FieldData::F32(flip_rows(&v, hf.width)),
+            FieldData::U8(v) => FieldData::U8(flip_rows(&v, hf.width)),
+        };
+        Self {
+            name,
+            width: hf.width,
+            height: hf.height,
+            bounds: GeoBounds::from_heightfield(hf),
+            data,
+            nodata: None,
+        }
+    }
+
+    /// A whole-globe field that is already north-up.
+    pub fn global(name: &'static str, width: usize, height: usize, data: FieldData) -> Self {
+        Self {
+            name,
+            width,
+            height,
+            bounds: GeoBounds::GLOBAL,
+            data,
+            nodata: None,
+        }
+    }
+}
+
+fn flip_rows<T: Copy>(data: &[T], width: usize) -> Vec<T> {
+    if width == 0 {
+        return Vec::new();
+    }
+    data.chunks(width).rev().flatten().copied().collect()
+}
+
+/// Write `value` as pretty-printed JSON.
+pub fn write_json<T: Serialize + ?Sized>(path: &Path, value: &T) -> Result<()> {
+    let file = File::create(path).with_context(|| format!("creating {}", path.display()))?;
+    serde_json::to_writer_pretty(BufWriter::new(file), value)?;
+    Ok(())
+}
+
+/// Write every field in every format and return the manifest entries.
+pub fn write_fields(
+    dir: &Path,
+    fields: &[Field],
+    formats: &[Format],
+) -> Result<Vec<serde_json::Value>> {
+    fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    let mut manifest = Vec::with_capacity(fields.len());
+
+    for field in fields {
+        let mut files = Vec::new();
+        for &format in formats {
+            let file_name = match (format, &field.data) {
+                (Format::Geotiff, _) => format!("{}.tif", field.name),
+                (Format::Json, _) => format!("{}.json", field.name),
+                (Format::Raw, data) => format!("{}.{}", field.name, data.dtype()),
+            };
+            let path = dir.join(&file_name);
+            write_field(&path, field, format)
+                .with_context(|| format!("writing {}", path.display()))?;
+            files.push(file_name);
+        }
+
+        let b = field.bounds;
+        manifest.push(json!({
+            "name": field.name,
+            "dtype": field.data.dtype(),
+            "width": field.width,
+            "height": field.height,
+            "bounds": [b.min_lon, b.min_lat, b.max_lon, b.max_lat],
+            "row_order": "north_first",
+            "nodata": field.nodata,
+            "files": files,
+        }));
+    }
+    Ok(manifest)
+}
+
+fn write_field(path: &Path, field: &Field, format: Format) -> Result<()> {
+    let (w, h, b) = (field.width, field.height, field.bounds);
+    match format {
+        Format::Geotiff => {
+            let file = BufWriter::new(File::create(path)?);
+            match &field.data {
+                FieldData::F32(v) => write_f32_field(file, v, w, h, b, field.nodata)?,
+                FieldData::U8(v) => write_u8_field(file, v, w, h, b, None)?,
+            }
+        }
+        Format::Json => {
+            let data = match &field.data {
+                FieldData::F32(v) => json!(v),
+                FieldData::U8(v) => json!(v),
+            };
+            let value = json!({
+                "width": w,
+                "height": h,
+                "bounds": [b.min_lon, b.min_lat, b.max_lon, b.max_lat],
+                "row_order": "north_first",
+                "data": data,
+            });
+            let file = File::create(path)?;
+            serde_json::to_writer(BufWriter::new(file), &value)?;
+        }
+        Format::Raw => {
+            let mut file = BufWriter::new(File::create(path)?);
+            match &field.data {
+                FieldData::F32(v) => {
+                    for x in v {
+                        file.write_all(&x.to_le_bytes())?;
+                    }
+                }
+                FieldData::U8(v) => file.write_all(v)?,
+            }
+            file.flush()?;
+        }
+    }
+    Ok(())
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn heightfield_is_flipped_north_up() {
+        let mut hf = HeightField::new(2, 2, 0.0, 2.0, 0.0, 2.0, 0.0);
+        hf.data = vec![1.0, 2.0, 3.0, 4.0]; // row 0 = south
+        let field = Field::heightfield("heightfield", &hf);
+        let FieldData::F32(data) = &field.data else {
+            panic!("heightfield must be f32");
+        };
+        assert_eq!(data, &[3.0, 4.0, 1.0, 2.0]);
+        assert_eq!(field.nodata, Some(HEIGHTFIELD_NODATA));
+    }
+
+    #[test]
+    fn every_format_written_and_listed() {
+        let dir = std::env::temp_dir().join(format!("terra-cli-out-{}", std::process::id()));
+        let fields = [
+            Field::global("map_mm", 4, 2, FieldData::F32(vec![1.5; 8])),
+            Field::global("regime", 4, 2, FieldData::U8(vec![3; 8])),
+        ];
+        let manifest =
+            write_fields(&dir, &fields, &[Format::Geotiff, Format::Json, Format::Raw]).unwrap();
+
+        assert_eq!(manifest.len(), 2);
+        assert_eq!(
+            manifest[1]["files"],
+            json!(["regime.tif", "regime.json", "regime.u8"])
+        );
+        assert_eq!(fs::read(dir.join("map_mm.f32")).unwrap().len(), 8 * 4);
+        assert_eq!(fs::read(dir.join("regime.u8")).unwrap(), vec![3; 8]);
+        let v: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join("map_mm.json")).unwrap()).unwrap();
+        assert_eq!(v["data"][7], 1.5);
+        assert!(fs::metadata(dir.join("map_mm.tif")).unwrap().len() > 32);
+        fs::remove_dir_all(&dir).ok();
+    }
+}
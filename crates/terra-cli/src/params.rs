@@ -0,0 +1,165 @@
+//! `GlobalParams` resolution: defaults or a preset, then an optional recipe
+//! file, then flags.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use terra_core::generator::GlobalParams;
+use terra_core::recipe::{Preset, Recipe};
+
+/// Parameter sources shared by every subcommand.
+#[derive(Args, Debug, Default)]
+pub struct ParamArgs {
+    /// Recipe file (.json or .toml); omitted keys keep their defaults or
+    /// those of the preset the file names
+    #[arg(long = "params", value_name = "FILE")]
+    pub file: Option<PathBuf>,
+
+    /// Start from a named preset (earth-like, pangaea, archipelago,
+    /// arid-mars, snowball)
+    #[arg(long, conflicts_with = "file")]
+    pub preset: Option<Preset>,
+
+    #[arg(long)]
+    pub seed: Option<u64>,
+    #[arg(long)]
+    pub tectonic_activity: Option<f32>,
+    #[arg(long)]
+    pub water_abundance: Option<f32>,
+    #[arg(long)]
+    pub surface_age: Option<f32>,
+    #[arg(long)]
+    pub climate_diversity: Option<f32>,
+    #[arg(long)]
+    pub glaciation: Option<f32>,
+    #[arg(long)]
+    pub continental_fragmentation: Option<f32>,
+    #[arg(long)]
+    pub mountain_prevalence: Option<f32>,
+}
+
+impl ParamArgs {
+    /// Resolve the final parameters: defaults or preset < file < flags.
+    pub fn resolve(&self) -> Result<GlobalParams> {
+        let mut p = match (&self.file, self.preset) {
+            (Some(path), _) => load_params_file(path)?,
+            (None, Some(preset)) => preset.params(),
+            (None, None) => GlobalParams::default(),
+        };
+
+        if let Some(v) = self.seed {
+            p.seed = v;
+        }
+        let sliders = [
+            (self.tectonic_activity, &mut p.tectonic_activity),
+            (self.water_abundance, &mut p.water_abundance),
+            (self.surface_age, &mut p.surface_age),
+            (self.climate_diversity, &mut p.climate_diversity),
+            (self.glaciation, &mut p.glaciation),
+            (
+                self.continental_fragmentation,
+                &mut p.continental_fragmentation,
+            ),
+            (self.mountain_prevalence, &mut p.mountain_prevalence),
+        ];
+        for (flag, slot) in sliders {
+            if let Some(v) = flag {
+                *slot = v;
+            }
+        }
+
+        p.validate()?;
+        Ok(p)
+    }
+}
+
+/// Read a (possibly partial) recipe file, filling gaps from its preset.
+pub fn load_params_file(path: &Path) -> Result<GlobalParams> {
+    let text = fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    let recipe = match path.extension().and_then(|e| e.to_str()) {
+        Some("toml") => Recipe::from_toml(&text),
+        _ => Recipe::from_json(&text),
+    };
+    let recipe = recipe.with_context(|| format!("invalid recipe {}", path.display()))?;
+    Ok(recipe.params)
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("terra-cli-{}-{name}", std::process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn partial_toml_keeps_defaults() {
+        let path = temp_file("p.toml", "seed = 7\nglaciation = 0.9\n");
+        let p = load_params_file(&path).unwrap();
+        fs::remove_file(&path).ok();
+        assert_eq!(p.seed, 7);
+        assert_eq!(p.glaciation, 0.9);
+        assert_eq!(p.surface_age, GlobalParams::default().surface_age);
+    }
+
+    #[test]
+    fn flags_override_file() {
+        let path = temp_file("p.json", r#"{ "seed": 7, "surface_age": 0.2 }"#);
+        let args = ParamArgs {
+            file: Some(path.clone()),
+            seed: Some(99),
+            ..ParamArgs::default()
+        };
+        let p = args.resolve().unwrap();
+        fs::remove_file(&path).ok();
+        assert_eq!(p.seed, 99);
+        assert_eq!(p.surface_age, 0.2);
+    }
+
+    #[test]
+    fn unknown_key_and_out_of_range_slider_rejected() {
+        let path = temp_file("bad.json", r#"{ "sead": 7 }"#);
+        assert!(load_params_file(&path).is_err());
+        fs::remove_file(&path).ok();
+
+        let args = ParamArgs {
+            water_abundance: Some(1.5),
+            ..ParamArgs::default()
+        };
+        assert!(args.resolve().is_err());
+    }
+
+    #[test]
+    fn preset_then_flags() {
+        let args = ParamArgs {
+            preset: Some(Preset::Snowball),
+            seed: Some(3),
+            ..ParamArgs::default()
+        };
+        let p = args.resolve().unwrap();
+        let snowball = Preset::Snowball.params();
+        assert_eq!(p.seed, 3);
+        assert_eq!(p.glaciation, snowball.glaciation);
+        assert_eq!(p.tuning, snowball.tuning);
+    }
+
+    #[test]
+    fn recipe_tuning_range_checked() {
+        let path = temp_file(
+            "r.toml",
+            "preset = \"archipelago\"\n[tuning.plates]\nhotspots = 99\n",
+        );
+        let err = load_params_file(&path).unwrap_err();
+        fs::remove_file(&path).ok();
+        assert!(
+            format!("{err:#}").contains("tuning.plates.hotspots"),
+            "{err:#}"
+        );
+    }
+}
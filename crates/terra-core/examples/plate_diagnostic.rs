@@ -11,6 +11,7 @@ use terra_core::plates::plate_dynamics::{
     compute_plate_dynamics, BoundaryCharacter, PlateDynamics,
 };
 use terra_core::plates::plate_generation::{generate_plate_geometry, PlateGeometry};
+use terra_core::sphere::EARTH_RADIUS_KM;
 
 const WIDTH: usize = 1024;
 const HEIGHT: usize = 512;
@@ -52,13 +53,14 @@ fn main() -> Result<()> {
 
     for seed in seeds {
         let geometry = generate_plate_geometry(N_PLATES, seed, WARP_AMPLITUDE_DEG, WIDTH, HEIGHT);
-        let dynamics = compute_plate_dynamics(&geometry, TECTONIC_ACTIVITY, seed);
+        let dynamics = compute_plate_dynamics(&geometry, TECTONIC_ACTIVITY, EARTH_RADIUS_KM, seed);
         let placement = place_continents(
             &geometry,
             &dynamics,
             CONTINENTAL_COVERAGE,
             N_CONTINENTS,
             seed,
+            EARTH_RADIUS_KM,
             WIDTH,
             HEIGHT,
         );
@@ -253,7 +255,9 @@ fn overlay_boundaries(rgba: &mut [u8], geometry: &PlateGeometry, dynamics: &Plat
 fn overlay_velocity_arrows(rgba: &mut [u8], geometry: &PlateGeometry, dynamics: &PlateDynamics) {
     let anchors = plate_arrow_anchors(geometry);
     for (plate_id, &(row, col)) in anchors.iter().enumerate() {
-        let velocity = dynamics.plate_velocities[plate_id];
+        let anchor =
+            terra_core::plates::age_field::cell_to_vec3(row, col, geometry.width, geometry.height);
+        let velocity = dynamics.euler_poles[plate_id].velocity_at(anchor, EARTH_RADIUS_KM);
         let speed = (velocity.0 * velocity.0 + velocity.1 * velocity.1).sqrt();
         if speed <= 0.05 {
             continue;
@@ -0,0 +1,184 @@
+//! Physical parameters of the planet being generated.
+//!
+//! The pipeline was calibrated on Earth.  `PlanetBody` carries the few bulk
+//! properties that stages need to stay physical on other worlds:
+//!
+//! * the radius converts every kilometre constant (margin widths, hotspot
+//!   radii, smoothing sigmas, ocean-floor ages) to grid cells, so a larger
+//!   planet has more room between the same features;
+//! * the axial tilt sets how far the ITCZ migrates, how seasonal the
+//!   mid-latitudes are and how large the polar ice caps grow;
+//! * the rotation sense sets which way the prevailing winds blow, and so
+//!   which side of a mountain belt is wet;
+//! * surface gravity sets how much tectonic relief the crust supports.
+//!
+//! Every scale factor is a ratio against the Earth value, so
+//! `PlanetBody::EARTH` reproduces the calibrated pipeline exactly.
+
+use serde::{Deserialize, Serialize};
+
+use crate::generator::{check_range, RangeError};
+use crate::sphere::EARTH_RADIUS_KM;
+
+/// Earth's obliquity in degrees.
+pub const EARTH_AXIAL_TILT_DEG: f32 = 23.44;
+/// Earth's standard surface gravity in m/s².
+pub const EARTH_SURFACE_GRAVITY_M_S2: f32 = 9.81;
+
+/// Spin direction relative to the planet's orbit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rotation {
+    /// Same sense as the orbit, like Earth: easterly trades, westerlies.
+    #[default]
+    Prograde,
+    /// Opposite sense, like Venus: every zonal wind belt is reversed.
+    Retrograde,
+}
+
+/// Bulk physical properties of the generated planet.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlanetBody {
+    /// Mean radius in kilometres.
+    pub radius_km: f64,
+    /// Obliquity in degrees, 0 (no seasons) to 90 (pole faces the star).
+    pub axial_tilt_deg: f32,
+    pub rotation: Rotation,
+    /// Surface gravity in m/s².
+    pub surface_gravity_m_s2: f32,
+}
+
+impl Default for PlanetBody {
+    fn default() -> Self {
+        Self::EARTH
+    }
+}
+
+impl PlanetBody {
+    pub const EARTH: PlanetBody = PlanetBody {
+        radius_km: EARTH_RADIUS_KM,
+        axial_tilt_deg: EARTH_AXIAL_TILT_DEG,
+        rotation: Rotation::Prograde,
+        surface_gravity_m_s2: EARTH_SURFACE_GRAVITY_M_S2,
+    };
+
+    /// Check each property against the range the pipeline supports.
+    ///
+    /// Errors are named by recipe key, e.g. `body.radius_km`.
+    pub fn validate(&self) -> Result<(), RangeError> {
+        check_range("body.radius_km", self.radius_km, 2000.0, 16_000.0)?;
+        check_range("body.axial_tilt_deg", self.axial_tilt_deg as f64, 0.0, 90.0)?;
+        check_range(
+            "body.surface_gravity_m_s2",
+            self.surface_gravity_m_s2 as f64,
+            2.0,
+            30.0,
+        )
+    }
+
+    /// Equatorial east-west size (km) of one cell of a global `width`-column grid.
+    pub fn equatorial_cell_km(&self, width: usize) -> f64 {
+        crate::sphere::equatorial_cell_km(width, self.radius_km)
+    }
+
+    /// Width of the ITCZ rain belt relative to Earth's.
+    ///
+    /// The belt follows the subsolar point, so it widens with tilt; half of
+    /// Earth's width is kept at zero tilt for the belt's own convective spread.
+    pub fn itcz_spread(&self) -> f64 {
+        0.5 + 0.5 * (self.axial_tilt_deg / EARTH_AXIAL_TILT_DEG) as f64
+    }
+
+    /// Amplitude of the seasonal cycle relative to Earth's, from the
+    /// insolation swing `sin(tilt)`.  Zero for an upright planet.
+    pub fn seasonality_scale(&self) -> f32 {
+        self.axial_tilt_deg.to_radians().sin() / EARTH_AXIAL_TILT_DEG.to_radians().sin()
+    }
+
+    /// Divisor for the angular size of the polar ice caps.
+    ///
+    /// Higher obliquity brings more summer sun to the poles and shrinks the
+    /// caps (above 1); an upright planet keeps its poles in permanent low
+    /// sun and grows them (below 1).
+    pub fn polar_cap_scale(&self) -> f32 {
+        (1.0 + self.axial_tilt_deg.to_radians().sin())
+            / (1.0 + EARTH_AXIAL_TILT_DEG.to_radians().sin())
+    }
+
+    /// Multiplier on tectonic relief relative to Earth.
+    ///
+    /// The height a mountain belt or volcanic edifice can reach before its
+    /// base yields scales with `1 / g`.
+    pub fn relief_scale(&self) -> f32 {
+        EARTH_SURFACE_GRAVITY_M_S2 / self.surface_gravity_m_s2
+    }
+
+    /// True when the prevailing surface wind at `lat_deg` blows eastward.
+    ///
+    /// Three-cell circulation: trades below 30°, westerlies to 60°, polar
+    /// easterlies beyond; retrograde rotation reverses all three.
+    pub fn prevailing_wind_eastward(&self, lat_deg: f64) -> bool {
+        let westerlies = (30.0..60.0).contains(&lat_deg.abs());
+        match self.rotation {
+            Rotation::Prograde => westerlies,
+            Rotation::Retrograde => !westerlies,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn earth_scale_factors_are_exactly_one() {
+        let earth = PlanetBody::default();
+        assert_eq!(earth, PlanetBody::EARTH);
+        earth.validate().unwrap();
+        assert_eq!(earth.itcz_spread(), 1.0);
+        assert_eq!(earth.seasonality_scale(), 1.0);
+        assert_eq!(earth.polar_cap_scale(), 1.0);
+        assert_eq!(earth.relief_scale(), 1.0);
+        assert!(earth.prevailing_wind_eastward(45.0));
+        assert!(!earth.prevailing_wind_eastward(-15.0));
+    }
+
+    #[test]
+    fn tilt_and_rotation_move_climate_belts() {
+        let upright = PlanetBody {
+            axial_tilt_deg: 0.0,
+            ..PlanetBody::EARTH
+        };
+        let oblique = PlanetBody {
+            axial_tilt_deg: 60.0,
+            ..PlanetBody::EARTH
+        };
+        assert_eq!(upright.seasonality_scale(), 0.0);
+        assert!(upright.itcz_spread() < 1.0 && oblique.itcz_spread() > 1.0);
+        assert!(upright.polar_cap_scale() < 1.0 && oblique.polar_cap_scale() > 1.0);
+
+        let retrograde = PlanetBody {
+            rotation: Rotation::Retrograde,
+            ..PlanetBody::EARTH
+        };
+        for lat in [-75.0, -45.0, 10.0, 45.0, 80.0] {
+            assert_ne!(
+                retrograde.prevailing_wind_eastward(lat),
+                PlanetBody::EARTH.prevailing_wind_eastward(lat)
+            );
+        }
+    }
+
+    #[test]
+    fn out_of_range_body_is_named_by_key() {
+        let body = PlanetBody {
+            radius_km: 500.0,
+            ..PlanetBody::EARTH
+        };
+        assert_eq!(
+            body.validate().unwrap_err().to_string(),
+            "body.radius_km must be in [2000, 16000], got 500"
+        );
+    }
+}
@@ -11,7 +11,11 @@
 //!
 //! Calibrated so the polar_glaciation metric (|lat|>60°, fraction of Active+Former)
 //! stays within ±0.15 of the expected value `(slider × 1.5).clamp(0,1)`.
+//!
+//! On other worlds both cap widths are divided by `PlanetBody::polar_cap_scale`;
+//! the metric's polar zone shrinks by the same factor, so the calibration holds.
 
+use crate::body::PlanetBody;
 use crate::noise::params::GlacialClass;
 
 /// Compute a glaciation mask for a `width × height` grid.
@@ -25,19 +29,22 @@ pub fn compute_glaciation_mask(
     width: usize,
     height: usize,
     glaciation_slider: f32,
+    body: &PlanetBody,
 ) -> Vec<GlacialClass> {
     let n = width * height;
     if n == 0 {
         return Vec::new();
     }
 
+    let cap_scale = body.polar_cap_scale();
+
     // Active glaciation: poleward of this absolute latitude.
-    // At slider=0.1: 90 − 0.1×25 = 87.5°.
-    let active_threshold = 90.0_f32 - glaciation_slider * 25.0;
+    // At slider=0.1 on Earth: 90 − 0.1×25 = 87.5°.
+    let active_threshold = 90.0_f32 - glaciation_slider * 25.0 / cap_scale;
 
     // Formerly glaciated: equatorward of Active, down to this latitude.
-    // At slider=0.3: 90 − 0.3×50 = 75°.
-    let former_threshold = 90.0_f32 - glaciation_slider * 50.0;
+    // At slider=0.3 on Earth: 90 − 0.3×50 = 75°.
+    let former_threshold = 90.0_f32 - glaciation_slider * 50.0 / cap_scale;
 
     let mut result = Vec::with_capacity(n);
 
@@ -75,7 +82,7 @@ mod tests {
     fn active_above_60_for_low_slider() {
         let w = 128usize;
         let h = 64usize;
-        let mask = compute_glaciation_mask(w, h, 0.1);
+        let mask = compute_glaciation_mask(w, h, 0.1, &PlanetBody::EARTH);
         for r in 0..h {
             let lat_abs = lat_of_row(r, h).abs();
             for c in 0..w {
@@ -92,7 +99,7 @@ mod tests {
     /// At slider = 0 there are no Active or Former cells.
     #[test]
     fn slider_zero_gives_no_glaciation() {
-        let mask = compute_glaciation_mask(64, 32, 0.0);
+        let mask = compute_glaciation_mask(64, 32, 0.0, &PlanetBody::EARTH);
         for &c in &mask {
             assert_eq!(
                 c,
@@ -105,7 +112,7 @@ mod tests {
     /// At slider = 1 some cells near the poles are Active.
     #[test]
     fn slider_one_has_active_cells() {
-        let mask = compute_glaciation_mask(64, 32, 1.0);
+        let mask = compute_glaciation_mask(64, 32, 1.0, &PlanetBody::EARTH);
         let has_active = mask.contains(&GlacialClass::Active);
         assert!(
             has_active,
@@ -116,14 +123,14 @@ mod tests {
     /// Output length matches grid.
     #[test]
     fn output_length_matches_grid() {
-        let mask = compute_glaciation_mask(32, 16, 0.3);
+        let mask = compute_glaciation_mask(32, 16, 0.3, &PlanetBody::EARTH);
         assert_eq!(mask.len(), 32 * 16);
     }
 
     /// Empty grid returns empty vec.
     #[test]
     fn empty_grid() {
-        assert!(compute_glaciation_mask(0, 16, 0.3).is_empty());
-        assert!(compute_glaciation_mask(16, 0, 0.3).is_empty());
+        assert!(compute_glaciation_mask(0, 16, 0.3, &PlanetBody::EARTH).is_empty());
+        assert!(compute_glaciation_mask(16, 0, 0.3, &PlanetBody::EARTH).is_empty());
     }
 }
@@ -9,17 +9,23 @@
 //!
 //! All values are in mm/yr. At `water_abundance = 0.55` (Earth default)
 //! the output matches observed zonal-mean precipitation.
+//!
+//! The ITCZ width follows the planet's axial tilt; the arid and westerly
+//! belts sit at the Hadley and Ferrel cell edges, which tilt does not move.
+
+use crate::body::PlanetBody;
 
 /// Returns the latitudinal MAP base value in mm/yr.
 ///
 /// `lat_deg` is geodetic latitude in degrees (–90 to +90).
 /// `water_abundance` is the global MAP scalar (0–1; Earth default 0.55).
-pub fn map_base_mm(lat_deg: f64, water_abundance: f32) -> f32 {
+pub fn map_base_mm(lat_deg: f64, water_abundance: f32, body: &PlanetBody) -> f32 {
     let lat_abs = lat_deg.abs();
 
-    // ITCZ: Gaussian peak centred on equator, σ ≈ 22° (wider than Earth to
-    // reduce visible latitudinal banding at planet-overview scale).
-    let equatorial = 1560.0 * (-lat_abs * lat_abs / 968.0_f64).exp();
+    // ITCZ: Gaussian peak centred on equator, σ ≈ 22° on Earth (wider than
+    // Earth to reduce visible latitudinal banding at planet-overview scale).
+    let itcz_sigma_sq = 484.0 * body.itcz_spread().powi(2);
+    let equatorial = 1560.0 * (-lat_abs * lat_abs / (2.0 * itcz_sigma_sq)).exp();
 
     // Subtropical arid belt: negative Gaussian centred at 28°, σ ≈ 8°.
     // On drier planets (wa < 0.55) the descending Hadley cell creates more
@@ -50,7 +56,7 @@ mod tests {
     #[test]
     fn equatorial_map_above_1500mm() {
         for lat in [0.0_f64, 5.0, 10.0] {
-            let mm = map_base_mm(lat, 0.55);
+            let mm = map_base_mm(lat, 0.55, &PlanetBody::EARTH);
             assert!(
                 mm > 1500.0,
                 "lat={lat}°: MAP={mm:.0} mm, expected > 1500 mm"
@@ -61,8 +67,8 @@ mod tests {
     /// Subtropical arid belt is drier than equatorial.
     #[test]
     fn subtropical_drier_than_equatorial() {
-        let equatorial = map_base_mm(5.0, 0.55);
-        let subtropical = map_base_mm(28.0, 0.55);
+        let equatorial = map_base_mm(5.0, 0.55, &PlanetBody::EARTH);
+        let subtropical = map_base_mm(28.0, 0.55, &PlanetBody::EARTH);
         assert!(
             subtropical < equatorial,
             "subtropical {subtropical:.0} should be < equatorial {equatorial:.0}"
@@ -72,11 +78,11 @@ mod tests {
     /// water_abundance scales output proportionally.
     #[test]
     fn water_abundance_scales_output() {
-        let high = map_base_mm(5.0, 1.0);
-        let low = map_base_mm(5.0, 0.1);
+        let high = map_base_mm(5.0, 1.0, &PlanetBody::EARTH);
+        let low = map_base_mm(5.0, 0.1, &PlanetBody::EARTH);
         assert!(high > low, "higher water_abundance should give higher MAP");
         // At water_abundance=0, output should be near 0.
-        let zero = map_base_mm(5.0, 0.0);
+        let zero = map_base_mm(5.0, 0.0, &PlanetBody::EARTH);
         assert!(zero < 1.0, "water_abundance=0 should give ~0 mm");
     }
 
@@ -84,7 +90,7 @@ mod tests {
     #[test]
     fn map_is_non_negative() {
         for lat in [-90.0_f64, -60.0, -30.0, 0.0, 30.0, 60.0, 90.0] {
-            let mm = map_base_mm(lat, 0.55);
+            let mm = map_base_mm(lat, 0.55, &PlanetBody::EARTH);
             assert!(mm >= 0.0, "lat={lat}°: MAP={mm:.1} should be ≥ 0");
         }
     }
@@ -93,8 +99,8 @@ mod tests {
     #[test]
     fn symmetric_about_equator() {
         for lat in [10.0_f64, 30.0, 50.0, 70.0] {
-            let n = map_base_mm(lat, 0.55);
-            let s = map_base_mm(-lat, 0.55);
+            let n = map_base_mm(lat, 0.55, &PlanetBody::EARTH);
+            let s = map_base_mm(-lat, 0.55, &PlanetBody::EARTH);
             assert!(
                 (n - s).abs() < 1e-3,
                 "lat ±{lat}°: N={n:.3} S={s:.3} should match"
@@ -8,6 +8,7 @@
 //! Three-octave fBm at very low base frequency (≈2 cycles across the grid)
 //! ensures the spatial correlation length exceeds 100 km (Design Bible §11).
 
+use crate::seed;
 use noise::{NoiseFn, Perlin};
 
 /// Returns a multiplicative noise field (1 element per grid cell, row-major).
@@ -24,7 +25,7 @@ pub fn generate_map_noise(
         return Vec::new();
     }
 
-    let perlin = Perlin::new((seed ^ 0xC1_1A_1E_00) as u32);
+    let perlin = Perlin::new(seed::derive_u32(seed, "climate/map-noise", &[]));
 
     // Very low frequency: ~2 cycles across each axis.
     let freq_x = 2.0 / width as f64;
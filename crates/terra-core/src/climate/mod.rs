@@ -13,6 +13,9 @@ pub mod map_noise;
 pub mod orographic;
 pub mod seasonality;
 
+use crate::body::PlanetBody;
+use crate::error::{check_field_len, check_grid, TerraError};
+use crate::generator::check_range;
 use crate::noise::params::GlacialClass;
 use crate::plates::regime_field::RegimeField;
 
@@ -34,9 +37,55 @@ pub struct ClimateLayer {
     pub height: usize,
 }
 
-/// Run the full climate layer pipeline.
+/// `simulate_climate_with` after checking the sliders, the body and that
+/// `regime_field` covers the non-empty `width × height` grid.
+#[allow(clippy::too_many_arguments)]
+pub fn try_simulate_climate(
+    seed: u64,
+    water_abundance: f32,
+    climate_diversity: f32,
+    glaciation: f32,
+    regime_field: &RegimeField,
+    body: &PlanetBody,
+    width: usize,
+    height: usize,
+) -> Result<ClimateLayer, TerraError> {
+    let sliders = [
+        ("water_abundance", water_abundance),
+        ("climate_diversity", climate_diversity),
+        ("glaciation", glaciation),
+    ];
+    for (name, v) in sliders {
+        check_range(name, v as f64, 0.0, 1.0)?;
+    }
+    body.validate()?;
+    check_grid("climate", width, height)?;
+    if (regime_field.width, regime_field.height) != (width, height) {
+        return Err(TerraError::GridMismatch {
+            field: "regime_field",
+            width,
+            height,
+            actual_width: regime_field.width,
+            actual_height: regime_field.height,
+        });
+    }
+    check_field_len("regime_field", regime_field.data.len(), width, height)?;
+    Ok(simulate_climate_with(
+        seed,
+        water_abundance,
+        climate_diversity,
+        glaciation,
+        regime_field,
+        body,
+        width,
+        height,
+    ))
+}
+
+/// Run the full climate layer pipeline for an Earth-like planet.
 ///
-/// `regime_field` must match the `width × height` grid dimensions.
+/// `regime_field` must match the `width × height` grid dimensions; see
+/// `try_simulate_climate` for a checked variant.
 pub fn simulate_climate(
     seed: u64,
     water_abundance: f32,
@@ -45,12 +94,39 @@ pub fn simulate_climate(
     regime_field: &RegimeField,
     width: usize,
     height: usize,
+) -> ClimateLayer {
+    simulate_climate_with(
+        seed,
+        water_abundance,
+        climate_diversity,
+        glaciation,
+        regime_field,
+        &PlanetBody::EARTH,
+        width,
+        height,
+    )
+}
+
+/// `simulate_climate` on a planet with the given physical parameters.
+///
+/// Tilt sets the ITCZ width, seasonality and ice-cap extent; rotation sets
+/// the prevailing winds; radius sets the kilometre scale of the cells.
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_climate_with(
+    seed: u64,
+    water_abundance: f32,
+    climate_diversity: f32,
+    glaciation: f32,
+    regime_field: &RegimeField,
+    body: &PlanetBody,
+    width: usize,
+    height: usize,
 ) -> ClimateLayer {
     // P5.1: Latitudinal MAP base.
     let mut map_field: Vec<f32> = (0..height)
         .flat_map(|r| {
             let lat = 90.0 - (r as f64 + 0.5) / height as f64 * 180.0;
-            let base = map_base_mm(lat, water_abundance);
+            let base = map_base_mm(lat, water_abundance, body);
             std::iter::repeat_n(base, width)
         })
         .collect();
@@ -62,13 +138,14 @@ pub fn simulate_climate(
     }
 
     // P5.2: Orographic correction (windward / leeward).
-    apply_orographic_correction(&mut map_field, regime_field, width, height);
+    apply_orographic_correction(&mut map_field, regime_field, width, height, body);
 
     // P5.4: Seasonality field.
-    let seasonality_field = generate_seasonality(&map_field, width, height, climate_diversity);
+    let seasonality_field =
+        generate_seasonality(&map_field, width, height, climate_diversity, body);
 
     // P5.5: Glaciation mask.
-    let glaciation_mask = compute_glaciation_mask(width, height, glaciation);
+    let glaciation_mask = compute_glaciation_mask(width, height, glaciation, body);
 
     ClimateLayer {
         map_field,
@@ -108,6 +185,37 @@ mod tests {
 
     // ── Roadmap testable end states ──────────────────────────────────────────
 
+    #[test]
+    fn try_simulate_climate_rejects_mismatched_regime_field() {
+        let regime = flat_regime(16, 8);
+        assert!(try_simulate_climate(1, 0.5, 0.5, 0.3, &regime, &PlanetBody::EARTH, 16, 8).is_ok());
+        assert_eq!(
+            try_simulate_climate(1, 0.5, 0.5, 0.3, &regime, &PlanetBody::EARTH, 8, 16)
+                .err()
+                .unwrap()
+                .to_string(),
+            "regime_field is 16 × 8, expected 8 × 16"
+        );
+        let truncated = RegimeField {
+            data: regime.data[..100].to_vec(),
+            ..regime
+        };
+        assert!(matches!(
+            try_simulate_climate(1, 0.5, 0.5, 0.3, &truncated, &PlanetBody::EARTH, 16, 8),
+            Err(TerraError::FieldSizeMismatch {
+                field: "regime_field",
+                expected: 128,
+                actual: 100,
+                ..
+            })
+        ));
+        let regime = flat_regime(16, 8);
+        assert!(matches!(
+            try_simulate_climate(1, f32::NAN, 0.5, 0.3, &regime, &PlanetBody::EARTH, 16, 8),
+            Err(TerraError::NotFinite { .. })
+        ));
+    }
+
     /// ✓ End-state 1: equatorial MAP > 1500 mm for water_abundance = 0.55.
     ///
     /// Using climate_diversity = 0 to suppress noise variation.
@@ -243,7 +351,7 @@ mod tests {
 
         // ── wa = 0.55 (Earth-like) ────────────────────────────────────────
         let cl55 = simulate_climate(
-            seed ^ 0x5A5A,
+            seed,
             0.55,
             climate_diversity,
             glaciation,
@@ -274,7 +382,7 @@ mod tests {
 
         // ── wa = 0.30 (arid planet) ───────────────────────────────────────
         let cl30 = simulate_climate(
-            seed ^ 0x5A5A,
+            seed,
             0.30,
             climate_diversity,
             glaciation,
@@ -296,7 +404,7 @@ mod tests {
 
         // ── wa = 0.80 (wet planet) ────────────────────────────────────────
         let cl80 = simulate_climate(
-            seed ^ 0x5A5A,
+            seed,
             0.80,
             climate_diversity,
             glaciation,
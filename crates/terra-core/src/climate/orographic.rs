@@ -14,18 +14,23 @@
 //! | Belt width | Windward | Leeward | Physical analogue |
 //! |-----------|----------|---------|-------------------|
 //! | 1 cell    |  1.5×    |  0.70×  | ~500 m coastal ridge |
-//! | 4 cells   |  2.1×    |  0.53×  | ~2000 m mid-range   |
-//! | 8+ cells  |  3.0×    |  0.30×  | ~4000 m major belt  |
+//! | ~150 km   |  2.1×    |  0.53×  | ~2000 m mid-range   |
+//! | ≥ 313 km  |  3.0×    |  0.30×  | ~4000 m major belt  |
 //!
 //! The full Design Bible ranges (1.5×–3×, 0.3×–0.7×) are used;
-//! the interpolation parameter is `t = (belt_width.min(8) − 1) / 7`.
+//! the interpolation parameter is `t = (belt_width.min(s) − 1) / (s − 1)`,
+//! where `s` is `BELT_WIDTH_SATURATE_KM` in cells (8 at 1024 × 512).
 //!
 //! ## Prevailing wind model
 //!
 //! - |lat| < 30°  → trade winds (westward; upwind direction = east)
 //! - 30° ≤ |lat| < 60° → westerlies (eastward; upwind direction = west)
 //! - |lat| ≥ 60°  → polar easterlies (westward; upwind direction = east)
+//!
+//! A retrograde `PlanetBody` reverses all three belts, moving the rain
+//! shadow to the other side of every range.
 
+use crate::body::PlanetBody;
 use crate::plates::regime_field::{RegimeField, TectonicRegime};
 
 // ── Design Bible §4.2 range limits ──────────────────────────────────────────
@@ -35,8 +40,8 @@ const WINDWARD_MAX: f32 = 3.0; // wide belt  (8+ cells)
 const LEEWARD_MIN: f32 = 0.3; // wide belt
 const LEEWARD_MAX: f32 = 0.7; // narrow belt
 
-/// Belt width (in cells) at which the maximum multiplier is reached.
-const BELT_WIDTH_SATURATE: usize = 8;
+/// Belt width (km) at which the maximum multiplier is reached.
+const BELT_WIDTH_SATURATE_KM: f64 = 313.0;
 
 // ── Public API ───────────────────────────────────────────────────────────────
 
@@ -44,11 +49,13 @@ const BELT_WIDTH_SATURATE: usize = 8;
 ///
 /// `map_field` is row-major, length = `width × height`.
 /// Latitude is derived from row index (row 0 = +90°, last row = −90°).
+/// `body` sets the cell size and the prevailing wind directions.
 pub fn apply_orographic_correction(
     map_field: &mut [f32],
     regime_field: &RegimeField,
     width: usize,
     height: usize,
+    body: &PlanetBody,
 ) {
     if width == 0 || height == 0 {
         return;
@@ -62,10 +69,11 @@ pub fn apply_orographic_correction(
 
     // Scan radius: 12.5% of grid width, minimum 4 cells.
     let influence = (width / 8).max(4);
+    let saturate = belt_saturate_cells(body.equatorial_cell_km(width));
 
     for r in 0..height {
         let lat_deg = 90.0 - (r as f64 + 0.5) / height as f64 * 180.0;
-        let upwind: i64 = if prevailing_wind_eastward(lat_deg) {
+        let upwind: i64 = if body.prevailing_wind_eastward(lat_deg) {
             -1
         } else {
             1
@@ -81,12 +89,12 @@ pub fn apply_orographic_correction(
             // Leeward: mountain lies upwind (wind has passed over it).
             if let Some(mc) = scan_direction(r, c, upwind, influence, width, &is_mountain) {
                 let bw = belt_width_at(r, mc, width, &is_mountain);
-                map_field[idx] *= leeward_mult(bw);
+                map_field[idx] *= leeward_mult(bw, saturate);
             // Windward: mountain lies downwind (wind will hit it next).
             } else if let Some(mc) = scan_direction(r, c, downwind, influence, width, &is_mountain)
             {
                 let bw = belt_width_at(r, mc, width, &is_mountain);
-                map_field[idx] *= windward_mult(bw);
+                map_field[idx] *= windward_mult(bw, saturate);
             }
         }
     }
@@ -140,32 +148,31 @@ fn belt_width_at(row: usize, mountain_col: usize, width: usize, is_mountain: &[b
     count
 }
 
-/// Interpolation parameter: 0.0 = narrowest (1 cell), 1.0 = widest (≥ BELT_WIDTH_SATURATE).
+/// `BELT_WIDTH_SATURATE_KM` in cells of `cell_km` (≥ 2).
+fn belt_saturate_cells(cell_km: f64) -> usize {
+    ((BELT_WIDTH_SATURATE_KM / cell_km).round() as usize).max(2)
+}
+
+/// Interpolation parameter: 0.0 = narrowest (1 cell), 1.0 = widest (≥ `saturate` cells).
 #[inline]
-fn belt_strength(belt_width: usize) -> f32 {
-    ((belt_width.min(BELT_WIDTH_SATURATE) - 1) as f32 / (BELT_WIDTH_SATURATE - 1) as f32)
-        .clamp(0.0, 1.0)
+fn belt_strength(belt_width: usize, saturate: usize) -> f32 {
+    ((belt_width.min(saturate) - 1) as f32 / (saturate - 1) as f32).clamp(0.0, 1.0)
 }
 
 /// Windward multiplier scaled by belt width: 1.5× (narrow) → 3.0× (wide).
 #[inline]
-fn windward_mult(belt_width: usize) -> f32 {
-    let t = belt_strength(belt_width);
+fn windward_mult(belt_width: usize, saturate: usize) -> f32 {
+    let t = belt_strength(belt_width, saturate);
     WINDWARD_MIN + (WINDWARD_MAX - WINDWARD_MIN) * t
 }
 
 /// Leeward multiplier scaled by belt width: 0.70× (narrow) → 0.30× (wide).
 #[inline]
-fn leeward_mult(belt_width: usize) -> f32 {
-    let t = belt_strength(belt_width);
+fn leeward_mult(belt_width: usize, saturate: usize) -> f32 {
+    let t = belt_strength(belt_width, saturate);
     LEEWARD_MAX - (LEEWARD_MAX - LEEWARD_MIN) * t
 }
 
-/// Returns true when the prevailing wind at `lat_deg` blows eastward.
-fn prevailing_wind_eastward(lat_deg: f64) -> bool {
-    (30.0..60.0).contains(&lat_deg.abs())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,7 +213,7 @@ mod tests {
         let h = 64usize;
         let regime = mountain_at_col(w, h, 32);
         let mut map = vec![1000.0_f32; w * h];
-        apply_orographic_correction(&mut map, &regime, w, h);
+        apply_orographic_correction(&mut map, &regime, w, h, &PlanetBody::EARTH);
 
         let r = 16usize;
         let windward = map[r * w + 28];
@@ -230,7 +237,7 @@ mod tests {
         // Narrow belt: 1 column.
         let regime_narrow = mountain_at_col(w, h, 32);
         let mut map_narrow = vec![1000.0_f32; w * h];
-        apply_orographic_correction(&mut map_narrow, &regime_narrow, w, h);
+        apply_orographic_correction(&mut map_narrow, &regime_narrow, w, h, &PlanetBody::EARTH);
         let ratio_narrow = map_narrow[r * w + 36] / map_narrow[r * w + 28];
 
         // Wide belt: 8 columns (cols 29–36).
@@ -238,7 +245,7 @@ mod tests {
         let regime_wide = mountain_cols(w, h, &wide_cols);
         // Use cells outside the belt for comparison (col 20 windward, col 44 leeward).
         let mut map_wide = vec![1000.0_f32; w * h];
-        apply_orographic_correction(&mut map_wide, &regime_wide, w, h);
+        apply_orographic_correction(&mut map_wide, &regime_wide, w, h, &PlanetBody::EARTH);
         let ratio_wide = map_wide[r * w + 44] / map_wide[r * w + 20];
 
         assert!(
@@ -247,19 +254,42 @@ mod tests {
         );
     }
 
-    /// belt_strength saturates at BELT_WIDTH_SATURATE.
+    /// Retrograde rotation moves the rain shadow to the west of the belt.
+    #[test]
+    fn retrograde_rotation_flips_rain_shadow() {
+        let w = 64usize;
+        let h = 64usize;
+        let regime = mountain_at_col(w, h, 32);
+        let mut map = vec![1000.0_f32; w * h];
+        let body = PlanetBody {
+            rotation: crate::body::Rotation::Retrograde,
+            ..PlanetBody::EARTH
+        };
+        apply_orographic_correction(&mut map, &regime, w, h, &body);
+
+        let r = 16usize; // westerlies on Earth, easterlies here
+        assert!(map[r * w + 28] < map[r * w + 36] * 0.6);
+    }
+
+    /// belt_strength saturates at BELT_WIDTH_SATURATE_KM (8 cells at 1024 wide).
     #[test]
     fn belt_strength_saturates() {
-        assert!((belt_strength(BELT_WIDTH_SATURATE) - 1.0).abs() < 1e-5);
-        assert!((belt_strength(BELT_WIDTH_SATURATE + 10) - 1.0).abs() < 1e-5);
+        let s = belt_saturate_cells(PlanetBody::EARTH.equatorial_cell_km(1024));
+        assert_eq!(s, 8);
+        assert_eq!(
+            belt_saturate_cells(PlanetBody::EARTH.equatorial_cell_km(4096)),
+            32
+        );
+        assert!((belt_strength(s, s) - 1.0).abs() < 1e-5);
+        assert!((belt_strength(s + 10, s) - 1.0).abs() < 1e-5);
     }
 
     /// Multipliers stay within Design Bible ranges.
     #[test]
     fn multipliers_within_design_bible_range() {
         for w in 1..=16 {
-            let wm = windward_mult(w);
-            let lm = leeward_mult(w);
+            let wm = windward_mult(w, 8);
+            let lm = leeward_mult(w, 8);
             assert!(
                 (WINDWARD_MIN..=WINDWARD_MAX).contains(&wm),
                 "windward_mult({w}) = {wm:.3} outside [1.5, 3.0]"
@@ -279,7 +309,7 @@ mod tests {
         let regime = mountain_at_col(w, h, 8);
         let base = 1000.0_f32;
         let mut map = vec![base; w * h];
-        apply_orographic_correction(&mut map, &regime, w, h);
+        apply_orographic_correction(&mut map, &regime, w, h, &PlanetBody::EARTH);
         for r in 0..h {
             let v = map[r * w + 8];
             assert!(
@@ -302,7 +332,7 @@ mod tests {
         };
         let base = 1000.0_f32;
         let mut map = vec![base; w * h];
-        apply_orographic_correction(&mut map, &regime, w, h);
+        apply_orographic_correction(&mut map, &regime, w, h, &PlanetBody::EARTH);
         for &v in &map {
             assert!(
                 (v - base).abs() < 1e-3,
@@ -320,7 +350,7 @@ mod tests {
             height: 0,
         };
         let mut map: Vec<f32> = vec![];
-        apply_orographic_correction(&mut map, &regime, 0, 0);
+        apply_orographic_correction(&mut map, &regime, 0, 0, &PlanetBody::EARTH);
         assert!(map.is_empty());
     }
 }
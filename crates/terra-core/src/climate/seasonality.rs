@@ -7,6 +7,12 @@
 //!
 //! This guarantees the roadmap constraint: no point has seasonality > 0.8
 //! when MAP > 2500 mm (physically, very wet = maritime/equatorial = low seasonal).
+//!
+//! The latitude contribution scales with `PlanetBody::seasonality_scale`: an
+//! upright planet is aseasonal, a high-obliquity one saturates at mid-latitudes.
+//! The MAP cap still holds up to 90° tilt (0.20 × 2.5 < 0.8).
+
+use crate::body::PlanetBody;
 
 /// Generate a seasonality field from the MAP field.
 ///
@@ -17,12 +23,14 @@ pub fn generate_seasonality(
     width: usize,
     height: usize,
     _climate_diversity: f32,
+    body: &PlanetBody,
 ) -> Vec<f32> {
     let n = width * height;
     if n == 0 || map_field.is_empty() {
         return Vec::new();
     }
 
+    let tilt_scale = body.seasonality_scale();
     let mut result = Vec::with_capacity(n);
 
     for r in 0..height {
@@ -32,7 +40,7 @@ pub fn generate_seasonality(
 
         // Latitude contribution: rises from 0 at equator to 1 at poles.
         // Exponent < 1 gives a gentle rise in mid-latitudes.
-        let lat_contribution = (lat_abs / 90.0).powf(0.7_f32);
+        let lat_contribution = (lat_abs / 90.0).powf(0.7_f32) * tilt_scale;
 
         for c in 0..width {
             let map_mm = map_field[r * width + c];
@@ -64,7 +72,7 @@ mod tests {
         let w = 64usize;
         let h = 64usize;
         let map = uniform_map(3000.0, w, h);
-        let s = generate_seasonality(&map, w, h, 0.70);
+        let s = generate_seasonality(&map, w, h, 0.70, &PlanetBody::EARTH);
         for (i, &v) in s.iter().enumerate() {
             assert!(
                 v <= 0.8,
@@ -79,7 +87,7 @@ mod tests {
         let w = 64usize;
         let h = 64usize;
         let map = uniform_map(800.0, w, h);
-        let s = generate_seasonality(&map, w, h, 0.70);
+        let s = generate_seasonality(&map, w, h, 0.70, &PlanetBody::EARTH);
 
         // Row 0 ≈ +90° (polar), row h/2 ≈ 0° (equatorial).
         let polar_s = s[0]; // row 0
@@ -93,7 +101,13 @@ mod tests {
     /// Output length matches grid size.
     #[test]
     fn output_length_matches_grid() {
-        let v = generate_seasonality(&uniform_map(800.0, 32, 16), 32, 16, 0.70);
+        let v = generate_seasonality(
+            &uniform_map(800.0, 32, 16),
+            32,
+            16,
+            0.70,
+            &PlanetBody::EARTH,
+        );
         assert_eq!(v.len(), 32 * 16);
     }
 
@@ -101,7 +115,7 @@ mod tests {
     #[test]
     fn values_in_unit_range() {
         let map = uniform_map(500.0, 64, 32);
-        let s = generate_seasonality(&map, 64, 32, 0.70);
+        let s = generate_seasonality(&map, 64, 32, 0.70, &PlanetBody::EARTH);
         for &v in &s {
             assert!((0.0..=1.0).contains(&v), "seasonality {v:.3} outside [0,1]");
         }
@@ -110,6 +124,6 @@ mod tests {
     /// Empty grid returns empty.
     #[test]
     fn empty_grid() {
-        assert!(generate_seasonality(&[], 0, 16, 0.70).is_empty());
+        assert!(generate_seasonality(&[], 0, 16, 0.70, &PlanetBody::EARTH).is_empty());
     }
 }
@@ -0,0 +1,183 @@
+//! Error type of the fallible (`try_*`) generation entry points.
+//!
+//! The infallible entry points (`PlanetGenerator::generate`,
+//! `OverviewPipeline::new`, …) keep their historical behaviour: sliders are
+//! clamped where a stage needs it and impossible inputs panic.  Their `try_*`
+//! counterparts check every input first and report the first problem found.
+
+use crate::coords::TileAddr;
+use crate::generator::RangeError;
+use crate::planet::addressed_tile::MAX_TILE_ZOOM;
+use crate::progress::Cancelled;
+
+/// Why a `try_*` entry point did not produce a result.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TerraError {
+    /// A slider or tuning knob outside its accepted range.
+    #[error(transparent)]
+    OutOfRange(RangeError),
+    /// A slider or tuning knob that is NaN or infinite.
+    #[error("{name} is not a finite number")]
+    NotFinite { name: String },
+    #[error("{grid} grid is {width} × {height}; both dimensions must be non-zero")]
+    EmptyGrid {
+        grid: &'static str,
+        width: usize,
+        height: usize,
+    },
+    #[error("{field} has {actual} values, expected {width} × {height} = {expected}")]
+    FieldSizeMismatch {
+        field: &'static str,
+        width: usize,
+        height: usize,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("{field} is {actual_width} × {actual_height}, expected {width} × {height}")]
+    GridMismatch {
+        field: &'static str,
+        width: usize,
+        height: usize,
+        actual_width: usize,
+        actual_height: usize,
+    },
+    /// Latitude outside [-90, 90] or longitude outside [-180, 180].
+    #[error("({lat}, {lon}) is not a latitude/longitude on the globe")]
+    InvalidLocation { lat: f32, lon: f32 },
+    /// Zoom above `MAX_TILE_ZOOM`, or x/y outside the 2^zoom × 2^zoom grid.
+    #[error("tile {zoom}/{x}/{y} is not on the globe")]
+    InvalidTile { zoom: u32, x: u32, y: u32 },
+    /// The run's cancel token was set.
+    #[error("generation cancelled")]
+    Cancelled,
+}
+
+impl From<RangeError> for TerraError {
+    fn from(e: RangeError) -> Self {
+        if e.value.is_finite() {
+            TerraError::OutOfRange(e)
+        } else {
+            TerraError::NotFinite { name: e.name }
+        }
+    }
+}
+
+impl From<Cancelled> for TerraError {
+    fn from(_: Cancelled) -> Self {
+        TerraError::Cancelled
+    }
+}
+
+/// Check that a `width × height` grid has cells.
+pub(crate) fn check_grid(
+    grid: &'static str,
+    width: usize,
+    height: usize,
+) -> Result<(), TerraError> {
+    if width == 0 || height == 0 {
+        return Err(TerraError::EmptyGrid {
+            grid,
+            width,
+            height,
+        });
+    }
+    Ok(())
+}
+
+/// Check that a row-major field covers a `width × height` grid.
+pub(crate) fn check_field_len(
+    field: &'static str,
+    actual: usize,
+    width: usize,
+    height: usize,
+) -> Result<(), TerraError> {
+    let expected = width * height;
+    if actual != expected {
+        return Err(TerraError::FieldSizeMismatch {
+            field,
+            width,
+            height,
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+/// Check that `(lat, lon)` is a point on the globe.
+pub fn check_location(lat: f32, lon: f32) -> Result<(), TerraError> {
+    if !(-90.0..=90.0).contains(&lat) || !(-180.0..=180.0).contains(&lon) {
+        return Err(TerraError::InvalidLocation { lat, lon });
+    }
+    Ok(())
+}
+
+/// Check that `addr` names a slippy-map tile on the globe.
+pub fn check_tile_addr(addr: TileAddr) -> Result<(), TerraError> {
+    let TileAddr { zoom, x, y } = addr;
+    if zoom > MAX_TILE_ZOOM || x >= 1 << zoom || y >= 1 << zoom {
+        return Err(TerraError::InvalidTile { zoom, x, y });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::GlobalParams;
+
+    #[test]
+    fn nan_slider_reported_as_not_finite() {
+        let params = GlobalParams {
+            glaciation: f32::NAN,
+            ..GlobalParams::default()
+        };
+        let err: TerraError = params.validate().unwrap_err().into();
+        assert_eq!(
+            err,
+            TerraError::NotFinite {
+                name: "glaciation".into()
+            }
+        );
+
+        let params = GlobalParams {
+            glaciation: 1.5,
+            ..GlobalParams::default()
+        };
+        let err: TerraError = params.validate().unwrap_err().into();
+        assert!(matches!(err, TerraError::OutOfRange(ref r) if r.name == "glaciation"));
+    }
+
+    #[test]
+    fn grid_field_and_location_checks() {
+        assert!(check_grid("overview", 8, 4).is_ok());
+        assert!(matches!(
+            check_grid("tile", 0, 4),
+            Err(TerraError::EmptyGrid { grid: "tile", .. })
+        ));
+        assert!(check_field_len("regime_field", 32, 8, 4).is_ok());
+        assert_eq!(
+            check_field_len("regime_field", 31, 8, 4)
+                .unwrap_err()
+                .to_string(),
+            "regime_field has 31 values, expected 8 × 4 = 32"
+        );
+        assert!(check_location(-90.0, 180.0).is_ok());
+        assert!(check_location(91.0, 0.0).is_err());
+        assert!(check_location(0.0, f32::NAN).is_err());
+        assert!(check_tile_addr(TileAddr::new(0, 0, 0)).is_ok());
+        assert!(check_tile_addr(TileAddr::new(3, 7, 7)).is_ok());
+        assert!(check_tile_addr(TileAddr::new(MAX_TILE_ZOOM, 0, 0)).is_ok());
+        assert_eq!(
+            check_tile_addr(TileAddr::new(3, 8, 0)),
+            Err(TerraError::InvalidTile {
+                zoom: 3,
+                x: 8,
+                y: 0
+            })
+        );
+        assert!(check_tile_addr(TileAddr::new(3, 0, 8)).is_err());
+        assert!(check_tile_addr(TileAddr::new(MAX_TILE_ZOOM + 1, 0, 0)).is_err());
+        assert!(check_tile_addr(TileAddr::new(40, 0, 0)).is_err());
+    }
+}
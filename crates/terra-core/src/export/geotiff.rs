@@ -0,0 +1,290 @@
+//! GeoTIFF writer for heightfields and planet fields.
+//!
+//! Produces single-band, uncompressed, north-up rasters georeferenced in
+//! EPSG:4326 (WGS 84 lat/lon) with pixel-is-area semantics:
+//!   - ModelTiepointTag maps raster (0, 0) to `(min_lon, max_lat)`;
+//!   - ModelPixelScaleTag holds the lon/lat extent of one pixel;
+//!   - GeoKeyDirectoryTag declares a geographic model and EPSG:4326;
+//!   - GDAL_NODATA carries the nodata value when one is given.
+//!
+//! Planet overview fields (`PlanetOverview::map_field`, `erodibility_field`,
+//! regimes as `regime as u8`, …) are already stored north-up and are written
+//! as-is with [`GeoBounds::GLOBAL`].  `HeightField` stores row 0 at `min_lat`
+//! and is flipped on write.
+//!
+//! [`write_heightfield`] assumes rows equally spaced in latitude, as in
+//! location tiles.  Addressed slippy-map tiles (`Planet::tile_for_addr`) have
+//! Web-Mercator-spaced rows and go through [`write_mercator_heightfield`],
+//! which resamples them onto equal latitude steps first.
+
+use std::io::{Seek, Write};
+
+use tiff::encoder::{colortype, DirectoryEncoder, TiffEncoder, TiffKind};
+use tiff::tags::Tag;
+
+use super::{check_len, ExportError, GeoBounds};
+use crate::heightfield::HeightField;
+
+/// Nodata value written for heightfields.
+pub const HEIGHTFIELD_NODATA: f32 = -32768.0;
+
+// GeoKey IDs and values (GeoTIFF 1.0 spec §6).
+const GT_MODEL_TYPE_GEO_KEY: u16 = 1024;
+const GT_RASTER_TYPE_GEO_KEY: u16 = 1025;
+const GEOGRAPHIC_TYPE_GEO_KEY: u16 = 2048;
+const MODEL_TYPE_GEOGRAPHIC: u16 = 2;
+const RASTER_PIXEL_IS_AREA: u16 = 1;
+const GCS_WGS_84: u16 = 4326;
+
+/// Write `hf` as a Float32 GeoTIFF with [`HEIGHTFIELD_NODATA`] as nodata.
+///
+/// Rows must be equally spaced in latitude; see [`write_mercator_heightfield`]
+/// for Web-Mercator-spaced tiles.
+pub fn write_heightfield<W: Write + Seek>(writer: W, hf: &HeightField) -> Result<(), ExportError> {
+    check_len(hf.data.len(), hf.width, hf.height)?;
+    let mut north_up = Vec::with_capacity(hf.data.len());
+    for r in (0..hf.height).rev() {
+        north_up.extend_from_slice(&hf.data[r * hf.width..(r + 1) * hf.width]);
+    }
+    write_f32_field(
+        writer,
+        &north_up,
+        hf.width,
+        hf.height,
+        GeoBounds::from_heightfield(hf),
+        Some(HEIGHTFIELD_NODATA),
+    )
+}
+
+/// Write `hf`, whose rows are spaced in Web Mercator, as a Float32 GeoTIFF.
+///
+/// Each output row is linearly interpolated between the two Mercator rows
+/// around its latitude, so the raster matches the EPSG:4326 pixel scale.
+pub fn write_mercator_heightfield<W: Write + Seek>(
+    writer: W,
+    hf: &HeightField,
+) -> Result<(), ExportError> {
+    check_len(hf.data.len(), hf.width, hf.height)?;
+    let mercator_y = |lat: f64| {
+        (std::f64::consts::FRAC_PI_4 + lat.to_radians() / 2.0)
+            .tan()
+            .ln()
+    };
+    let (y_min, y_max) = (mercator_y(hf.min_lat), mercator_y(hf.max_lat));
+    let lat_step = (hf.max_lat - hf.min_lat) / hf.height as f64;
+    let last_row = hf.height.saturating_sub(1) as f64;
+    let mut north_up = Vec::with_capacity(hf.data.len());
+    for out_row in 0..hf.height {
+        let lat = hf.max_lat - (out_row as f64 + 0.5) * lat_step;
+        let src = ((mercator_y(lat) - y_min) / (y_max - y_min) * hf.height as f64 - 0.5)
+            .clamp(0.0, last_row);
+        let (r0, t) = (src.floor() as usize, (src - src.floor()) as f32);
+        let r1 = (r0 + 1).min(hf.height - 1);
+        for c in 0..hf.width {
+            north_up.push(hf.get(r0, c) * (1.0 - t) + hf.get(r1, c) * t);
+        }
+    }
+    write_f32_field(
+        writer,
+        &north_up,
+        hf.width,
+        hf.height,
+        GeoBounds::from_heightfield(hf),
+        Some(HEIGHTFIELD_NODATA),
+    )
+}
+
+/// Write a north-up, row-major `width × height` f32 field as a Float32 GeoTIFF.
+pub fn write_f32_field<W: Write + Seek>(
+    writer: W,
+    data: &[f32],
+    width: usize,
+    height: usize,
+    bounds: GeoBounds,
+    nodata: Option<f32>,
+) -> Result<(), ExportError> {
+    check_len(data.len(), width, height)?;
+    let mut encoder = TiffEncoder::new(writer)?;
+    let mut image = encoder.new_image::<colortype::Gray32Float>(width as u32, height as u32)?;
+    write_geo_tags(
+        image.encoder(),
+        width,
+        height,
+        bounds,
+        nodata.map(|v| v.to_string()),
+    )?;
+    image.write_data(data)?;
+    Ok(())
+}
+
+/// Write a north-up, row-major `width × height` u8 field as a UInt8 GeoTIFF.
+pub fn write_u8_field<W: Write + Seek>(
+    writer: W,
+    data: &[u8],
+    width: usize,
+    height: usize,
+    bounds: GeoBounds,
+    nodata: Option<u8>,
+) -> Result<(), ExportError> {
+    check_len(data.len(), width, height)?;
+    let mut encoder = TiffEncoder::new(writer)?;
+    let mut image = encoder.new_image::<colortype::Gray8>(width as u32, height as u32)?;
+    write_geo_tags(
+        image.encoder(),
+        width,
+        height,
+        bounds,
+        nodata.map(|v| v.to_string()),
+    )?;
+    image.write_data(data)?;
+    Ok(())
+}
+
+fn write_geo_tags<W: Write + Seek, K: TiffKind>(
+    dir: &mut DirectoryEncoder<'_, W, K>,
+    width: usize,
+    height: usize,
+    bounds: GeoBounds,
+    nodata: Option<String>,
+) -> Result<(), ExportError> {
+    let scale_x = (bounds.max_lon - bounds.min_lon) / width as f64;
+    let scale_y = (bounds.max_lat - bounds.min_lat) / height as f64;
+    dir.write_tag(Tag::ModelPixelScaleTag, &[scale_x, scale_y, 0.0][..])?;
+    dir.write_tag(
+        Tag::ModelTiepointTag,
+        &[0.0, 0.0, 0.0, bounds.min_lon, bounds.max_lat, 0.0][..],
+    )?;
+
+    // Header: KeyDirectoryVersion, KeyRevision, MinorRevision, NumberOfKeys;
+    // then (KeyID, TIFFTagLocation = 0, Count = 1, Value) per key.
+    let geo_keys: [u16; 16] = [
+        1,
+        1,
+        0,
+        3,
+        GT_MODEL_TYPE_GEO_KEY,
+        0,
+        1,
+        MODEL_TYPE_GEOGRAPHIC,
+        GT_RASTER_TYPE_GEO_KEY,
+        0,
+        1,
+        RASTER_PIXEL_IS_AREA,
+        GEOGRAPHIC_TYPE_GEO_KEY,
+        0,
+        1,
+        GCS_WGS_84,
+    ];
+    dir.write_tag(Tag::GeoKeyDirectoryTag, &geo_keys[..])?;
+
+    if let Some(nodata) = nodata {
+        dir.write_tag(Tag::GdalNodata, nodata.as_str())?;
+    }
+    Ok(())
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coords::TileAddr;
+    use std::io::Cursor;
+    use tiff::decoder::{Decoder, DecodingResult};
+
+    fn decode(bytes: Vec<u8>) -> Decoder<Cursor<Vec<u8>>> {
+        Decoder::new(Cursor::new(bytes)).expect("valid TIFF")
+    }
+
+    /// Heightfield round-trips north-up with georeferencing and nodata tags.
+    #[test]
+    fn heightfield_roundtrip_is_north_up_and_georeferenced() {
+        // 3 × 2 field: row 0 (south) = 0..3, row 1 (north) = 10..13.
+        let mut hf = HeightField::new(3, 2, 10.0, 13.0, 40.0, 42.0, 0.0);
+        for c in 0..3 {
+            hf.set(0, c, c as f32);
+            hf.set(1, c, 10.0 + c as f32);
+        }
+        let mut buf = Cursor::new(Vec::new());
+        write_heightfield(&mut buf, &hf).unwrap();
+
+        let mut dec = decode(buf.into_inner());
+        assert_eq!(dec.dimensions().unwrap(), (3, 2));
+        match dec.read_image().unwrap() {
+            DecodingResult::F32(v) => assert_eq!(v, vec![10.0, 11.0, 12.0, 0.0, 1.0, 2.0]),
+            _ => panic!("expected Float32 samples"),
+        }
+
+        let scale = dec.get_tag_f64_vec(Tag::ModelPixelScaleTag).unwrap();
+        assert_eq!(scale, vec![1.0, 1.0, 0.0]);
+        let tie = dec.get_tag_f64_vec(Tag::ModelTiepointTag).unwrap();
+        assert_eq!(tie, vec![0.0, 0.0, 0.0, 10.0, 42.0, 0.0]);
+        let keys = dec.get_tag_u16_vec(Tag::GeoKeyDirectoryTag).unwrap();
+        assert_eq!(keys.last(), Some(&GCS_WGS_84));
+        let nodata = dec.get_tag_ascii_string(Tag::GdalNodata).unwrap();
+        assert_eq!(nodata.trim_end_matches('\0'), "-32768");
+    }
+
+    /// UInt8 fields keep their values and carry no nodata tag unless asked.
+    #[test]
+    fn u8_field_roundtrip() {
+        let data: Vec<u8> = (0..8).collect();
+        let mut buf = Cursor::new(Vec::new());
+        write_u8_field(&mut buf, &data, 4, 2, GeoBounds::GLOBAL, None).unwrap();
+
+        let mut dec = decode(buf.into_inner());
+        match dec.read_image().unwrap() {
+            DecodingResult::U8(v) => assert_eq!(v, data),
+            _ => panic!("expected UInt8 samples"),
+        }
+        assert_eq!(
+            dec.get_tag_f64_vec(Tag::ModelPixelScaleTag).unwrap(),
+            vec![90.0, 90.0, 0.0]
+        );
+        assert!(dec.find_tag(Tag::GdalNodata).unwrap().is_none());
+    }
+
+    /// Mercator rows land at their latitude in the EPSG:4326 raster.
+    #[test]
+    fn mercator_heightfield_is_resampled_to_equal_latitude_rows() {
+        let (lat_min, lon_min, lat_max, lon_max) = TileAddr::new(2, 1, 0).bounds();
+        let (width, height) = (2, 64);
+        let mut hf = HeightField::new(width, height, lon_min, lon_max, lat_min, lat_max, 0.0);
+        // Each row holds the latitude of its Web Mercator row centre.
+        for r in 0..height {
+            let t = 1.0 - (r as f64 + 0.5) / height as f64;
+            let lat = (std::f64::consts::PI * (1.0 - 2.0 * t / 4.0))
+                .sinh()
+                .atan()
+                .to_degrees();
+            for c in 0..width {
+                hf.set(r, c, lat as f32);
+            }
+        }
+        let mut buf = Cursor::new(Vec::new());
+        write_mercator_heightfield(&mut buf, &hf).unwrap();
+
+        let DecodingResult::F32(v) = decode(buf.into_inner()).read_image().unwrap() else {
+            panic!("expected Float32 samples");
+        };
+        let step = (lat_max - lat_min) / height as f64;
+        for row in 1..height - 1 {
+            let expected = lat_max - (row as f64 + 0.5) * step;
+            let got = f64::from(v[row * width]);
+            assert!(
+                (got - expected).abs() < 0.05,
+                "row {row}: {got} vs {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn size_mismatch_is_rejected() {
+        let mut buf = Cursor::new(Vec::new());
+        let err = write_f32_field(&mut buf, &[0.0; 5], 3, 2, GeoBounds::GLOBAL, None);
+        assert!(matches!(
+            err,
+            Err(ExportError::SizeMismatch { expected: 6, .. })
+        ));
+    }
+}
@@ -0,0 +1,66 @@
+//! File export of generated terrain and planet fields.
+//!
+//! Writers take any `std::io::Write + Seek` sink so that native callers can
+//! stream to a `File` and WASM callers to an in-memory `Cursor<Vec<u8>>`.
+
+pub mod geotiff;
+
+use crate::heightfield::HeightField;
+
+/// Error returned by the export writers.
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("TIFF encoding error: {0}")]
+    Tiff(#[from] tiff::TiffError),
+    #[error("field has {actual} values, expected {width} × {height} = {expected}")]
+    SizeMismatch {
+        width: usize,
+        height: usize,
+        expected: usize,
+        actual: usize,
+    },
+}
+
+/// Geographic extent of an equirectangular raster, in degrees (EPSG:4326).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoBounds {
+    pub min_lon: f64,
+    pub max_lon: f64,
+    pub min_lat: f64,
+    pub max_lat: f64,
+}
+
+impl GeoBounds {
+    /// Whole-globe extent used by the planet overview fields.
+    pub const GLOBAL: Self = Self {
+        min_lon: -180.0,
+        max_lon: 180.0,
+        min_lat: -90.0,
+        max_lat: 90.0,
+    };
+
+    pub fn from_heightfield(hf: &HeightField) -> Self {
+        Self {
+            min_lon: hf.min_lon,
+            max_lon: hf.max_lon,
+            min_lat: hf.min_lat,
+            max_lat: hf.max_lat,
+        }
+    }
+}
+
+pub(crate) fn check_len(len: usize, width: usize, height: usize) -> Result<(), ExportError> {
+    let expected = width * height;
+    if len == expected {
+        Ok(())
+    } else {
+        Err(ExportError::SizeMismatch {
+            width,
+            height,
+            expected,
+            actual: len,
+        })
+    }
+}
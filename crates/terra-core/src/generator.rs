@@ -1,31 +1,78 @@
 //! Pipeline orchestrator: runs all generation stages in order.
 //! Phase 7, Task P7.1.
 
-use crate::climate::{latitude_bands::map_base_mm, simulate_climate};
+use crate::body::PlanetBody;
+use crate::climate::{latitude_bands::map_base_mm, simulate_climate_with, ClimateLayer};
+use crate::error::{check_grid, check_location, TerraError};
 use crate::heightfield::HeightField;
-use crate::hydraulic::apply_hydraulic_shaping;
+use crate::hydraulic::{apply_hydraulic_shaping_with, HydraulicTuning};
 use crate::metrics::score::{compute_realism_score, RealismScore};
 use crate::noise::{
     generate_tile,
     params::{GlacialClass, NoiseParams, TerrainClass},
 };
+use crate::planet::field_smoothing::SmoothingParams;
 use crate::plates::{
     continents::CrustType, plate_generation::plate_count_from_fragmentation,
-    regime_field::TectonicRegime, simulate_plates,
+    regime_field::TectonicRegime, simulate_plates_with, PlateSimulation, PlateTuning,
 };
+use crate::progress::{self, Cancelled, RunControl};
+use crate::trace::{FeatureCounts, PipelineTrace};
 use serde::{Deserialize, Serialize};
 
+/// Version of the generation algorithms.
+///
+/// Bumped by every change that alters generated output for the same seed,
+/// params and config, i.e. every change that re-blesses the `golden/`
+/// manifests.  Save files and manifests record it, so a reader can tell
+/// whether this build regenerates what they hold.
+pub const OUTPUT_VERSION: u32 = 8;
+
 // ── Grid size ─────────────────────────────────────────────────────────────────
 
-/// Internal generation resolution: equirectangular 2:1 ratio.
+/// Default tile resolution: equirectangular 2:1 ratio.
 pub const GRID_WIDTH: usize = 512;
 pub const GRID_HEIGHT: usize = 256;
 
+/// Grid resolutions used by the generation pipelines.
+///
+/// The overview grid drives the plate and climate simulations, the planet
+/// overview and everything sampled from it; the tile grid sizes the output of
+/// `PlanetGenerator::generate` and of drill-down tiles.  Scoring derives its
+/// cell size from the heightfield bounds, so it follows the tile grid.
+/// Both grids are expected to be 2:1 equirectangular.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationConfig {
+    pub overview_width: usize,
+    pub overview_height: usize,
+    pub tile_width: usize,
+    pub tile_height: usize,
+}
+
+impl Default for GenerationConfig {
+    fn default() -> Self {
+        Self {
+            overview_width: crate::planet::OVERVIEW_WIDTH,
+            overview_height: crate::planet::OVERVIEW_HEIGHT,
+            tile_width: GRID_WIDTH,
+            tile_height: GRID_HEIGHT,
+        }
+    }
+}
+
+impl GenerationConfig {
+    /// Check that both grids have cells.
+    pub fn validate(&self) -> Result<(), TerraError> {
+        check_grid("overview", self.overview_width, self.overview_height)?;
+        check_grid("tile", self.tile_width, self.tile_height)
+    }
+}
+
 // ── Public structs ────────────────────────────────────────────────────────────
 
 /// User-facing global parameters (8 sliders + seed).
 /// Defaults are calibrated to Earth-like values (P7.3 spec).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GlobalParams {
     pub seed: u64,
     /// 0-1, default 0.5. Proportion of active vs. cratonic terrain.
@@ -42,6 +89,12 @@ pub struct GlobalParams {
     pub continental_fragmentation: f32,
     /// 0-1, default 0.50. Relative area of high-relief terrain.
     pub mountain_prevalence: f32,
+    /// Radius, tilt, rotation and gravity; Earth unless set.
+    #[serde(default)]
+    pub body: PlanetBody,
+    /// Internal knobs behind the sliders; see [`Tuning`].
+    #[serde(default)]
+    pub tuning: Tuning,
 }
 
 impl Default for GlobalParams {
@@ -55,25 +108,142 @@ impl Default for GlobalParams {
             glaciation: 0.30,
             continental_fragmentation: 0.50,
             mountain_prevalence: 0.50,
+            body: PlanetBody::EARTH,
+            tuning: Tuning::default(),
         }
     }
 }
 
+impl GlobalParams {
+    /// Check every slider, body property and tuning knob against its
+    /// accepted range.
+    ///
+    /// Sliders are in [0, 1]; the body and tuning ranges keep each stage
+    /// within the regime it was calibrated for.  Reports the first violation in
+    /// declaration order, named by its recipe key.
+    pub fn validate(&self) -> Result<(), RangeError> {
+        let sliders = [
+            ("tectonic_activity", self.tectonic_activity),
+            ("water_abundance", self.water_abundance),
+            ("surface_age", self.surface_age),
+            ("climate_diversity", self.climate_diversity),
+            ("glaciation", self.glaciation),
+            ("continental_fragmentation", self.continental_fragmentation),
+            ("mountain_prevalence", self.mountain_prevalence),
+        ];
+        for (name, v) in sliders {
+            check_range(name, v as f64, 0.0, 1.0)?;
+        }
+        self.body.validate()?;
+
+        let plates = &self.tuning.plates;
+        check_range("tuning.plates.hotspots", plates.hotspots as f64, 0.0, 32.0)?;
+        check_range(
+            "tuning.plates.plume_strength",
+            plates.plume_strength as f64,
+            0.0,
+            3.0,
+        )?;
+        check_range(
+            "tuning.plates.continental_coverage",
+            plates.continental_coverage as f64,
+            0.05,
+            0.80,
+        )?;
+        check_range(
+            "tuning.plates.warp_amplitude_deg",
+            plates.warp_amplitude_deg,
+            0.0,
+            20.0,
+        )?;
+
+        let smoothing = &self.tuning.smoothing;
+        let sigmas = [
+            ("regime_sigma_km", smoothing.regime_sigma_km),
+            ("climate_sigma_km", smoothing.climate_sigma_km),
+            ("erodibility_sigma_km", smoothing.erodibility_sigma_km),
+        ];
+        for (name, v) in sigmas {
+            check_range(&format!("tuning.smoothing.{name}"), v as f64, 0.0, 5000.0)?;
+        }
+
+        for (class, hp) in self.tuning.hydraulic.classes() {
+            let key = |field: &str| format!("tuning.hydraulic.{class}.{field}");
+            check_range(&key("a_min"), hp.a_min as f64, 1.0, 10_000.0)?;
+            check_range(&key("erosion_iters"), hp.erosion_iters as f64, 0.0, 200.0)?;
+            check_range(
+                &key("angle_of_repose_deg"),
+                hp.angle_of_repose_deg as f64,
+                5.0,
+                60.0,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// `RangeError` unless `value` is in `[min, max]` (NaN never is).
+pub(crate) fn check_range(name: &str, value: f64, min: f64, max: f64) -> Result<(), RangeError> {
+    if (min..=max).contains(&value) {
+        return Ok(());
+    }
+    // Round-trip through the f32 text so messages show `1.2`, not
+    // `1.2000000476837158`.
+    let value = (value as f32).to_string().parse().unwrap_or(value);
+    Err(RangeError {
+        name: name.to_owned(),
+        value,
+        min,
+        max,
+    })
+}
+
+/// A parameter outside its accepted range.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("{name} must be in [{min}, {max}], got {value}")]
+pub struct RangeError {
+    /// Recipe key of the parameter, e.g. `tuning.plates.hotspots`.
+    pub name: String,
+    pub value: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+/// Knobs that no slider drives, exposed for recipes.
+///
+/// Defaults are the calibrated constants of each stage, so leaving `tuning`
+/// out of a params file or JS object reproduces the slider-only pipeline.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Tuning {
+    /// Hotspot count, continental coverage and plate boundary warp.
+    pub plates: PlateTuning,
+    /// Overview field smoothing radii.
+    pub smoothing: SmoothingParams,
+    /// Per-terrain-class hydraulic shaping schedules.
+    pub hydraulic: HydraulicTuning,
+}
+
 /// Full output of the planet generation pipeline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlanetResult {
     pub heightfield: HeightField,
-    /// Flattened tectonic regime field, row-major, GRID_WIDTH × GRID_HEIGHT.
+    /// Flattened tectonic regime field, row-major, tile_width × tile_height.
     pub regime_field: Vec<TectonicRegime>,
-    /// Mean annual precipitation (mm/yr), row-major, GRID_WIDTH × GRID_HEIGHT.
+    /// Mean annual precipitation (mm/yr), row-major, tile_width × tile_height.
     pub map_field: Vec<f32>,
     pub score: RealismScore,
     pub generation_time_ms: u64,
+    /// Stage timings and the resolved parameters this run consumed.
+    #[serde(default)]
+    pub trace: PipelineTrace,
 }
 
 // ── Debug params ─────────────────────────────────────────────────────────────
 
 /// Lightweight resolved-parameter snapshot for slider audit / diagnostics.
-/// Computed analytically without running the full generation pipeline.
+/// Computed analytically without running the full generation pipeline;
+/// `PlanetResult::trace` records what a run actually used.
 #[derive(Debug, Clone, Serialize)]
 pub struct DebugParams {
     pub terrain_class: String,
@@ -100,14 +270,7 @@ pub fn derive_debug_params(p: &GlobalParams) -> DebugParams {
     let h_base = (0.65 + p.mountain_prevalence * 0.20 - p.surface_age * 0.10).clamp(0.55, 0.90);
     let h_variance = (0.10 + p.climate_diversity * 0.15).clamp(0.10, 0.25);
 
-    // Per-class erosion iteration counts (mirror hydraulic::params_for_class).
-    let erosion_iterations = match terrain_class {
-        TerrainClass::Alpine => 30,
-        TerrainClass::FluvialHumid => 50,
-        TerrainClass::FluvialArid => 20,
-        TerrainClass::Cratonic => 10,
-        TerrainClass::Coastal => 25,
-    };
+    let erosion_iterations = p.tuning.hydraulic.for_class(terrain_class).erosion_iters;
 
     // Plate parameters (analytical — no simulation).
     let n_plates = plate_count_from_fragmentation(p.continental_fragmentation);
@@ -117,7 +280,7 @@ pub fn derive_debug_params(p: &GlobalParams) -> DebugParams {
     let mountain_height_scale = 0.7 + p.mountain_prevalence * 0.6;
 
     // Climate (equatorial MAP, analytical).
-    let map_base_mm_equator = map_base_mm(0.0, p.water_abundance);
+    let map_base_mm_equator = map_base_mm(0.0, p.water_abundance, &p.body);
 
     // Erosion factor applied to erodibility field.
     let water_scale = 0.3 + p.water_abundance * 1.4;
@@ -147,7 +310,7 @@ pub fn derive_debug_params(p: &GlobalParams) -> DebugParams {
 }
 
 /// Direct slider → GlacialClass (used for both debug and generation).
-fn direct_glacial_class(glaciation: f32) -> GlacialClass {
+pub(crate) fn direct_glacial_class(glaciation: f32) -> GlacialClass {
     if glaciation > 0.65 {
         GlacialClass::Active
     } else if glaciation > 0.25 {
@@ -159,12 +322,33 @@ fn direct_glacial_class(glaciation: f32) -> GlacialClass {
 
 // ── Orchestrator ──────────────────────────────────────────────────────────────
 
+/// Stages of `PlanetGenerator::generate` and their typical share of its run
+/// time at the default resolution.
+const GENERATE_STAGES: &[(&str, f32)] = &[
+    ("plates", 0.30),
+    ("climate", 0.02),
+    ("noise", 0.13),
+    ("hydraulic", 0.46),
+    ("scoring", 0.09),
+];
+
 /// The main pipeline orchestrator.
-pub struct PlanetGenerator;
+pub struct PlanetGenerator {
+    config: GenerationConfig,
+}
 
 impl PlanetGenerator {
     pub fn new() -> Self {
-        Self
+        Self::with_config(GenerationConfig::default())
+    }
+
+    /// Orchestrator generating at the tile resolution of `config`.
+    pub fn with_config(config: GenerationConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn config(&self) -> &GenerationConfig {
+        &self.config
     }
 
     /// Run the full generation pipeline for the given parameters.
@@ -176,35 +360,72 @@ impl PlanetGenerator {
     ///   4. Hydraulic shaping
     ///   5. Realism scoring
     pub fn generate(&self, params: &GlobalParams) -> PlanetResult {
+        self.run_traced(params, &mut RunControl::new())
+            .expect("a run without a shared cancel token is never cancelled")
+    }
+
+    /// `generate` after validating `params` and the grid config, reporting
+    /// each stage to `control`'s observer and stopping early if its cancel
+    /// token is set.
+    pub fn try_generate(
+        &self,
+        params: &GlobalParams,
+        control: &mut RunControl,
+    ) -> Result<PlanetResult, TerraError> {
+        params.validate()?;
+        self.config.validate()?;
+        Ok(self.run_traced(params, control)?)
+    }
+
+    /// `run_stages` as a progress run, with the stage times in its trace.
+    fn run_traced(
+        &self,
+        params: &GlobalParams,
+        control: &mut RunControl,
+    ) -> Result<PlanetResult, Cancelled> {
+        let (mut result, stages) =
+            progress::run(control, GENERATE_STAGES, || self.run_stages(params))?;
+        result.trace.stages = stages;
+        Ok(result)
+    }
+
+    fn run_stages(&self, params: &GlobalParams) -> Result<PlanetResult, Cancelled> {
+        let (width, height) = (self.config.tile_width, self.config.tile_height);
+
         // ── 1. Plate simulation ─────────────────────────────────────────────
-        let plates = simulate_plates(
+        progress::stage("plates")?;
+        let plates = simulate_plates_with(
             params.seed,
             params.continental_fragmentation,
             params.mountain_prevalence,
-            GRID_WIDTH,
-            GRID_HEIGHT,
+            &params.tuning.plates,
+            &params.body,
+            width,
+            height,
         );
 
         // ── 2. Climate layer ────────────────────────────────────────────────
-        let climate = simulate_climate(
-            params.seed ^ 0x5A5A,
+        progress::stage("climate")?;
+        let climate = simulate_climate_with(
+            params.seed,
             params.water_abundance,
             params.climate_diversity,
             params.glaciation,
             &plates.regime_field,
-            GRID_WIDTH,
-            GRID_HEIGHT,
+            &params.body,
+            width,
+            height,
         );
 
         // ── 3. Noise synthesis ──────────────────────────────────────────────
+        progress::stage("noise")?;
         let noise_params = derive_noise_params(params, &plates, &climate);
 
-        let seed32 = (params.seed & 0xFFFF_FFFF) as u32;
         let mut hf = generate_tile(
             &noise_params,
-            seed32,
-            GRID_WIDTH,
-            GRID_HEIGHT,
+            params.seed,
+            width,
+            height,
             -180.0,
             180.0,
             -90.0,
@@ -214,14 +435,16 @@ impl PlanetGenerator {
         // ── Tectonic uplift + mountain height scaling ───────────────────────
         // tectonic_activity: more active tectonics → higher relief (0.5× to 2.0×).
         // mountain_prevalence: additional direct height scale (0.7× to 1.3×).
+        // Lower surface gravity supports proportionally more relief.
         let tectonic_uplift = 0.5 + params.tectonic_activity * 1.5;
         let mountain_scale = 0.7 + params.mountain_prevalence * 0.6;
-        let total_uplift = tectonic_uplift * mountain_scale;
+        let total_uplift = tectonic_uplift * mountain_scale * params.body.relief_scale();
         for v in &mut hf.data {
             *v *= total_uplift;
         }
 
         // ── 4. Hydraulic shaping ────────────────────────────────────────────
+        progress::stage("hydraulic")?;
         // Erosion intensity scales with water_abundance (more water → more erosion)
         // and surface_age (older terrain → more cumulative erosion).
         let water_scale = 0.3 + params.water_abundance * 1.4;
@@ -236,18 +459,36 @@ impl PlanetGenerator {
         // Glacial class: direct slider threshold (not climate-mask-derived, which
         // has too high a threshold to trigger at low slider values).
         let glacial_class = direct_glacial_class(params.glaciation);
+        let hydraulic = *params
+            .tuning
+            .hydraulic
+            .for_class(noise_params.terrain_class);
 
-        apply_hydraulic_shaping(
-            &mut hf,
-            noise_params.terrain_class,
-            &scaled_erodibility,
-            glacial_class,
-        );
+        let shaped =
+            apply_hydraulic_shaping_with(&mut hf, &hydraulic, &scaled_erodibility, glacial_class);
 
         // ── 5. Realism scoring ──────────────────────────────────────────────
+        progress::stage("scoring")?;
         let score = compute_realism_score(&hf, noise_params.terrain_class);
 
-        PlanetResult {
+        let trace = PipelineTrace {
+            stages: Vec::new(), // filled in by `run_traced`
+            terrain_class: Some(noise_params.terrain_class),
+            glacial_class: Some(glacial_class),
+            noise: Some(noise_params),
+            hydraulic: Some(hydraulic),
+            relief_scale: Some(total_uplift),
+            erosion_factor: Some(erosion_factor),
+            sea_level_km: None,
+            counts: FeatureCounts {
+                plates: plates.n_plates,
+                hotspots: plates.hotspots.len(),
+                continents: plates.n_continents,
+                basins: Some(shaped.basins.len()),
+            },
+        };
+
+        Ok(PlanetResult {
             heightfield: hf,
             regime_field: plates.regime_field.data,
             map_field: climate.map_field,
@@ -255,7 +496,8 @@ impl PlanetGenerator {
             // Timing measured by the caller (WASM layer uses js_sys::Date::now();
             // native callers may set this themselves if needed).
             generation_time_ms: 0,
-        }
+            trace,
+        })
     }
 }
 
@@ -345,7 +587,7 @@ fn derive_noise_params(
 ///   4. Coastal     — water_abundance > 0.70 AND mountain_prevalence < 0.25
 ///      (low-relief, water-rich; analogous to DB §12.2 mean_elev<200m + humid)
 ///   5. FluvialHumid — catch-all
-fn classify_terrain(params: &GlobalParams) -> TerrainClass {
+pub(crate) fn classify_terrain(params: &GlobalParams) -> TerrainClass {
     if params.mountain_prevalence > 0.65 {
         TerrainClass::Alpine
     } else if params.mountain_prevalence < 0.20 && params.tectonic_activity < 0.30 {
@@ -365,9 +607,9 @@ fn classify_terrain(params: &GlobalParams) -> TerrainClass {
 pub struct LocationTileResult {
     /// Generated heightfield at the clicked location.
     pub heightfield: HeightField,
-    /// Flattened regime field, GRID_WIDTH × GRID_HEIGHT.
+    /// Flattened regime field, same grid as `heightfield`.
     pub regime_field: Vec<TectonicRegime>,
-    /// MAP field (mm/yr), GRID_WIDTH × GRID_HEIGHT.
+    /// MAP field (mm/yr), same grid as `heightfield`.
     pub map_field: Vec<f32>,
     /// Realism score for this tile.
     pub score: RealismScore,
@@ -416,90 +658,163 @@ fn classify_terrain_local(regime: TectonicRegime, map_mm: f32) -> TerrainClass {
     }
 }
 
+/// Planet spatial fields sampled at a single lat/lon (Phase B, PB.3).
+///
+/// This is the per-click input of the tile pipeline: everything the tile
+/// needs from the plate and climate layers, resolved to one overview cell.
+#[derive(Debug, Clone, Copy)]
+pub struct LocationSample {
+    pub lat: f32,
+    pub lon: f32,
+    pub terrain_class: TerrainClass,
+    pub regime: TectonicRegime,
+    pub map_mm: f32,
+    pub erodibility: f32,
+    pub grain_angle: f32,
+    pub grain_intensity: f32,
+    pub crust: CrustType,
+    pub glaciation: GlacialClass,
+}
+
+/// Row-major index of the equirectangular `w × h` cell containing `(lat, lon)`.
+pub(crate) fn latlon_to_cell(lat: f32, lon: f32, w: usize, h: usize) -> usize {
+    let col = ((lon + 180.0) / 360.0 * w as f32).clamp(0.0, (w - 1) as f32) as usize;
+    let row = ((90.0 - lat) / 180.0 * h as f32).clamp(0.0, (h - 1) as f32) as usize;
+    row * w + col
+}
+
+/// Sample the plate and climate fields at the cell containing `(lat, lon)`.
+///
+/// `plates` and `climate` must share the same grid; any resolution works,
+/// the lat/lon is mapped onto whatever grid the fields were simulated at.
+pub fn sample_location(
+    plates: &PlateSimulation,
+    climate: &ClimateLayer,
+    lat: f32,
+    lon: f32,
+) -> LocationSample {
+    let idx = latlon_to_cell(lat, lon, plates.width, plates.height);
+
+    let regime = plates.regime_field.data[idx];
+    let map_mm = climate.map_field[idx];
+
+    LocationSample {
+        lat,
+        lon,
+        terrain_class: classify_terrain_local(regime, map_mm),
+        regime,
+        map_mm,
+        erodibility: plates.erodibility_field[idx],
+        grain_angle: plates.grain_field.angles[idx],
+        grain_intensity: plates.grain_field.intensities[idx],
+        crust: plates.crust_field[idx],
+        glaciation: climate.glaciation_mask[idx],
+    }
+}
+
 /// Generate a tile characterised by the planet fields at a specific lat/lon.
 ///
 /// Runs the full planet simulation at overview resolution, samples the
 /// spatial fields (regime, MAP, erodibility, grain) at the clicked cell,
 /// then runs the tile pipeline at `GRID_WIDTH × GRID_HEIGHT`.
+///
+/// Each call pays the full plate + climate cost; use
+/// [`crate::planet::Planet::tile_at`] to drill down repeatedly into one planet.
 pub fn generate_at_location(params: &GlobalParams, lat: f32, lon: f32) -> LocationTileResult {
-    use crate::planet::{OVERVIEW_HEIGHT, OVERVIEW_WIDTH};
+    generate_at_location_with_config(params, &GenerationConfig::default(), lat, lon)
+}
 
+/// `generate_at_location` at the overview and tile resolutions of `config`.
+pub fn generate_at_location_with_config(
+    params: &GlobalParams,
+    config: &GenerationConfig,
+    lat: f32,
+    lon: f32,
+) -> LocationTileResult {
     // ── 1. Planet simulation at overview resolution ─────────────────────
-    let plates = simulate_plates(
-        params.seed,
-        params.continental_fragmentation,
-        params.mountain_prevalence,
-        OVERVIEW_WIDTH,
-        OVERVIEW_HEIGHT,
-    );
-    let climate = simulate_climate(
-        params.seed ^ 0x5A5A,
-        params.water_abundance,
-        params.climate_diversity,
-        params.glaciation,
-        &plates.regime_field,
-        OVERVIEW_WIDTH,
-        OVERVIEW_HEIGHT,
-    );
+    let (plates, climate) = crate::planet::simulate_planet_layers(params, config);
+
+    // ── 2. Sample local fields at the clicked cell ──────────────────────
+    let sample = sample_location(&plates, &climate, lat, lon);
+
+    generate_tile_for_sample(params, config, &sample)
+}
 
-    // ── 2. Map lat/lon → overview grid cell ─────────────────────────────
-    let col = ((lon + 180.0) / 360.0 * OVERVIEW_WIDTH as f32)
-        .clamp(0.0, (OVERVIEW_WIDTH - 1) as f32) as usize;
-    let row = ((90.0 - lat) / 180.0 * OVERVIEW_HEIGHT as f32)
-        .clamp(0.0, (OVERVIEW_HEIGHT - 1) as f32) as usize;
-    let idx = row * OVERVIEW_WIDTH + col;
-
-    // ── 3. Sample local fields ───────────────────────────────────────────
-    let local_regime = plates.regime_field.data[idx];
-    let local_map_mm = climate.map_field[idx];
-    let local_erodibility = plates.erodibility_field[idx];
-    let local_grain_angle = plates.grain_field.angles[idx];
-    let local_grain_intensity = plates.grain_field.intensities[idx];
-    let local_crust = plates.crust_field[idx];
-    let local_glaciation = climate.glaciation_mask[idx];
-
-    let terrain_class = classify_terrain_local(local_regime, local_map_mm);
-
-    // ── 4. Build NoiseParams from local values + global slider params ────
+/// `generate_at_location_with_config` after validating the params, grids
+/// and location.
+pub fn try_generate_at_location(
+    params: &GlobalParams,
+    config: &GenerationConfig,
+    lat: f32,
+    lon: f32,
+) -> Result<LocationTileResult, TerraError> {
+    params.validate()?;
+    config.validate()?;
+    check_location(lat, lon)?;
+    Ok(generate_at_location_with_config(params, config, lat, lon))
+}
+
+/// Build the tile `NoiseParams` from a location sample and the global sliders.
+pub(crate) fn noise_params_for_sample(
+    params: &GlobalParams,
+    sample: &LocationSample,
+) -> NoiseParams {
     let h_base =
         (0.65 + params.mountain_prevalence * 0.20 - params.surface_age * 0.10).clamp(0.55, 0.90);
     let h_variance = (0.10 + params.climate_diversity * 0.15).clamp(0.10, 0.25);
     let tectonic_grain_scale = 0.3 + params.tectonic_activity * 1.4;
     let age_grain_scale = 1.0 - params.surface_age * 0.40;
     let grain_intensity = {
-        let raw = (local_grain_intensity * tectonic_grain_scale * age_grain_scale).clamp(0.0, 1.0);
+        let raw = (sample.grain_intensity * tectonic_grain_scale * age_grain_scale).clamp(0.0, 1.0);
         // Oceanic ActiveExtensional cells (island arcs, thin oceanic rifts) receive
         // the same grain_intensity as continental AE (major rift valleys) but their
         // near-arc geometry can produce coherence values approaching 1.0.  At that
         // level the noise kernel becomes so elongated that uniform diagonal striping
         // appears across the full tile.  Cap at 0.55 for oceanic AE so that grain
         // is a visible directional tendency rather than parallel wall-to-wall lines.
-        if local_regime == TectonicRegime::ActiveExtensional && local_crust == CrustType::Oceanic {
+        if sample.regime == TectonicRegime::ActiveExtensional && sample.crust == CrustType::Oceanic
+        {
             raw.min(0.55)
         } else {
             raw
         }
     };
 
-    let noise_params = crate::noise::params::NoiseParams {
-        terrain_class,
+    NoiseParams {
+        terrain_class: sample.terrain_class,
         h_base,
         h_variance,
-        grain_angle: local_grain_angle,
+        grain_angle: sample.grain_angle,
         grain_intensity,
-        map_mm: local_map_mm,
+        map_mm: sample.map_mm,
         surface_age: params.surface_age,
-        erodibility: local_erodibility,
-        glacial_class: local_glaciation,
-    };
+        erodibility: sample.erodibility,
+        glacial_class: sample.glaciation,
+    }
+}
 
-    // ── 5. Generate tile at standard resolution ──────────────────────────
-    let seed32 = (params.seed & 0xFFFF_FFFF) as u32;
+/// Run the tile pipeline for an already-sampled location.
+///
+/// Shared by `generate_at_location` and `Planet::tile_at`; does not touch the
+/// plate or climate simulations.  The tile is `config.tile_width × tile_height`.
+pub(crate) fn generate_tile_for_sample(
+    params: &GlobalParams,
+    config: &GenerationConfig,
+    sample: &LocationSample,
+) -> LocationTileResult {
+    let terrain_class = sample.terrain_class;
+    let (width, height) = (config.tile_width, config.tile_height);
+    let n = width * height;
+
+    // ── 1. Build NoiseParams from local values + global slider params ────
+    let noise_params = noise_params_for_sample(params, sample);
+
+    // ── 2. Generate tile at standard resolution ──────────────────────────
     let mut hf = crate::noise::generate_tile(
         &noise_params,
-        seed32,
-        GRID_WIDTH,
-        GRID_HEIGHT,
+        params.seed,
+        width,
+        height,
         -180.0,
         180.0,
         -90.0,
@@ -508,32 +823,32 @@ pub fn generate_at_location(params: &GlobalParams, lat: f32, lon: f32) -> Locati
 
     let tectonic_uplift = 0.5 + params.tectonic_activity * 1.5;
     let mountain_scale = 0.7 + params.mountain_prevalence * 0.6;
+    let total_uplift = tectonic_uplift * mountain_scale * params.body.relief_scale();
     for v in &mut hf.data {
-        *v *= tectonic_uplift * mountain_scale;
+        *v *= total_uplift;
     }
 
-    // ── 6. Hydraulic shaping ─────────────────────────────────────────────
+    // ── 3. Hydraulic shaping ─────────────────────────────────────────────
     let water_scale = 0.3 + params.water_abundance * 1.4;
     let age_scale = 0.3 + params.surface_age * 1.4;
     let erosion_factor = (water_scale * age_scale).clamp(0.05, 2.0);
 
     // Use a uniform erodibility field scaled by the local value.
-    let scaled_erodibility =
-        vec![(local_erodibility * erosion_factor).clamp(0.0, 1.0); GRID_WIDTH * GRID_HEIGHT];
+    let scaled_erodibility = vec![(sample.erodibility * erosion_factor).clamp(0.0, 1.0); n];
 
-    apply_hydraulic_shaping(
+    apply_hydraulic_shaping_with(
         &mut hf,
-        terrain_class,
+        params.tuning.hydraulic.for_class(terrain_class),
         &scaled_erodibility,
-        local_glaciation,
+        sample.glaciation,
     );
 
-    // ── 7. Realism scoring ───────────────────────────────────────────────
+    // ── 4. Realism scoring ───────────────────────────────────────────────
     let score = compute_realism_score(&hf, terrain_class);
 
     // Regime and map fields for the tile (uniform, from location sample).
-    let regime_field = vec![local_regime; GRID_WIDTH * GRID_HEIGHT];
-    let map_field = vec![local_map_mm; GRID_WIDTH * GRID_HEIGHT];
+    let regime_field = vec![sample.regime; n];
+    let map_field = vec![sample.map_mm; n];
 
     LocationTileResult {
         heightfield: hf,
@@ -541,15 +856,15 @@ pub fn generate_at_location(params: &GlobalParams, lat: f32, lon: f32) -> Locati
         map_field,
         score,
         generation_time_ms: 0,
-        lat,
-        lon,
+        lat: sample.lat,
+        lon: sample.lon,
         terrain_class,
-        local_regime,
-        local_map_mm,
-        local_erodibility,
-        local_grain_angle,
-        local_grain_intensity,
-        local_glaciation,
+        local_regime: sample.regime,
+        local_map_mm: sample.map_mm,
+        local_erodibility: sample.erodibility,
+        local_grain_angle: sample.grain_angle,
+        local_grain_intensity: sample.grain_intensity,
+        local_glaciation: sample.glaciation,
     }
 }
 
@@ -695,6 +1010,36 @@ mod tests {
         assert!((result.lon - lon).abs() < 1e-4);
     }
 
+    /// Location tiles scale their relief with the body's gravity, like the
+    /// overview does.
+    #[test]
+    fn location_tile_relief_follows_body_gravity() {
+        let earth = GlobalParams::default();
+        let low_g = GlobalParams {
+            body: PlanetBody {
+                surface_gravity_m_s2: PlanetBody::EARTH.surface_gravity_m_s2 / 2.0,
+                ..PlanetBody::EARTH
+            },
+            ..earth.clone()
+        };
+        let config = GenerationConfig::default();
+        let (plates, climate) = crate::planet::simulate_planet_layers(&earth, &config);
+        let sample = sample_location(&plates, &climate, 20.0, 45.0);
+        let range = |params: &GlobalParams| {
+            let data = generate_tile_for_sample(params, &config, &sample)
+                .heightfield
+                .data;
+            let max = data.iter().copied().fold(f32::MIN, f32::max);
+            let min = data.iter().copied().fold(f32::MAX, f32::min);
+            max - min
+        };
+        let (earth_range, low_g_range) = (range(&earth), range(&low_g));
+        assert!(
+            low_g_range > 1.5 * earth_range,
+            "half gravity: relief {low_g_range:.0}m vs Earth {earth_range:.0}m"
+        );
+    }
+
     /// generate_at_location: ocean click (PassiveMargin) yields a non-Alpine tile.
     ///
     /// Chooses a high-latitude near-pole location which should be PM regime in
@@ -781,6 +1126,120 @@ mod tests {
         // Timing assertions belong in integration/WASM tests, not here.
     }
 
+    /// Progress visits every stage in order, reports inside the long loops and
+    /// finishes at 1; the result matches a plain `generate`.
+    #[test]
+    fn try_generate_reports_every_stage() {
+        use crate::progress::ProgressEvent;
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let gen = PlanetGenerator::with_config(GenerationConfig {
+            tile_width: 128,
+            tile_height: 64,
+            ..GenerationConfig::default()
+        });
+        let params = GlobalParams::default();
+        let events = Rc::new(RefCell::new(Vec::<ProgressEvent>::new()));
+        let sink = Rc::clone(&events);
+        let mut control = RunControl::new().with_observer(move |e: &ProgressEvent| {
+            sink.borrow_mut().push(*e);
+        });
+        let result = gen.try_generate(&params, &mut control).unwrap();
+        assert_eq!(
+            result.heightfield.data,
+            gen.generate(&params).heightfield.data
+        );
+
+        let events = events.borrow();
+        let mut stages: Vec<&str> = events.iter().map(|e| e.stage).collect();
+        stages.dedup();
+        let expected: Vec<&str> = GENERATE_STAGES.iter().map(|s| s.0).collect();
+        assert_eq!(stages, expected);
+        assert!(events.windows(2).all(|w| w[1].overall >= w[0].overall));
+        for stage in ["plates", "hydraulic"] {
+            assert!(
+                events
+                    .iter()
+                    .any(|e| e.stage == stage && e.stage_fraction > 0.0 && e.stage_fraction < 1.0),
+                "no intermediate progress inside {stage}"
+            );
+        }
+        assert_eq!(events.last().unwrap().overall, 1.0);
+    }
+
+    /// The trace times every stage and records the schedule hydraulic
+    /// shaping actually ran, including tuning overrides.
+    #[test]
+    fn generate_trace_records_stages_and_resolved_params() {
+        let gen = PlanetGenerator::with_config(GenerationConfig {
+            tile_width: 128,
+            tile_height: 64,
+            ..GenerationConfig::default()
+        });
+        let mut params = GlobalParams::default();
+        params.tuning.hydraulic.fluvial_humid.erosion_iters = 7;
+        let trace = gen.generate(&params).trace;
+
+        let stages: Vec<&str> = trace.stages.iter().map(|s| s.stage.as_str()).collect();
+        let expected: Vec<&str> = GENERATE_STAGES.iter().map(|s| s.0).collect();
+        assert_eq!(stages, expected);
+        assert!(trace.total_ms() >= trace.stage_ms("hydraulic").unwrap());
+
+        let class = classify_terrain(&params);
+        assert_eq!(trace.terrain_class, Some(class));
+        assert_eq!(trace.noise.as_ref().unwrap().terrain_class, class);
+        assert_eq!(trace.hydraulic.unwrap().erosion_iters, 7);
+        assert_eq!(trace.glacial_class, Some(GlacialClass::Former));
+        assert_eq!(trace.sea_level_km, None);
+        assert_eq!(
+            trace.counts.plates,
+            plate_count_from_fragmentation(params.continental_fragmentation)
+        );
+        assert_eq!(trace.counts.hotspots, params.tuning.plates.hotspots);
+        assert!(trace.counts.basins.unwrap() > 0);
+    }
+
+    /// A pre-cancelled token stops `try_generate` before any work.
+    #[test]
+    fn generate_with_cancelled_token_returns_cancelled() {
+        let token = crate::progress::CancelToken::new();
+        token.cancel();
+        let mut control = RunControl::new().with_cancel_token(token);
+        let result = PlanetGenerator::new().try_generate(&GlobalParams::default(), &mut control);
+        assert_eq!(result.err(), Some(TerraError::Cancelled));
+    }
+
+    /// Bad inputs are rejected before any stage runs.
+    #[test]
+    fn try_entry_points_reject_bad_inputs() {
+        let mut control = RunControl::new();
+        let params = GlobalParams {
+            water_abundance: 1.5,
+            ..GlobalParams::default()
+        };
+        let result = PlanetGenerator::new().try_generate(&params, &mut control);
+        assert!(matches!(result, Err(TerraError::OutOfRange(_))));
+
+        let empty = PlanetGenerator::with_config(GenerationConfig {
+            tile_width: 0,
+            ..GenerationConfig::default()
+        });
+        let result = empty.try_generate(&GlobalParams::default(), &mut control);
+        assert!(matches!(
+            result,
+            Err(TerraError::EmptyGrid { grid: "tile", .. })
+        ));
+
+        let result = try_generate_at_location(
+            &GlobalParams::default(),
+            &GenerationConfig::default(),
+            f32::NAN,
+            0.0,
+        );
+        assert!(matches!(result, Err(TerraError::InvalidLocation { .. })));
+    }
+
     /// Oceanic ActiveExtensional grain intensity must be ≤ 0.55 after the cap.
     ///
     /// Near long straight subduction arcs the grain coherence can approach 1.0.
@@ -792,7 +1251,7 @@ mod tests {
         use crate::planet::{OVERVIEW_HEIGHT, OVERVIEW_WIDTH};
 
         let params = GlobalParams::default();
-        let plates = simulate_plates(
+        let plates = crate::plates::simulate_plates(
             params.seed,
             params.continental_fragmentation,
             params.mountain_prevalence,
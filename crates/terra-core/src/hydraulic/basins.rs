@@ -26,79 +26,11 @@ pub struct DrainageBasin {
 pub fn delineate_basins(flow: &FlowField, hf: &HeightField) -> Vec<DrainageBasin> {
     let rows = flow.height;
     let cols = flow.width;
-    let n = rows * cols;
-
-    // ── Build reverse flow graph (donors) ────────────────────────────────────
-    let mut donors: Vec<Vec<usize>> = vec![Vec::new(); n];
-    for i in 0..n {
-        let code = flow.direction[i];
-        if code == 0 {
-            continue;
-        }
-        let (dr, dc) = D8_OFFSETS[(code - 1) as usize];
-        let r = i / cols;
-        let c = i % cols;
-        let nr = r as isize + dr;
-        let nc = c as isize + dc;
-        if nr >= 0 && nc >= 0 && nr < rows as isize && nc < cols as isize {
-            let j = nr as usize * cols + nc as usize;
-            donors[j].push(i);
-        }
-    }
-
-    // ── Find all outlets ─────────────────────────────────────────────────────
-    // An outlet is a cell whose direction is 0, OR whose downstream neighbour
-    // is outside the raster (edge cells that flow off the boundary).
-    let mut is_outlet = vec![false; n];
-    for (i, &code) in flow.direction.iter().enumerate() {
-        if code == 0 {
-            is_outlet[i] = true;
-            continue;
-        }
-        let (dr, dc) = D8_OFFSETS[(code - 1) as usize];
-        let r = i / cols;
-        let c = i % cols;
-        let nr = r as isize + dr;
-        let nc = c as isize + dc;
-        if nr < 0 || nc < 0 || nr >= rows as isize || nc >= cols as isize {
-            is_outlet[i] = true;
-        }
-    }
-
-    // ── BFS backwards from each outlet to assign basin IDs ───────────────────
-    let mut basin_id: Vec<u32> = vec![u32::MAX; n];
-    let mut next_id: u32 = 0;
-
-    for i in 0..n {
-        if !is_outlet[i] {
-            continue;
-        }
-        basin_id[i] = next_id;
-        let mut queue = std::collections::VecDeque::new();
-        queue.push_back(i);
-        while let Some(j) = queue.pop_front() {
-            for &donor in &donors[j] {
-                if basin_id[donor] == u32::MAX {
-                    basin_id[donor] = next_id;
-                    queue.push_back(donor);
-                }
-            }
-        }
-        next_id += 1;
-    }
-
-    // Any remaining unassigned cells (isolated sinks in flat regions after
-    // priority-flood) form their own single-cell basins.
-    for bid in &mut basin_id {
-        if *bid == u32::MAX {
-            *bid = next_id;
-            next_id += 1;
-        }
-    }
+    let basin_id = basin_labels(flow);
+    let num_basins = basin_id.iter().map(|&b| b as usize + 1).max().unwrap_or(0);
 
     // ── Compute per-basin statistics ─────────────────────────────────────────
     let cs = cellsize_m(hf);
-    let num_basins = next_id as usize;
     let mut min_z = vec![f32::INFINITY; num_basins];
     let mut max_z = vec![f32::NEG_INFINITY; num_basins];
     let mut sum_z = vec![0.0f64; num_basins];
@@ -197,6 +129,86 @@ pub fn delineate_basins(flow: &FlowField, hf: &HeightField) -> Vec<DrainageBasin
         .collect()
 }
 
+/// Assign every cell the ID of the drainage basin it belongs to.
+///
+/// IDs are dense (`0..num_basins`) and match `DrainageBasin::id` and the
+/// index into the vector returned by `delineate_basins`.
+pub fn basin_labels(flow: &FlowField) -> Vec<u32> {
+    let rows = flow.height;
+    let cols = flow.width;
+    let n = rows * cols;
+
+    // ── Build reverse flow graph (donors) ────────────────────────────────────
+    let mut donors: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for i in 0..n {
+        let code = flow.direction[i];
+        if code == 0 {
+            continue;
+        }
+        let (dr, dc) = D8_OFFSETS[(code - 1) as usize];
+        let r = i / cols;
+        let c = i % cols;
+        let nr = r as isize + dr;
+        let nc = c as isize + dc;
+        if nr >= 0 && nc >= 0 && nr < rows as isize && nc < cols as isize {
+            let j = nr as usize * cols + nc as usize;
+            donors[j].push(i);
+        }
+    }
+
+    // ── Find all outlets ─────────────────────────────────────────────────────
+    // An outlet is a cell whose direction is 0, OR whose downstream neighbour
+    // is outside the raster (edge cells that flow off the boundary).
+    let mut is_outlet = vec![false; n];
+    for (i, &code) in flow.direction.iter().enumerate() {
+        if code == 0 {
+            is_outlet[i] = true;
+            continue;
+        }
+        let (dr, dc) = D8_OFFSETS[(code - 1) as usize];
+        let r = i / cols;
+        let c = i % cols;
+        let nr = r as isize + dr;
+        let nc = c as isize + dc;
+        if nr < 0 || nc < 0 || nr >= rows as isize || nc >= cols as isize {
+            is_outlet[i] = true;
+        }
+    }
+
+    // ── BFS backwards from each outlet to assign basin IDs ───────────────────
+    let mut basin_id: Vec<u32> = vec![u32::MAX; n];
+    let mut next_id: u32 = 0;
+
+    for i in 0..n {
+        if !is_outlet[i] {
+            continue;
+        }
+        basin_id[i] = next_id;
+        let mut queue = std::collections::VecDeque::new();
+        queue.push_back(i);
+        while let Some(j) = queue.pop_front() {
+            for &donor in &donors[j] {
+                if basin_id[donor] == u32::MAX {
+                    basin_id[donor] = next_id;
+                    queue.push_back(donor);
+                }
+            }
+        }
+        next_id += 1;
+    }
+
+    // Any remaining unassigned cells (isolated sinks in flat regions after
+    // priority-flood) form their own single-cell basins.
+    for bid in &mut basin_id {
+        if *bid == u32::MAX {
+            *bid = next_id;
+            next_id += 1;
+        }
+    }
+
+    basin_id
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -9,9 +9,11 @@ pub mod stream_power;
 
 use crate::heightfield::HeightField;
 use crate::noise::params::{GlacialClass, TerrainClass};
+use crate::progress;
 use basins::{delineate_basins, DrainageBasin};
 use flow_routing::{compute_d8_flow, FlowField};
 use glacial::apply_glacial_carving;
+use serde::{Deserialize, Serialize};
 use stream_network::{
     extract_stream_network, StreamNetwork, A_MIN_ALPINE, A_MIN_COASTAL, A_MIN_CRATONIC,
     A_MIN_FLUVIAL_ARID, A_MIN_FLUVIAL_HUMID,
@@ -27,39 +29,93 @@ pub struct HydraulicResult {
 
 // ── Per-class parameter tables ────────────────────────────────────────────────
 
-struct HydraulicParams {
-    a_min: u32,
-    erosion_iters: u32,
-    angle_of_repose_deg: f32,
+/// Shaping schedule for one terrain class.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HydraulicParams {
+    /// Minimum flow accumulation (cells) for a cell to count as a stream.
+    pub a_min: u32,
+    /// Stream power erosion iterations.
+    pub erosion_iters: u32,
+    /// Mass-wasting threshold slope in degrees.
+    pub angle_of_repose_deg: f32,
 }
 
-fn params_for_class(class: TerrainClass) -> HydraulicParams {
-    match class {
-        TerrainClass::Alpine => HydraulicParams {
-            a_min: A_MIN_ALPINE,
-            erosion_iters: 30,
-            angle_of_repose_deg: 35.0,
-        },
-        TerrainClass::FluvialHumid => HydraulicParams {
-            a_min: A_MIN_FLUVIAL_HUMID,
-            erosion_iters: 50,
-            angle_of_repose_deg: 30.0,
-        },
-        TerrainClass::FluvialArid => HydraulicParams {
-            a_min: A_MIN_FLUVIAL_ARID,
-            erosion_iters: 20,
-            angle_of_repose_deg: 35.0,
-        },
-        TerrainClass::Cratonic => HydraulicParams {
-            a_min: A_MIN_CRATONIC,
-            erosion_iters: 10,
-            angle_of_repose_deg: 25.0,
-        },
-        TerrainClass::Coastal => HydraulicParams {
-            a_min: A_MIN_COASTAL,
-            erosion_iters: 25,
-            angle_of_repose_deg: 20.0,
-        },
+impl HydraulicParams {
+    /// Calibrated schedule for `class`.
+    pub fn for_class(class: TerrainClass) -> Self {
+        match class {
+            TerrainClass::Alpine => HydraulicParams {
+                a_min: A_MIN_ALPINE,
+                erosion_iters: 30,
+                angle_of_repose_deg: 35.0,
+            },
+            TerrainClass::FluvialHumid => HydraulicParams {
+                a_min: A_MIN_FLUVIAL_HUMID,
+                erosion_iters: 50,
+                angle_of_repose_deg: 30.0,
+            },
+            TerrainClass::FluvialArid => HydraulicParams {
+                a_min: A_MIN_FLUVIAL_ARID,
+                erosion_iters: 20,
+                angle_of_repose_deg: 35.0,
+            },
+            TerrainClass::Cratonic => HydraulicParams {
+                a_min: A_MIN_CRATONIC,
+                erosion_iters: 10,
+                angle_of_repose_deg: 25.0,
+            },
+            TerrainClass::Coastal => HydraulicParams {
+                a_min: A_MIN_COASTAL,
+                erosion_iters: 25,
+                angle_of_repose_deg: 20.0,
+            },
+        }
+    }
+}
+
+/// One `HydraulicParams` per terrain class; defaults to the calibrated table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HydraulicTuning {
+    pub alpine: HydraulicParams,
+    pub fluvial_humid: HydraulicParams,
+    pub fluvial_arid: HydraulicParams,
+    pub cratonic: HydraulicParams,
+    pub coastal: HydraulicParams,
+}
+
+impl Default for HydraulicTuning {
+    fn default() -> Self {
+        Self {
+            alpine: HydraulicParams::for_class(TerrainClass::Alpine),
+            fluvial_humid: HydraulicParams::for_class(TerrainClass::FluvialHumid),
+            fluvial_arid: HydraulicParams::for_class(TerrainClass::FluvialArid),
+            cratonic: HydraulicParams::for_class(TerrainClass::Cratonic),
+            coastal: HydraulicParams::for_class(TerrainClass::Coastal),
+        }
+    }
+}
+
+impl HydraulicTuning {
+    pub fn for_class(&self, class: TerrainClass) -> &HydraulicParams {
+        match class {
+            TerrainClass::Alpine => &self.alpine,
+            TerrainClass::FluvialHumid => &self.fluvial_humid,
+            TerrainClass::FluvialArid => &self.fluvial_arid,
+            TerrainClass::Cratonic => &self.cratonic,
+            TerrainClass::Coastal => &self.coastal,
+        }
+    }
+
+    /// Every class schedule with its key in recipe files.
+    pub fn classes(&self) -> [(&'static str, &HydraulicParams); 5] {
+        [
+            ("alpine", &self.alpine),
+            ("fluvial_humid", &self.fluvial_humid),
+            ("fluvial_arid", &self.fluvial_arid),
+            ("cratonic", &self.cratonic),
+            ("coastal", &self.coastal),
+        ]
     }
 }
 
@@ -81,12 +137,27 @@ pub fn apply_hydraulic_shaping(
     erodibility: &[f32],
     glacial_class: GlacialClass,
 ) -> HydraulicResult {
-    let p = params_for_class(terrain_class);
+    apply_hydraulic_shaping_with(
+        hf,
+        &HydraulicParams::for_class(terrain_class),
+        erodibility,
+        glacial_class,
+    )
+}
 
+/// `apply_hydraulic_shaping` with an explicit schedule instead of the
+/// calibrated one for a terrain class.
+pub fn apply_hydraulic_shaping_with(
+    hf: &mut HeightField,
+    p: &HydraulicParams,
+    erodibility: &[f32],
+    glacial_class: GlacialClass,
+) -> HydraulicResult {
     // Step 1 — stream power erosion.  Returns the final flow field after the
     // last erosion iteration.
-    let flow_after_erosion =
-        apply_stream_power(hf, erodibility, p.erosion_iters, p.angle_of_repose_deg);
+    let flow_after_erosion = progress::within(0.0, 0.8, || {
+        apply_stream_power(hf, erodibility, p.erosion_iters, p.angle_of_repose_deg)
+    });
 
     // Step 2 — glacial carving (borrows pre-erosion flow field only for the
     // glacial mask; recomputes internally after carving).
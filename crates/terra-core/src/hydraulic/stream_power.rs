@@ -10,6 +10,7 @@ use super::flow_routing::{compute_d8_flow, FlowField};
 use super::mass_wasting::apply_mass_wasting;
 use crate::heightfield::HeightField;
 use crate::metrics::gradient::{cellsize_m, horn_gradient};
+use crate::progress;
 
 /// Apply `iterations` rounds of stream power erosion + mass wasting.
 ///
@@ -30,7 +31,7 @@ pub fn apply_stream_power(
 
     let mut flow = compute_d8_flow(hf);
 
-    for _ in 0..iterations {
+    for iteration in 0..iterations {
         let cs = cellsize_m(hf);
         let rows = hf.height;
         let cols = hf.width;
@@ -64,6 +65,8 @@ pub fn apply_stream_power(
 
         // ── Recompute flow routing for next iteration ────────────────────────
         flow = compute_d8_flow(hf);
+
+        progress::report((iteration + 1) as f32 / iterations as f32);
     }
 
     flow
@@ -0,0 +1,585 @@
+//! Inverse design: find sliders and a seed that hit target planet statistics.
+//!
+//! Users describe worlds by outcome ("40 % land, 3 continents, little ice")
+//! rather than by slider value.  `solve` turns `DesignTargets` into
+//! `GlobalParams` in three steps:
+//!
+//! 1. **Analytic start.**  Statistics the model maps in closed form are
+//!    inverted directly: sea level is the `water_abundance` percentile of
+//!    the elevations, the glaciation mask is a pair of latitude thresholds,
+//!    the continent count placement is asked for is a step function of
+//!    `continental_fragmentation`, and the latitudinal MAP base is the
+//!    analytic curve `derive_debug_params` reports.
+//! 2. **Seed search.**  Each candidate seed is evaluated on the overview
+//!    pipeline at the analytic start; the closest one is kept.
+//! 3. **Pattern search.**  The sliders that drive a targeted statistic are
+//!    stepped up and down, halving the step whenever no move improves, with
+//!    `OverviewPipeline::update` rerunning only the stages a move touches.
+//!
+//! Statistics are measured on the evaluation grid of `SolveOptions::config`
+//! and shift slightly at other resolutions.
+
+use serde::{Deserialize, Serialize};
+
+use crate::body::PlanetBody;
+use crate::climate::latitude_bands::map_base_mm;
+use crate::error::{check_grid, TerraError};
+use crate::generator::{check_range, GenerationConfig, GlobalParams};
+use crate::noise::params::GlacialClass;
+use crate::planet::{OverviewPipeline, PlanetOverview};
+use crate::plates::plate_generation::continent_count_from_fragmentation;
+
+/// Landmasses smaller than this share of all land are islands, not
+/// continents.
+pub const CONTINENT_MIN_LAND_SHARE: f32 = 0.04;
+
+/// Height above the median land elevation from which a land cell counts as
+/// mountainous.  Measured from the median rather than sea level because the
+/// continental platforms of the structural field stand at different heights
+/// from seed to seed.
+pub const MOUNTAIN_RELIEF_KM: f32 = 1.0;
+
+/// Distance scales: a miss by this much adds 1 to the squared distance.
+const FRACTION_SCALE: f64 = 0.02;
+const CONTINENT_SCALE: f64 = 1.0;
+const MAP_SCALE_MM: f64 = 50.0;
+
+/// First and smallest pattern-search step, in slider units.
+const INITIAL_STEP: f32 = 0.25;
+const MIN_STEP: f32 = 1.0 / 64.0;
+
+// ── Statistics ───────────────────────────────────────────────────────────────
+
+/// Planet-scale statistics of an overview.  Fractions, landmass sizes and
+/// means weight each grid cell by its area, cos(latitude), so the polar rows
+/// of the equirectangular grid do not count more than the equator.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlanetStats {
+    /// Share of the surface above sea level.
+    pub land_fraction: f32,
+    /// Landmasses holding at least `CONTINENT_MIN_LAND_SHARE` of the land.
+    pub continent_count: usize,
+    /// Share of the land in the largest landmass.
+    pub largest_landmass_share: f32,
+    /// Share of land at least `MOUNTAIN_RELIEF_KM` above the median land
+    /// elevation.
+    pub mountain_fraction: f32,
+    /// Mean annual precipitation over land (mm/yr).
+    pub mean_map_mm: f32,
+    /// Share of land under a former or active glacial overprint.
+    pub glaciated_fraction: f32,
+}
+
+impl PlanetStats {
+    pub fn of_overview(overview: &PlanetOverview) -> Self {
+        let (w, h) = (overview.width, overview.height);
+        let weights = row_weights(h);
+        let area = |i: usize| weights[i / w];
+        let land: Vec<usize> = (0..w * h).filter(|&i| !overview.ocean_mask[i]).collect();
+        if land.is_empty() {
+            return Self::default();
+        }
+        let land_area: f32 = land.iter().map(|&i| area(i)).sum();
+        let share = |pred: &dyn Fn(usize) -> bool| {
+            land.iter()
+                .filter(|&&i| pred(i))
+                .map(|&i| area(i))
+                .sum::<f32>()
+                / land_area
+        };
+
+        let masses = landmass_areas(&overview.ocean_mask, &weights, w, h);
+        let min_continent = CONTINENT_MIN_LAND_SHARE * land_area;
+        // Area-weighted median land elevation.
+        let mut by_height = land.clone();
+        by_height.sort_unstable_by(|&a, &b| {
+            overview.physical_elevations[a].total_cmp(&overview.physical_elevations[b])
+        });
+        let mut below = 0.0;
+        let median = by_height
+            .iter()
+            .find(|&&i| {
+                below += area(i);
+                below >= 0.5 * land_area
+            })
+            .map_or(0.0, |&i| overview.physical_elevations[i]);
+        let mountain_km = median + MOUNTAIN_RELIEF_KM;
+        Self {
+            land_fraction: land_area / (weights.iter().sum::<f32>() * w as f32),
+            continent_count: masses.iter().filter(|&&m| m >= min_continent).count(),
+            largest_landmass_share: masses[0] / land_area,
+            mountain_fraction: share(&|i| overview.physical_elevations[i] >= mountain_km),
+            mean_map_mm: land
+                .iter()
+                .map(|&i| overview.map_field[i] * area(i))
+                .sum::<f32>()
+                / land_area,
+            glaciated_fraction: share(&|i| overview.glaciation[i] != GlacialClass::None),
+        }
+    }
+}
+
+/// Relative area of a cell in each row of an equirectangular grid:
+/// cos(latitude) at the row centre.
+fn row_weights(height: usize) -> Vec<f32> {
+    (0..height)
+        .map(|r| {
+            (90.0 - (r as f64 + 0.5) * 180.0 / height as f64)
+                .to_radians()
+                .cos() as f32
+        })
+        .collect()
+}
+
+/// Areas of the 4-connected land components, largest first, with each cell
+/// weighted by its row's entry in `row_weights`.
+///
+/// Columns wrap, so a landmass crossing the antimeridian counts once.
+fn landmass_areas(
+    ocean_mask: &[bool],
+    row_weights: &[f32],
+    width: usize,
+    height: usize,
+) -> Vec<f32> {
+    let mut visited = vec![false; width * height];
+    let mut areas = Vec::new();
+    let mut stack = Vec::new();
+    for start in 0..width * height {
+        if ocean_mask[start] || visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        stack.push(start);
+        let mut area = 0.0;
+        while let Some(idx) = stack.pop() {
+            let (r, c) = (idx / width, idx % width);
+            area += row_weights[r];
+            let neighbours = [
+                (r > 0).then(|| idx - width),
+                (r + 1 < height).then(|| idx + width),
+                Some(r * width + (c + width - 1) % width),
+                Some(r * width + (c + 1) % width),
+            ];
+            for nb in neighbours.into_iter().flatten() {
+                if !ocean_mask[nb] && !visited[nb] {
+                    visited[nb] = true;
+                    stack.push(nb);
+                }
+            }
+        }
+        areas.push(area);
+    }
+    areas.sort_unstable_by(|a, b| b.total_cmp(a));
+    areas
+}
+
+// ── Targets ──────────────────────────────────────────────────────────────────
+
+/// Statistics to aim for; `None` leaves a statistic free.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DesignTargets {
+    pub land_fraction: Option<f32>,
+    pub continent_count: Option<usize>,
+    pub largest_landmass_share: Option<f32>,
+    pub mountain_fraction: Option<f32>,
+    pub mean_map_mm: Option<f32>,
+    pub glaciated_fraction: Option<f32>,
+}
+
+impl DesignTargets {
+    /// Check every target against the range the statistic can take.
+    pub fn validate(&self) -> Result<(), TerraError> {
+        let fractions = [
+            ("land_fraction", self.land_fraction),
+            ("largest_landmass_share", self.largest_landmass_share),
+            ("mountain_fraction", self.mountain_fraction),
+            ("glaciated_fraction", self.glaciated_fraction),
+        ];
+        for (name, target) in fractions {
+            if let Some(v) = target {
+                check_range(name, v as f64, 0.0, 1.0)?;
+            }
+        }
+        if let Some(n) = self.continent_count {
+            check_range("continent_count", n as f64, 0.0, 64.0)?;
+        }
+        if let Some(mm) = self.mean_map_mm {
+            check_range("mean_map_mm", mm as f64, 0.0, 10_000.0)?;
+        }
+        Ok(())
+    }
+
+    /// Normalised distance from `stats` to the targets: the root sum of
+    /// squared misses, each in units of 2 percentage points, one continent
+    /// or 50 mm/yr.  Zero when nothing is targeted.
+    pub fn distance(&self, stats: &PlanetStats) -> f64 {
+        let fraction = |target: Option<f32>, actual: f32| {
+            target.map_or(0.0, |t| ((actual - t) as f64 / FRACTION_SCALE).powi(2))
+        };
+        let continents = self.continent_count.map_or(0.0, |t| {
+            ((stats.continent_count as f64 - t as f64) / CONTINENT_SCALE).powi(2)
+        });
+        let map = self.mean_map_mm.map_or(0.0, |t| {
+            ((stats.mean_map_mm - t) as f64 / MAP_SCALE_MM).powi(2)
+        });
+        (fraction(self.land_fraction, stats.land_fraction)
+            + continents
+            + fraction(self.largest_landmass_share, stats.largest_landmass_share)
+            + fraction(self.mountain_fraction, stats.mountain_fraction)
+            + map
+            + fraction(self.glaciated_fraction, stats.glaciated_fraction))
+        .sqrt()
+    }
+}
+
+// ── Solver ───────────────────────────────────────────────────────────────────
+
+/// Search budget and evaluation grid of `solve_with`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SolveOptions {
+    /// Grid the candidate overviews are generated on; only the overview
+    /// size is used.
+    pub config: GenerationConfig,
+    /// Seeds tried, consecutive from the base params' seed (at least one).
+    pub seeds: u64,
+    /// Overview evaluations across seed and pattern search.
+    pub max_evaluations: usize,
+    /// Stop once the distance to the targets is at most this.
+    pub tolerance: f64,
+}
+
+impl Default for SolveOptions {
+    fn default() -> Self {
+        Self {
+            config: GenerationConfig {
+                overview_width: 256,
+                overview_height: 128,
+                ..GenerationConfig::default()
+            },
+            seeds: 8,
+            max_evaluations: 200,
+            tolerance: 0.5,
+        }
+    }
+}
+
+/// Best params found and what they achieve.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DesignSolution {
+    pub params: GlobalParams,
+    pub achieved: PlanetStats,
+    /// `DesignTargets::distance` of `achieved`.
+    pub distance: f64,
+    /// Overviews generated during the search.
+    pub evaluations: usize,
+}
+
+/// `solve_with` with the default options.
+pub fn solve(targets: &DesignTargets, base: &GlobalParams) -> Result<DesignSolution, TerraError> {
+    solve_with(targets, base, &SolveOptions::default())
+}
+
+/// Find params close to `targets`, starting from `base`.
+///
+/// Sliders no target depends on, the body and the tuning knobs keep their
+/// `base` values.
+pub fn solve_with(
+    targets: &DesignTargets,
+    base: &GlobalParams,
+    options: &SolveOptions,
+) -> Result<DesignSolution, TerraError> {
+    targets.validate()?;
+    base.validate()?;
+    let config = options.config;
+    check_grid("overview", config.overview_width, config.overview_height)?;
+
+    let start = analytic_start(targets, base);
+    let mut search = Search {
+        targets,
+        pipeline: OverviewPipeline::new(&start, config),
+        evaluations: 0,
+        max_evaluations: options.max_evaluations.max(1),
+    };
+    let mut best = search.measure(&start);
+
+    for k in 1..options.seeds {
+        if search.exhausted() || best.distance <= options.tolerance {
+            break;
+        }
+        let candidate = GlobalParams {
+            seed: start.seed.wrapping_add(k),
+            ..start.clone()
+        };
+        let solution = search.evaluate(&candidate);
+        if solution.distance < best.distance {
+            best = solution;
+        }
+    }
+
+    let knobs = free_knobs(targets);
+    let mut step = INITIAL_STEP;
+    while step >= MIN_STEP && !search.exhausted() && best.distance > options.tolerance {
+        let mut improved = false;
+        'knobs: for &knob in &knobs {
+            for delta in [step, -step] {
+                let mut candidate = best.params.clone();
+                let slot = knob.slot(&mut candidate);
+                let moved = (*slot + delta).clamp(0.0, 1.0);
+                if moved == *slot {
+                    continue;
+                }
+                *slot = moved;
+                if search.exhausted() {
+                    break 'knobs;
+                }
+                let solution = search.evaluate(&candidate);
+                if solution.distance < best.distance {
+                    best = solution;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+        if !improved {
+            step /= 2.0;
+        }
+    }
+
+    best.evaluations = search.evaluations;
+    Ok(best)
+}
+
+/// Invert the closed-form slider → statistic relations.
+fn analytic_start(targets: &DesignTargets, base: &GlobalParams) -> GlobalParams {
+    let mut p = base.clone();
+    if let Some(land) = targets.land_fraction {
+        // Sea level is a percentile of the cells, so this misses the land
+        // area by the land's latitude bias; the search takes up the rest.
+        p.water_abundance = 1.0 - land;
+    } else if let Some(mm) = targets.mean_map_mm {
+        p.water_abundance = water_for_mean_map(mm, p.water_abundance, &p.body);
+    }
+    if let Some(n) = targets.continent_count {
+        p.continental_fragmentation = fragmentation_for_continents(n);
+    }
+    if let Some(glaciated) = targets.glaciated_fraction {
+        // Former overprint reaches 90° − 50° · slider / cap scale, and the
+        // sphere poleward of latitude φ is 1 − sin φ of its area.
+        let reach_deg = (1.0 - glaciated.clamp(0.0, 1.0)).acos().to_degrees();
+        p.glaciation = (reach_deg * p.body.polar_cap_scale() / 50.0).clamp(0.0, 1.0);
+    }
+    p
+}
+
+/// Middle of the `continental_fragmentation` range for which placement is
+/// asked for the continent count closest to `n`.
+fn fragmentation_for_continents(n: usize) -> f32 {
+    const STEPS: usize = 100;
+    let candidates = (0..=STEPS).map(|k| k as f32 / STEPS as f32);
+    let miss = |f: f32| continent_count_from_fragmentation(f).abs_diff(n);
+    let best = candidates.clone().map(miss).min().unwrap_or(0);
+    let (lo, hi) = candidates
+        .filter(|&f| miss(f) == best)
+        .fold((1.0_f32, 0.0_f32), |(lo, hi), f| (lo.min(f), hi.max(f)));
+    0.5 * (lo + hi)
+}
+
+/// `water_abundance` whose mean latitudinal MAP base is `target_mm`.
+///
+/// MAP scales linearly with water except for the deepening of the arid belt
+/// on dry worlds, so a few fixed-point steps converge.
+fn water_for_mean_map(target_mm: f32, water: f32, body: &PlanetBody) -> f32 {
+    const ROWS: usize = 180;
+    let mean_map = |wa: f32| {
+        (0..ROWS)
+            .map(|r| map_base_mm(90.0 - (r as f64 + 0.5), wa, body))
+            .sum::<f32>()
+            / ROWS as f32
+    };
+    let mut wa = water.max(0.01);
+    for _ in 0..4 {
+        wa = (wa * target_mm / mean_map(wa).max(1.0)).clamp(0.01, 1.0);
+    }
+    wa
+}
+
+/// A slider the pattern search may move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Knob {
+    Water,
+    Fragmentation,
+    Mountains,
+    ClimateDiversity,
+    Glaciation,
+}
+
+impl Knob {
+    fn slot(self, p: &mut GlobalParams) -> &mut f32 {
+        match self {
+            Knob::Water => &mut p.water_abundance,
+            Knob::Fragmentation => &mut p.continental_fragmentation,
+            Knob::Mountains => &mut p.mountain_prevalence,
+            Knob::ClimateDiversity => &mut p.climate_diversity,
+            Knob::Glaciation => &mut p.glaciation,
+        }
+    }
+}
+
+/// Sliders that drive at least one targeted statistic.
+fn free_knobs(t: &DesignTargets) -> Vec<Knob> {
+    let landmass = t.continent_count.is_some() || t.largest_landmass_share.is_some();
+    let wanted = [
+        (
+            Knob::Water,
+            t.land_fraction.is_some() || t.mean_map_mm.is_some() || landmass,
+        ),
+        (Knob::Fragmentation, landmass),
+        (Knob::Mountains, t.mountain_fraction.is_some()),
+        (Knob::ClimateDiversity, t.mean_map_mm.is_some()),
+        (Knob::Glaciation, t.glaciated_fraction.is_some()),
+    ];
+    wanted
+        .into_iter()
+        .filter_map(|(knob, wanted)| wanted.then_some(knob))
+        .collect()
+}
+
+struct Search<'a> {
+    targets: &'a DesignTargets,
+    pipeline: OverviewPipeline,
+    evaluations: usize,
+    max_evaluations: usize,
+}
+
+impl Search<'_> {
+    fn exhausted(&self) -> bool {
+        self.evaluations >= self.max_evaluations
+    }
+
+    fn evaluate(&mut self, params: &GlobalParams) -> DesignSolution {
+        self.pipeline.update(params);
+        self.measure(params)
+    }
+
+    /// Score the pipeline's current overview, generated from `params`.
+    fn measure(&mut self, params: &GlobalParams) -> DesignSolution {
+        self.evaluations += 1;
+        let achieved = PlanetStats::of_overview(&self.pipeline.overview());
+        DesignSolution {
+            params: params.clone(),
+            achieved,
+            distance: self.targets.distance(&achieved),
+            evaluations: self.evaluations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn landmasses_wrap_across_the_antimeridian() {
+        // 8 × 4 grid: one landmass split by the grid edge, one interior.
+        #[rustfmt::skip]
+        let land = [
+            1, 0, 0, 0, 0, 0, 0, 1,
+            1, 0, 0, 1, 1, 0, 0, 1,
+            0, 0, 0, 1, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let ocean: Vec<bool> = land.iter().map(|&l| l == 0).collect();
+        assert_eq!(landmass_areas(&ocean, &[1.0; 4], 8, 4), vec![4.0, 3.0]);
+        // By area the three cells nearer the equator outweigh the four
+        // reaching the pole row.
+        let weights = row_weights(4);
+        let areas = landmass_areas(&ocean, &weights, 8, 4);
+        assert!((areas[0] - 3.0 * weights[1]).abs() < 1e-6);
+        assert!((areas[1] - 2.0 * (weights[0] + weights[1])).abs() < 1e-6);
+    }
+
+    #[test]
+    fn analytic_start_inverts_closed_form_relations() {
+        let targets = DesignTargets {
+            land_fraction: Some(0.4),
+            continent_count: Some(5),
+            glaciated_fraction: Some(0.1),
+            ..DesignTargets::default()
+        };
+        let p = analytic_start(&targets, &GlobalParams::default());
+        assert!((p.water_abundance - 0.6).abs() < 1e-6);
+        assert_eq!(
+            continent_count_from_fragmentation(p.continental_fragmentation),
+            5
+        );
+        // 10 % of the sphere lies poleward of 64.2°, 25.8° from the pole.
+        assert!((p.glaciation - 0.5168).abs() < 1e-3, "{}", p.glaciation);
+        for n in 0..10 {
+            let count = continent_count_from_fragmentation(fragmentation_for_continents(n));
+            assert_eq!(count, n.clamp(3, 7), "target {n}");
+        }
+
+        let dry = DesignTargets {
+            mean_map_mm: Some(300.0),
+            ..DesignTargets::default()
+        };
+        let wet = DesignTargets {
+            mean_map_mm: Some(1200.0),
+            ..DesignTargets::default()
+        };
+        let base = GlobalParams::default();
+        assert!(
+            analytic_start(&dry, &base).water_abundance
+                < analytic_start(&wet, &base).water_abundance
+        );
+    }
+
+    #[test]
+    fn solve_moves_toward_targets() {
+        let targets = DesignTargets {
+            land_fraction: Some(0.45),
+            glaciated_fraction: Some(0.2),
+            mountain_fraction: Some(0.1),
+            ..DesignTargets::default()
+        };
+        let options = SolveOptions {
+            config: GenerationConfig {
+                overview_width: 64,
+                overview_height: 32,
+                ..GenerationConfig::default()
+            },
+            seeds: 2,
+            max_evaluations: 16,
+            tolerance: 0.0,
+        };
+        let base = GlobalParams::default();
+        let solution = solve_with(&targets, &base, &options).unwrap();
+        assert!(solution.evaluations <= 16);
+
+        let untouched = OverviewPipeline::new(&base, options.config).into_overview();
+        let start = targets.distance(&PlanetStats::of_overview(&untouched));
+        assert!(
+            solution.distance < start,
+            "{} vs {start}",
+            solution.distance
+        );
+        assert!((solution.achieved.land_fraction - 0.45).abs() < 0.03);
+        // Untargeted sliders keep their base values.
+        assert_eq!(solution.params.surface_age, base.surface_age);
+        assert_eq!(
+            solution.params.continental_fragmentation,
+            base.continental_fragmentation
+        );
+
+        let bad = DesignTargets {
+            land_fraction: Some(1.5),
+            ..DesignTargets::default()
+        };
+        assert!(matches!(
+            solve_with(&bad, &base, &options),
+            Err(TerraError::OutOfRange(_))
+        ));
+    }
+}
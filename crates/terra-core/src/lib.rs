@@ -1,10 +1,22 @@
+pub mod body;
 pub mod climate;
 pub mod coords;
+pub mod error;
+pub mod export;
 pub mod generator;
 pub mod heightfield;
 pub mod hydraulic;
+pub mod inverse;
+pub mod manifest;
 pub mod metrics;
 pub mod noise;
+mod parallel;
 pub mod planet;
 pub mod plates;
+pub mod progress;
+pub mod recipe;
+pub mod save;
+pub mod seed;
 pub mod sphere;
+pub mod trace;
+pub mod vector;
@@ -0,0 +1,474 @@
+//! Reproducibility manifests for generated outputs.
+//!
+//! A `Manifest` records everything needed to regenerate an output — the
+//! `terra-core` version, `SEED_SCHEME_VERSION`, `OUTPUT_VERSION`, params and
+//! grid config — and
+//! a digest of every field it produced: a 64-bit FNV-1a hash of the values'
+//! bits plus their mean, min and max.  `Manifest::verify` reruns the pipeline
+//! with the current build and reports each field whose hash changed, with
+//! the shift in its summary statistics.
+//!
+//! The manifests under `golden/` are verified by `cargo test`.  After an
+//! intended output change, bump `OUTPUT_VERSION`, rewrite them with
+//! `TERRA_BLESS_GOLDEN=1 cargo test -p terra-core golden` and commit the
+//! diff alongside the change; blessing refuses changed fields without the
+//! bump.  Hashes cover exact float bits, so goldens are
+//! blessed on the CI platform (x86-64 Linux).
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::generator::{
+    generate_at_location_with_config, GenerationConfig, GlobalParams, LocationTileResult,
+    PlanetGenerator, PlanetResult, OUTPUT_VERSION,
+};
+use crate::metrics::score::RealismScore;
+use crate::planet::{generate_planet_overview_with_config, PlanetOverview};
+use crate::save::GENERATOR_VERSION;
+use crate::seed::SEED_SCHEME_VERSION;
+
+const FNV_OFFSET: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+/// Which pipeline produced the output.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "pipeline", rename_all = "snake_case")]
+pub enum ManifestKind {
+    /// `generate_planet_overview_with_config`.
+    Overview,
+    /// `PlanetGenerator::generate`.
+    Generate,
+    /// `generate_at_location_with_config`.
+    Location { lat: f32, lon: f32 },
+}
+
+/// Content hash and summary statistics of one output field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldDigest {
+    /// FNV-1a of the values' little-endian bits, as 16 hex digits.
+    pub hash: String,
+    pub len: usize,
+    /// Statistics over the finite values; 0 when there are none.
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl FieldDigest {
+    pub fn of_f32(values: &[f32]) -> Self {
+        let bytes = values.iter().flat_map(|v| v.to_bits().to_le_bytes());
+        Self::new(bytes, values.iter().map(|&v| v as f64), values.len())
+    }
+
+    /// Digest of byte-sized values: masks, or enums by discriminant.
+    pub fn of_u8(values: impl IntoIterator<Item = u8>) -> Self {
+        let values: Vec<u8> = values.into_iter().collect();
+        Self::new(
+            values.iter().copied(),
+            values.iter().map(|&v| v as f64),
+            values.len(),
+        )
+    }
+
+    fn new(bytes: impl Iterator<Item = u8>, values: impl Iterator<Item = f64>, len: usize) -> Self {
+        let hash = bytes.fold(FNV_OFFSET, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+        });
+        let (mut sum, mut min, mut max, mut finite) =
+            (0.0, f64::INFINITY, f64::NEG_INFINITY, 0usize);
+        for v in values.filter(|v| v.is_finite()) {
+            sum += v;
+            min = min.min(v);
+            max = max.max(v);
+            finite += 1;
+        }
+        if finite == 0 {
+            (min, max) = (0.0, 0.0);
+        }
+        Self {
+            hash: format!("{hash:016x}"),
+            len,
+            mean: if finite > 0 { sum / finite as f64 } else { 0.0 },
+            min,
+            max,
+        }
+    }
+}
+
+/// Provenance and field digests of one generated output.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// `terra-core` version that generated the output.
+    pub generator_version: String,
+    /// `SEED_SCHEME_VERSION` of the generating build.
+    pub seed_scheme_version: u32,
+    /// `OUTPUT_VERSION` of the generating build; 0 in manifests written
+    /// before it was recorded.
+    #[serde(default)]
+    pub output_version: u32,
+    #[serde(flatten)]
+    pub kind: ManifestKind,
+    pub params: GlobalParams,
+    pub config: GenerationConfig,
+    /// Digest of every output field, by field name.
+    pub fields: BTreeMap<String, FieldDigest>,
+}
+
+impl Manifest {
+    fn new(
+        kind: ManifestKind,
+        params: &GlobalParams,
+        config: &GenerationConfig,
+        fields: Vec<(&str, FieldDigest)>,
+    ) -> Self {
+        Self {
+            generator_version: GENERATOR_VERSION.to_owned(),
+            seed_scheme_version: SEED_SCHEME_VERSION,
+            output_version: OUTPUT_VERSION,
+            kind,
+            params: params.clone(),
+            config: *config,
+            fields: fields
+                .into_iter()
+                .map(|(name, digest)| (name.to_owned(), digest))
+                .collect(),
+        }
+    }
+
+    /// Manifest of an overview generated from `params` and `config`.
+    pub fn for_overview(
+        params: &GlobalParams,
+        config: &GenerationConfig,
+        overview: &PlanetOverview,
+    ) -> Self {
+        let metrics: Vec<f32> = overview
+            .planet_metrics
+            .metrics
+            .iter()
+            .map(|m| m.raw_value)
+            .collect();
+        let fields = vec![
+            ("elevations", FieldDigest::of_f32(&overview.elevations)),
+            (
+                "physical_elevations",
+                FieldDigest::of_f32(&overview.physical_elevations),
+            ),
+            (
+                "ocean_mask",
+                FieldDigest::of_u8(overview.ocean_mask.iter().map(|&o| u8::from(o))),
+            ),
+            (
+                "sea_level_km",
+                FieldDigest::of_f32(&[overview.sea_level_km]),
+            ),
+            (
+                "regimes",
+                FieldDigest::of_u8(overview.regimes.iter().map(|&r| r as u8)),
+            ),
+            ("map_field", FieldDigest::of_f32(&overview.map_field)),
+            (
+                "erodibility_field",
+                FieldDigest::of_f32(&overview.erodibility_field),
+            ),
+            (
+                "glaciation",
+                FieldDigest::of_u8(overview.glaciation.iter().map(|&g| g as u8)),
+            ),
+            ("planet_metrics", FieldDigest::of_f32(&metrics)),
+        ];
+        Self::new(ManifestKind::Overview, params, config, fields)
+    }
+
+    /// Manifest of a `PlanetGenerator::generate` result.
+    pub fn for_result(
+        params: &GlobalParams,
+        config: &GenerationConfig,
+        result: &PlanetResult,
+    ) -> Self {
+        let fields = tile_fields(
+            &result.heightfield.data,
+            result.regime_field.iter().map(|&r| r as u8),
+            &result.map_field,
+            &result.score,
+        );
+        Self::new(ManifestKind::Generate, params, config, fields)
+    }
+
+    /// Manifest of a location tile.
+    pub fn for_location(
+        params: &GlobalParams,
+        config: &GenerationConfig,
+        tile: &LocationTileResult,
+    ) -> Self {
+        let fields = tile_fields(
+            &tile.heightfield.data,
+            tile.regime_field.iter().map(|&r| r as u8),
+            &tile.map_field,
+            &tile.score,
+        );
+        let kind = ManifestKind::Location {
+            lat: tile.lat,
+            lon: tile.lon,
+        };
+        Self::new(kind, params, config, fields)
+    }
+
+    /// Rerun the recorded pipeline with this build.
+    pub fn regenerate(&self) -> Manifest {
+        let (params, config) = (&self.params, &self.config);
+        match self.kind {
+            ManifestKind::Overview => Self::for_overview(
+                params,
+                config,
+                &generate_planet_overview_with_config(params, config),
+            ),
+            ManifestKind::Generate => Self::for_result(
+                params,
+                config,
+                &PlanetGenerator::with_config(*config).generate(params),
+            ),
+            ManifestKind::Location { lat, lon } => Self::for_location(
+                params,
+                config,
+                &generate_at_location_with_config(params, config, lat, lon),
+            ),
+        }
+    }
+
+    /// Regenerate the output and compare it with this manifest.
+    pub fn verify(&self) -> Verification {
+        self.compare(&self.regenerate())
+    }
+
+    /// Fields of `actual` that differ from this manifest.
+    pub fn compare(&self, actual: &Manifest) -> Verification {
+        let names: std::collections::BTreeSet<&String> =
+            self.fields.keys().chain(actual.fields.keys()).collect();
+        let diverged = names
+            .into_iter()
+            .filter_map(|name| {
+                let expected = self.fields.get(name);
+                let found = actual.fields.get(name);
+                let same = matches!((expected, found), (Some(e), Some(a)) if e.hash == a.hash);
+                (!same).then(|| FieldDivergence {
+                    field: name.clone(),
+                    expected: expected.cloned(),
+                    actual: found.cloned(),
+                })
+            })
+            .collect();
+        Verification {
+            expected_version: self.generator_version.clone(),
+            actual_version: actual.generator_version.clone(),
+            seed_scheme_changed: self.seed_scheme_version != actual.seed_scheme_version,
+            output_version_changed: self.output_version != actual.output_version,
+            diverged,
+        }
+    }
+}
+
+fn tile_fields(
+    heights: &[f32],
+    regimes: impl IntoIterator<Item = u8>,
+    map_field: &[f32],
+    score: &RealismScore,
+) -> Vec<(&'static str, FieldDigest)> {
+    let mut score_values = vec![score.total];
+    score_values.extend(score.metrics.iter().map(|m| m.raw_value));
+    vec![
+        ("heightfield", FieldDigest::of_f32(heights)),
+        ("regime_field", FieldDigest::of_u8(regimes)),
+        ("map_field", FieldDigest::of_f32(map_field)),
+        ("score", FieldDigest::of_f32(&score_values)),
+    ]
+}
+
+/// One field whose content no longer matches its manifest.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldDivergence {
+    pub field: String,
+    /// `None` when the manifest has no such field.
+    pub expected: Option<FieldDigest>,
+    /// `None` when the regenerated output has no such field.
+    pub actual: Option<FieldDigest>,
+}
+
+impl fmt::Display for FieldDivergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (e, a) = match (&self.expected, &self.actual) {
+            (Some(e), Some(a)) => (e, a),
+            (Some(_), None) => return write!(f, "{}: no longer produced", self.field),
+            (None, _) => return write!(f, "{}: not in the manifest", self.field),
+        };
+        write!(
+            f,
+            "{}: mean {} → {} ({:+}), min {} → {}, max {} → {}",
+            self.field,
+            e.mean,
+            a.mean,
+            a.mean - e.mean,
+            e.min,
+            a.min,
+            e.max,
+            a.max
+        )?;
+        if e.len != a.len {
+            write!(f, ", {} → {} values", e.len, a.len)?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of checking a manifest against the current build.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Verification {
+    /// Generator version recorded in the manifest.
+    pub expected_version: String,
+    /// Generator version of this build.
+    pub actual_version: String,
+    /// The seed derivation scheme differs, so divergence is expected.
+    pub seed_scheme_changed: bool,
+    /// The generation algorithms differ, so divergence is expected.
+    pub output_version_changed: bool,
+    /// Fields whose hash changed, by name.
+    pub diverged: Vec<FieldDivergence>,
+}
+
+impl Verification {
+    /// True when every field matches its recorded hash.
+    pub fn reproduced(&self) -> bool {
+        self.diverged.is_empty()
+    }
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.reproduced() {
+            return write!(f, "reproduced");
+        }
+        write!(
+            f,
+            "{} field(s) diverged (manifest from {}, this build {}",
+            self.diverged.len(),
+            self.expected_version,
+            self.actual_version
+        )?;
+        if self.seed_scheme_changed {
+            write!(f, ", seed scheme changed")?;
+        }
+        if self.output_version_changed {
+            write!(f, ", output version changed")?;
+        }
+        write!(f, ")")?;
+        for d in &self.diverged {
+            write!(f, "\n  {d}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn digest_hash_and_stats() {
+        let d = FieldDigest::of_f32(&[1.0, -2.0, 4.0]);
+        assert_eq!((d.len, d.mean, d.min, d.max), (3, 1.0, -2.0, 4.0));
+        assert_ne!(d.hash, FieldDigest::of_f32(&[1.0, -2.0, 4.5]).hash);
+        // Signed zeros have different bits.
+        assert_ne!(
+            FieldDigest::of_f32(&[0.0]).hash,
+            FieldDigest::of_f32(&[-0.0]).hash
+        );
+        let d = FieldDigest::of_f32(&[f32::NAN, 2.0]);
+        assert_eq!((d.len, d.mean, d.min, d.max), (2, 2.0, 2.0, 2.0));
+        assert_eq!(FieldDigest::of_u8([]).hash, format!("{FNV_OFFSET:016x}"));
+    }
+
+    #[test]
+    fn compare_reports_changed_fields() {
+        let params = GlobalParams::default();
+        let config = GenerationConfig {
+            overview_width: 64,
+            overview_height: 32,
+            ..GenerationConfig::default()
+        };
+        let manifest = Manifest::for_overview(
+            &params,
+            &config,
+            &generate_planet_overview_with_config(&params, &config),
+        );
+        assert!(manifest.verify().reproduced());
+
+        let mut tampered = manifest.clone();
+        let map = tampered.fields.get_mut("map_field").unwrap();
+        map.hash = "0".repeat(16);
+        map.mean -= 10.0;
+        tampered.fields.remove("glaciation");
+        let report = tampered.compare(&manifest);
+        let fields: Vec<&str> = report.diverged.iter().map(|d| d.field.as_str()).collect();
+        assert_eq!(fields, ["glaciation", "map_field"]);
+        assert!(report.to_string().contains("(+10)"), "{report}");
+        assert!(!report.output_version_changed);
+        tampered.output_version -= 1;
+        assert!(tampered.compare(&manifest).output_version_changed);
+
+        let json = serde_json::to_string(&manifest).unwrap();
+        let loaded: Manifest = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            (loaded.kind, &loaded.params),
+            (manifest.kind, &manifest.params)
+        );
+        assert!(loaded.compare(&manifest).reproduced());
+    }
+
+    /// Every manifest under `golden/` still reproduces.
+    ///
+    /// Set `TERRA_BLESS_GOLDEN=1` to rewrite them from the current build;
+    /// manifests whose fields changed are only rewritten after an
+    /// `OUTPUT_VERSION` bump.
+    #[test]
+    fn golden_manifests_reproduce() {
+        let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("golden");
+        let bless = std::env::var_os("TERRA_BLESS_GOLDEN").is_some();
+        let mut paths: Vec<_> = std::fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.extension().is_some_and(|e| e == "json"))
+            .collect();
+        paths.sort();
+        assert!(
+            !paths.is_empty(),
+            "no golden manifests in {}",
+            dir.display()
+        );
+
+        let mut failures = Vec::new();
+        for path in paths {
+            let text = std::fs::read_to_string(&path).unwrap();
+            let manifest: Manifest = serde_json::from_str(&text).unwrap();
+            let actual = manifest.regenerate();
+            let report = manifest.compare(&actual);
+            if bless {
+                if !report.reproduced() && !report.output_version_changed {
+                    failures.push(format!(
+                        "{}: outputs changed without an OUTPUT_VERSION bump: {report}",
+                        path.display()
+                    ));
+                    continue;
+                }
+                let json = serde_json::to_string_pretty(&actual).unwrap();
+                std::fs::write(&path, json + "\n").unwrap();
+                continue;
+            }
+            if !report.reproduced() {
+                failures.push(format!("{}: {report}", path.display()));
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
@@ -11,21 +11,24 @@
 //!   Hurst(0.10), RoughnessElev(0.10), Multifractal(0.08),
 //!   Slope(0.08), Aspect(0.08), TPI(0.08),
 //!   Hypsometric(0.12), Geomorphon(0.14), Drainage(0.12), Moran(0.10).
+use serde::{Deserialize, Serialize};
+
 use crate::noise::params::TerrainClass;
+use crate::parallel;
 
 /// Per-metric score result.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricScore {
-    pub name: &'static str,
+    pub name: String,
     pub raw_value: f32,
     pub score_0_1: f32,
     pub passed: bool,
     /// "noise_synth" or "hydraulic"
-    pub subsystem: &'static str,
+    pub subsystem: String,
 }
 
 /// Full realism score for a single tile.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RealismScore {
     /// Total weighted score 0-100.
     pub total: f32,
@@ -298,14 +301,6 @@ pub fn compute_realism_score(
         compute_roughness_elev, compute_slope, compute_tpi,
     };
 
-    // Compute all metrics.
-    let hurst_r = compute_hurst(hf);
-    let rough_r = compute_roughness_elev(hf);
-    let multi_r = compute_multifractal(hf);
-    let slope_r = compute_slope(hf);
-    let aspect_r = compute_aspect(hf);
-    let tpi_r = compute_tpi(hf);
-    let hyps_r = compute_hypsometric(hf);
     let cs = super::gradient::cellsize_m(hf);
     // At tile scale (cs ≤ 1 km): maintain 1.57 m absolute elevation sensitivity
     // (90 m × tan 1° from Phase 1 SRTM reference data).
@@ -322,9 +317,31 @@ pub fn compute_realism_score(
     } else {
         ((1.57_f64 / cs).atan().to_degrees() as f32).clamp(0.001, 2.0)
     };
-    let geom_r = classify_geomorphons(hf, 3, flat_deg, terrain_class);
-    let drain_r = compute_drainage_density(hf);
-    let morans_val = compute_morans_i_from_heightfield(hf);
+
+    // Compute all metrics.  They only read `hf`, so with `threading` they run
+    // in three groups of similar cost: TPI, the variogram metrics, the rest.
+    let (tpi_r, ((multi_r, hurst_r), (geom_r, drain_r, morans_val, others))) = parallel::join(
+        || compute_tpi(hf),
+        || {
+            parallel::join(
+                || (compute_multifractal(hf), compute_hurst(hf)),
+                || {
+                    (
+                        classify_geomorphons(hf, 3, flat_deg, terrain_class),
+                        compute_drainage_density(hf),
+                        compute_morans_i_from_heightfield(hf),
+                        (
+                            compute_roughness_elev(hf),
+                            compute_slope(hf),
+                            compute_aspect(hf),
+                            compute_hypsometric(hf),
+                        ),
+                    )
+                },
+            )
+        },
+    );
+    let (rough_r, slope_r, aspect_r, hyps_r) = others;
 
     // TPI: use ratio_r1_r2 as a summary value (or NaN).
     let tpi_val = tpi_r.ratio_r1_r2;
@@ -408,74 +425,74 @@ pub fn compute_realism_score(
 
     let metrics = vec![
         MetricScore {
-            name: "hurst",
+            name: "hurst".into(),
             raw_value: hurst_r.h,
             score_0_1: h_score,
             passed: h_score >= 0.5,
-            subsystem: "noise_synth",
+            subsystem: "noise_synth".into(),
         },
         MetricScore {
-            name: "roughness_elev",
+            name: "roughness_elev".into(),
             raw_value: rough_r.pearson_r,
             score_0_1: re_score,
             passed: re_score >= 0.5,
-            subsystem: "noise_synth",
+            subsystem: "noise_synth".into(),
         },
         MetricScore {
-            name: "multifractal",
+            name: "multifractal".into(),
             raw_value: multi_r.width,
             score_0_1: mf_score,
             passed: mf_score >= 0.5,
-            subsystem: "noise_synth",
+            subsystem: "noise_synth".into(),
         },
         MetricScore {
-            name: "slope_mode",
+            name: "slope_mode".into(),
             raw_value: slope_r.mode_deg,
             score_0_1: sl_score,
             passed: sl_score >= 0.5,
-            subsystem: "hydraulic",
+            subsystem: "hydraulic".into(),
         },
         MetricScore {
-            name: "aspect_circ_var",
+            name: "aspect_circ_var".into(),
             raw_value: aspect_r.circular_variance,
             score_0_1: as_score,
             passed: as_score >= 0.5,
-            subsystem: "hydraulic",
+            subsystem: "hydraulic".into(),
         },
         MetricScore {
-            name: "tpi_ratio",
+            name: "tpi_ratio".into(),
             raw_value: tpi_val,
             score_0_1: tp_score,
             passed: tp_score >= 0.5,
-            subsystem: "hydraulic",
+            subsystem: "hydraulic".into(),
         },
         MetricScore {
-            name: "hypsometric",
+            name: "hypsometric".into(),
             raw_value: hyps_r.integral,
             score_0_1: hy_score,
             passed: hy_score >= 0.5,
-            subsystem: "hydraulic",
+            subsystem: "hydraulic".into(),
         },
         MetricScore {
-            name: "geomorphon_l1",
+            name: "geomorphon_l1".into(),
             raw_value: geom_r.l1_distance,
             score_0_1: gm_score,
             passed: gm_score >= 0.5,
-            subsystem: "hydraulic",
+            subsystem: "hydraulic".into(),
         },
         MetricScore {
-            name: "drainage",
+            name: "drainage".into(),
             raw_value: drain_r.density_km_per_km2,
             score_0_1: dr_score,
             passed: dr_score >= 0.5,
-            subsystem: "hydraulic",
+            subsystem: "hydraulic".into(),
         },
         MetricScore {
-            name: "morans_i",
+            name: "morans_i".into(),
             raw_value: morans_val,
             score_0_1: mo_score,
             passed: mo_score >= 0.5,
-            subsystem: "hydraulic",
+            subsystem: "hydraulic".into(),
         },
     ];
 
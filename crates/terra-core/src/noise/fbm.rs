@@ -3,13 +3,15 @@
 //!
 //! fBm: sum of octaves with amplitude = gain^i and frequency = lacunarity^i.
 //! Persistence: gain = lacunarity^(−H).  For lacunarity=2, H=0.75 → gain≈0.595.
-use noise::{NoiseFn, Perlin};
+use noise::NoiseFn;
+
+use super::simd::{LanePerlin, Lanes, LANES};
 
 pub struct Fbm {
     pub h: f32,
     pub octaves: u32,
     pub lacunarity: f32,
-    noise: Perlin,
+    noise: LanePerlin,
 }
 
 impl Fbm {
@@ -20,7 +22,7 @@ impl Fbm {
             h,
             octaves,
             lacunarity: 2.0,
-            noise: Perlin::new(seed),
+            noise: LanePerlin::new(seed),
         }
     }
 
@@ -43,7 +45,37 @@ impl Fbm {
         let mut amp = 1.0f64;
         let mut freq = 1.0f64;
         for _ in 0..self.octaves {
-            value += amp * self.noise.get([x * freq, y * freq]);
+            value += amp * self.noise.scalar().get([x * freq, y * freq]);
+            amp *= gain;
+            freq *= self.lacunarity as f64;
+        }
+        value
+    }
+
+    /// Evaluate `sample` at `LANES` points at once (vectorised under the
+    /// `simd` feature, within `simd::SIMD_TOLERANCE` of `sample`).
+    pub fn sample_lanes(&self, x: Lanes, y: Lanes) -> Lanes {
+        self.noise.fbm(
+            x,
+            y,
+            [self.gain(); LANES],
+            self.octaves,
+            self.lacunarity as f64,
+        )
+    }
+
+    /// Evaluate fBm at the 3D point `(x, y, z)`.
+    ///
+    /// Used for seamless sampling on the sphere: a unit vector scaled by a
+    /// base frequency has no seam at the antimeridian or the poles, so any two
+    /// tiles that evaluate the same point get the same value.
+    pub fn sample_3d(&self, x: f64, y: f64, z: f64) -> f64 {
+        let gain = self.gain();
+        let mut value = 0.0f64;
+        let mut amp = 1.0f64;
+        let mut freq = 1.0f64;
+        for _ in 0..self.octaves {
+            value += amp * self.noise.scalar().get([x * freq, y * freq, z * freq]);
             amp *= gain;
             freq *= self.lacunarity as f64;
         }
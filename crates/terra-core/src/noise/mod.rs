@@ -4,11 +4,14 @@ pub mod hypsometric_shape;
 pub mod multifractal;
 pub mod nonstationary;
 pub mod params;
+pub mod simd;
 pub mod warp;
 
 use crate::heightfield::HeightField;
-use noise::{NoiseFn, Perlin};
+use crate::parallel;
+use crate::seed;
 use params::{NoiseParams, TerrainClass};
+use simd::{fill_lanes, LanePerlin, LANES};
 
 /// Target HI per terrain class from Phase 1 empirical data.
 fn target_hi(tc: TerrainClass) -> f32 {
@@ -46,12 +49,13 @@ fn elevation_range(tc: TerrainClass) -> f32 {
 ///   5. Scale to terrain-class elevation range.
 ///   6. Apply hypsometric shaping.
 ///
-/// `seed` is a `u32` tile seed; geographic bounds are used only for the returned
+/// `seed` is the tile seed; each pass derives its own sub-seed from it with
+/// `seed::derive`.  Geographic bounds are used only for the returned
 /// `HeightField` metadata (and for computing cellsize_m in scoring).
 #[allow(clippy::too_many_arguments)]
 pub fn generate_tile(
     params: &NoiseParams,
-    seed: u32,
+    seed: u64,
     width: usize,
     height: usize,
     min_lon: f64,
@@ -69,14 +73,19 @@ pub fn generate_tile(
     let smooth_freq = base_freq * 0.25; // ¼ of detail freq for smooth base
 
     // ── Pass 1: smooth base (3 octaves, no warp, no anisotropy) ────────────
-    let smooth_fbm = fbm::Fbm::new(seed ^ 0xF001, params.h_base, 3);
+    let smooth_fbm = fbm::Fbm::new(
+        seed::derive_u32(seed, "noise/smooth-base", &[]),
+        params.h_base,
+        3,
+    );
     let mut smooth = vec![0.0f32; n];
-    for r in 0..height {
-        for c in 0..width {
-            smooth[r * width + c] =
-                smooth_fbm.sample(c as f64 * smooth_freq, r as f64 * smooth_freq) as f32;
-        }
-    }
+    parallel::for_each_row(&mut smooth, width, |r, row| {
+        let y = [r as f64 * smooth_freq; LANES];
+        fill_lanes(row, |cols| {
+            let x = cols.map(|c| c as f64 * smooth_freq);
+            smooth_fbm.sample_lanes(x, y).map(|v| v as f32)
+        });
+    });
 
     // ── Percentile ranks of smooth base (for non-stationarity) ─────────────
     let mut order: Vec<usize> = (0..n).collect();
@@ -96,11 +105,13 @@ pub fn generate_tile(
         height,
         params.h_base,
         params.h_variance,
-        seed ^ 0xA100,
+        seed::derive_u32(seed, "noise/h-field", &[]),
     );
 
     // ── Pass 2: detail noise with anisotropy, warp, and local H ────────────
-    let detail_perlin = Perlin::new(seed ^ 0x0042);
+    let detail_perlin = LanePerlin::new(seed::derive_u32(seed, "noise/detail", &[]));
+    // Domain warp — amplitude < 1 pixel in noise-space to preserve Hurst scaling.
+    let warp = warp::DomainWarp::new(0.015, 0.004, seed::derive(seed, "noise/warp", &[]));
     // Gain formula: 2^(-(H + 0.35)).
     //
     // Standard fBm uses gain = 2^(-H), but for Perlin noise, saturated
@@ -113,39 +124,33 @@ pub fn generate_tile(
     let octaves: u32 = 8;
 
     let mut data = vec![0.0f32; n];
-    for r in 0..height {
-        for c in 0..width {
-            let idx = r * width + c;
-            let local_h = h_field[idx];
-
+    parallel::for_each_row(&mut data, width, |r, row| {
+        let row_off = r * width;
+        fill_lanes(row, |cols| {
             // Anisotropy transform.
-            let (xa, ya) = anisotropic::apply_anisotropy(
-                c as f64 * base_freq,
-                r as f64 * base_freq,
-                params.grain_angle as f64,
-                params.grain_intensity as f64,
-            );
-
-            // Domain warp — amplitude < 1 pixel in noise-space to preserve Hurst scaling.
-            let (xw, yw) = warp::domain_warp(xa, ya, 0.015, 0.004, seed ^ 0xBEEF);
+            let anisotropic = cols.map(|c| {
+                anisotropic::apply_anisotropy(
+                    c as f64 * base_freq,
+                    r as f64 * base_freq,
+                    params.grain_angle as f64,
+                    params.grain_intensity as f64,
+                )
+            });
+            let (xw, yw) = warp.apply_lanes(anisotropic.map(|p| p.0), anisotropic.map(|p| p.1));
 
             // Multifractal fBm with locally-varying gain.
-            let gain = gain_for(local_h);
-            let mut detail = 0.0f64;
-            let mut amp = 1.0f64;
-            let mut freq = 1.0f64;
-            for _ in 0..octaves {
-                detail += amp * detail_perlin.get([xw * freq, yw * freq]);
-                amp *= gain;
-                freq *= 2.0;
-            }
+            let gain = cols.map(|c| gain_for(h_field[row_off + c]));
+            let detail = detail_perlin.fbm(xw, yw, gain, octaves, 2.0);
 
-            // Non-stationarity: scale detail amplitude by elevation percentile.
-            let amp_mod = nonstationary::elevation_amplitude_modulation(rank[idx]) as f64;
-            // Blend: 30% smooth base + 70% amplitude-modulated detail.
-            data[idx] = (smooth[idx] as f64 * 0.3 + detail * amp_mod * 0.7) as f32;
-        }
-    }
+            std::array::from_fn(|i| {
+                let idx = row_off + cols[i];
+                // Non-stationarity: scale detail amplitude by elevation percentile.
+                let amp_mod = nonstationary::elevation_amplitude_modulation(rank[idx]) as f64;
+                // Blend: 30% smooth base + 70% amplitude-modulated detail.
+                (smooth[idx] as f64 * 0.3 + detail[i] * amp_mod * 0.7) as f32
+            })
+        });
+    });
 
     // ── Scale to terrain-class elevation range ───────────────────────────────
     let elev_range = elevation_range(params.terrain_class);
@@ -0,0 +1,253 @@
+//! Lane-batched Perlin and fBm evaluation.
+//!
+//! `LanePerlin` evaluates `LANES` 2D Perlin samples per call.  With the
+//! `simd` feature the samples go through a structure-of-arrays kernel whose
+//! per-lane arithmetic (corner split, gradients, quintic fade, lerps) is laid
+//! out for the compiler to vectorise; only the permutation-table lookups stay
+//! scalar.  Without the feature each lane falls back to `noise::Perlin`.
+//!
+//! The kernel rebuilds the `noise` crate's permutation table from the seed and
+//! performs the scalar path's floating-point operations in the same order, so
+//! the two paths agree to within `SIMD_TOLERANCE` (exactly, on the targets we
+//! test).
+use noise::Perlin;
+
+/// Samples evaluated per call.
+pub const LANES: usize = 4;
+
+/// One value per lane.
+pub type Lanes = [f64; LANES];
+
+/// Largest difference between a `simd` sample and the scalar `noise::Perlin`
+/// sample (or fBm sum of them) at the same point.
+pub const SIMD_TOLERANCE: f64 = 1e-12;
+
+/// 2D Perlin noise with a lane-batched entry point.
+pub struct LanePerlin {
+    scalar: Perlin,
+    #[cfg(feature = "simd")]
+    perm: [u8; 256],
+}
+
+impl LanePerlin {
+    pub fn new(seed: u32) -> Self {
+        Self {
+            scalar: Perlin::new(seed),
+            #[cfg(feature = "simd")]
+            perm: kernel::permutation_table(seed),
+        }
+    }
+
+    /// The scalar generator with the same seed.
+    pub fn scalar(&self) -> &Perlin {
+        &self.scalar
+    }
+
+    /// Perlin noise at `(x[i], y[i])` for every lane.
+    #[inline]
+    pub fn get(&self, x: Lanes, y: Lanes) -> Lanes {
+        #[cfg(feature = "simd")]
+        {
+            kernel::perlin_2d(&self.perm, x, y)
+        }
+        #[cfg(not(feature = "simd"))]
+        {
+            use noise::NoiseFn;
+            std::array::from_fn(|i| self.scalar.get([x[i], y[i]]))
+        }
+    }
+
+    /// fBm octave sum with a per-lane `gain`:
+    /// `Σ gain^k · perlin(x · lacunarity^k, y · lacunarity^k)` for `k < octaves`.
+    pub fn fbm(&self, x: Lanes, y: Lanes, gain: Lanes, octaves: u32, lacunarity: f64) -> Lanes {
+        let mut value = [0.0f64; LANES];
+        let mut amp = [1.0f64; LANES];
+        let mut freq = 1.0f64;
+        for _ in 0..octaves {
+            let n = self.get(x.map(|v| v * freq), y.map(|v| v * freq));
+            for i in 0..LANES {
+                value[i] += amp[i] * n[i];
+                amp[i] *= gain[i];
+            }
+            freq *= lacunarity;
+        }
+        value
+    }
+}
+
+/// Fill `out` group by group: `f` receives `LANES` consecutive indices and
+/// returns their values.  The final group is padded by repeating its last
+/// index; the padded lanes are discarded.
+pub fn fill_lanes<T: Copy>(out: &mut [T], mut f: impl FnMut([usize; LANES]) -> [T; LANES]) {
+    let n = out.len();
+    for (g, chunk) in out.chunks_mut(LANES).enumerate() {
+        let start = g * LANES;
+        let values = f(std::array::from_fn(|i| (start + i).min(n - 1)));
+        chunk.copy_from_slice(&values[..chunk.len()]);
+    }
+}
+
+#[cfg(feature = "simd")]
+mod kernel {
+    use super::{Lanes, LANES};
+    use rand::{seq::SliceRandom, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+
+    /// Same range normalisation as `noise::core::perlin::perlin_2d`.
+    const SCALE_FACTOR: f64 = 2.0 / std::f64::consts::SQRT_2;
+
+    /// `noise::permutationtable::PermutationTable::new(seed)`, whose values
+    /// are private to that crate.
+    pub(super) fn permutation_table(seed: u32) -> [u8; 256] {
+        let mut real = [0u8; 16];
+        real[0] = 1;
+        for i in 1..4 {
+            real[i * 4..i * 4 + 4].copy_from_slice(&seed.to_le_bytes());
+        }
+        let mut rng = XorShiftRng::from_seed(real);
+        let mut values: [u8; 256] = std::array::from_fn(|i| i as u8);
+        values.shuffle(&mut rng);
+        values
+    }
+
+    #[inline(always)]
+    fn hash(perm: &[u8; 256], x: isize, y: isize) -> u8 {
+        perm[perm[(x & 0xff) as usize] as usize ^ (y & 0xff) as usize]
+    }
+
+    /// Corner of the lattice cell: `noise` truncates, stepping down at ≤ 0.
+    #[inline(always)]
+    fn corner(v: f64) -> isize {
+        let t = v as isize;
+        if v <= 0.0 {
+            t - 1
+        } else {
+            t
+        }
+    }
+
+    #[inline(always)]
+    fn quintic(v: f64) -> f64 {
+        let v = v.clamp(0.0, 1.0);
+        v * v * v * (v * (v * 6.0 - 15.0) + 10.0)
+    }
+
+    #[inline(always)]
+    fn lerp(a: f64, b: f64, t: f64) -> f64 {
+        b * t + a * (1.0 - t)
+    }
+
+    /// Gradient `(±1, ±1)` selected by the low two hash bits, dotted with
+    /// `(dx, dy)`.
+    #[inline(always)]
+    fn gradient(h: u8, dx: f64, dy: f64) -> f64 {
+        let gx = if h & 1 == 0 { dx } else { -dx };
+        let gy = if h & 2 == 0 { dy } else { -dy };
+        gx + gy
+    }
+
+    pub(super) fn perlin_2d(perm: &[u8; 256], x: Lanes, y: Lanes) -> Lanes {
+        let cx = x.map(corner);
+        let cy = y.map(corner);
+        let mut dx = [0.0; LANES];
+        let mut dy = [0.0; LANES];
+        for i in 0..LANES {
+            dx[i] = x[i] - cx[i] as f64;
+            dy[i] = y[i] - cy[i] as f64;
+        }
+
+        // Permutation lookups (scalar gathers).
+        let mut h = [[0u8; 4]; LANES];
+        for i in 0..LANES {
+            h[i] = [
+                hash(perm, cx[i], cy[i]),
+                hash(perm, cx[i] + 1, cy[i]),
+                hash(perm, cx[i], cy[i] + 1),
+                hash(perm, cx[i] + 1, cy[i] + 1),
+            ];
+        }
+
+        let mut out = [0.0; LANES];
+        for i in 0..LANES {
+            let g00 = gradient(h[i][0], dx[i], dy[i]);
+            let g10 = gradient(h[i][1], dx[i] - 1.0, dy[i]);
+            let g01 = gradient(h[i][2], dx[i], dy[i] - 1.0);
+            let g11 = gradient(h[i][3], dx[i] - 1.0, dy[i] - 1.0);
+            let (u, v) = (quintic(dx[i]), quintic(dy[i]));
+            let result = lerp(lerp(g00, g01, v), lerp(g10, g11, v), u) * SCALE_FACTOR;
+            out[i] = result.clamp(-1.0, 1.0);
+        }
+        out
+    }
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::seed::StableRng;
+    use noise::NoiseFn;
+
+    fn random_lanes(rng: &mut StableRng, span: f64) -> Lanes {
+        std::array::from_fn(|_| rng.range_f64(-span, span))
+    }
+
+    #[test]
+    fn lane_perlin_matches_scalar_perlin() {
+        let mut rng = StableRng::new(11);
+        for seed in [0, 42, 0xBEEF ^ 0x0003, u32::MAX] {
+            let noise = LanePerlin::new(seed);
+            for _ in 0..2_000 {
+                let (x, y) = (random_lanes(&mut rng, 300.0), random_lanes(&mut rng, 300.0));
+                let lanes = noise.get(x, y);
+                for i in 0..LANES {
+                    let scalar = noise.scalar().get([x[i], y[i]]);
+                    assert!(
+                        (lanes[i] - scalar).abs() <= SIMD_TOLERANCE,
+                        "seed {seed} at ({}, {}): {} vs {scalar}",
+                        x[i],
+                        y[i],
+                        lanes[i]
+                    );
+                }
+            }
+            // Lattice points and zero exercise the `noise` corner rule.
+            let ints = [0.0, -1.0, 3.0, -0.0];
+            let lanes = noise.get(ints, ints);
+            for i in 0..LANES {
+                assert!(
+                    (lanes[i] - noise.scalar().get([ints[i], ints[i]])).abs() <= SIMD_TOLERANCE
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lane_fbm_matches_scalar_octave_sum() {
+        let mut rng = StableRng::new(5);
+        let noise = LanePerlin::new(77);
+        for _ in 0..500 {
+            let (x, y) = (random_lanes(&mut rng, 10.0), random_lanes(&mut rng, 10.0));
+            let gain = std::array::from_fn(|_| rng.range_f64(0.3, 0.8));
+            let lanes = noise.fbm(x, y, gain, 8, 2.0);
+            for i in 0..LANES {
+                let (mut sum, mut amp, mut freq) = (0.0, 1.0, 1.0);
+                for _ in 0..8 {
+                    sum += amp * noise.scalar().get([x[i] * freq, y[i] * freq]);
+                    amp *= gain[i];
+                    freq *= 2.0;
+                }
+                assert!((lanes[i] - sum).abs() <= SIMD_TOLERANCE);
+            }
+        }
+    }
+
+    #[test]
+    fn fill_lanes_covers_ragged_tail() {
+        let mut out = [0usize; 7];
+        fill_lanes(&mut out, |idx| idx.map(|i| i * 10));
+        assert_eq!(out, [0, 10, 20, 30, 40, 50, 60]);
+        fill_lanes(&mut [0u8; 0], |_| unreachable!());
+    }
+}
@@ -3,35 +3,85 @@
 //!
 //! Warped coordinates are used as fBm input, breaking up repetitive tiling
 //! and creating more organic-looking terrain structure.
-use noise::{NoiseFn, Perlin};
+use noise::NoiseFn;
 
-/// Warp `(x, y)` through two levels of Perlin-based domain warping.
-///
-/// * `macro_scale` — amplitude of the large-scale warp (typ. 0.15–0.25).
-/// * `micro_scale` — amplitude of the small-scale warp (typ. 0.02–0.08). 0 skips level 2.
-/// * `seed`        — base seed; warp Perlins use `seed ^ constants`.
+use super::simd::{LanePerlin, Lanes};
+use crate::seed;
+
+/// Two-level domain warp with its four Perlin generators built once.
 ///
-/// Returns warped `(x', y')` for use as fBm input.
-pub fn domain_warp(x: f64, y: f64, macro_scale: f64, micro_scale: f64, seed: u32) -> (f64, f64) {
-    let p_mx = Perlin::new(seed ^ 0x0001);
-    let p_my = Perlin::new(seed ^ 0x0002);
+/// `domain_warp` rebuilds the generators on every call; per-pixel loops
+/// should construct one `DomainWarp` and call `apply` / `apply_lanes`.
+pub struct DomainWarp {
+    macro_scale: f64,
+    micro_scale: f64,
+    p_mx: LanePerlin,
+    p_my: LanePerlin,
+    p_ux: LanePerlin,
+    p_uy: LanePerlin,
+}
+
+impl DomainWarp {
+    /// See [`domain_warp`] for the meaning of the arguments.
+    pub fn new(macro_scale: f64, micro_scale: f64, seed: u64) -> Self {
+        let perlin = |axis: u64| LanePerlin::new(seed::derive_u32(seed, "warp", &[axis]));
+        Self {
+            macro_scale,
+            micro_scale,
+            p_mx: perlin(0),
+            p_my: perlin(1),
+            p_ux: perlin(2),
+            p_uy: perlin(3),
+        }
+    }
 
-    // Level 1: macro warp with decorrelated x/y offsets.
-    let xm = x + macro_scale * p_mx.get([x, y]);
-    let ym = y + macro_scale * p_my.get([x + 5.2, y + 1.3]);
+    /// Warp a single point.
+    pub fn apply(&self, x: f64, y: f64) -> (f64, f64) {
+        // Level 1: macro warp with decorrelated x/y offsets.
+        let xm = x + self.macro_scale * self.p_mx.scalar().get([x, y]);
+        let ym = y + self.macro_scale * self.p_my.scalar().get([x + 5.2, y + 1.3]);
 
-    if micro_scale < 1e-9 {
-        return (xm, ym);
+        if self.micro_scale < 1e-9 {
+            return (xm, ym);
+        }
+
+        // Level 2: micro warp applied to already-warped coordinates.
+        let xu = xm + self.micro_scale * self.p_ux.scalar().get([xm, ym]);
+        let yu = ym + self.micro_scale * self.p_uy.scalar().get([xm + 3.7, ym + 9.1]);
+
+        (xu, yu)
     }
 
-    // Level 2: micro warp applied to already-warped coordinates.
-    let p_ux = Perlin::new(seed ^ 0x0003);
-    let p_uy = Perlin::new(seed ^ 0x0004);
+    /// Warp `LANES` points at once; lane `i` equals `apply(x[i], y[i])`
+    /// (within `simd::SIMD_TOLERANCE` under the `simd` feature).
+    pub fn apply_lanes(&self, x: Lanes, y: Lanes) -> (Lanes, Lanes) {
+        let nx = self.p_mx.get(x, y);
+        let ny = self.p_my.get(x.map(|v| v + 5.2), y.map(|v| v + 1.3));
+        let xm: Lanes = std::array::from_fn(|i| x[i] + self.macro_scale * nx[i]);
+        let ym: Lanes = std::array::from_fn(|i| y[i] + self.macro_scale * ny[i]);
 
-    let xu = xm + micro_scale * p_ux.get([xm, ym]);
-    let yu = ym + micro_scale * p_uy.get([xm + 3.7, ym + 9.1]);
+        if self.micro_scale < 1e-9 {
+            return (xm, ym);
+        }
 
-    (xu, yu)
+        let ux = self.p_ux.get(xm, ym);
+        let uy = self.p_uy.get(xm.map(|v| v + 3.7), ym.map(|v| v + 9.1));
+        (
+            std::array::from_fn(|i| xm[i] + self.micro_scale * ux[i]),
+            std::array::from_fn(|i| ym[i] + self.micro_scale * uy[i]),
+        )
+    }
+}
+
+/// Warp `(x, y)` through two levels of Perlin-based domain warping.
+///
+/// * `macro_scale` — amplitude of the large-scale warp (typ. 0.15–0.25).
+/// * `micro_scale` — amplitude of the small-scale warp (typ. 0.02–0.08). 0 skips level 2.
+/// * `seed`        — base seed; each warp Perlin derives its own sub-seed.
+///
+/// Returns warped `(x', y')` for use as fBm input.
+pub fn domain_warp(x: f64, y: f64, macro_scale: f64, micro_scale: f64, seed: u64) -> (f64, f64) {
+    DomainWarp::new(macro_scale, micro_scale, seed).apply(x, y)
 }
 
 #[cfg(test)]
@@ -58,4 +108,16 @@ mod tests {
         assert!((xo - 0.5).abs() <= 0.26, "x displacement exceeds 0.26");
         assert!((yo - 0.5).abs() <= 0.26, "y displacement exceeds 0.26");
     }
+
+    #[test]
+    fn lane_warp_matches_point_warp() {
+        use crate::noise::simd::SIMD_TOLERANCE;
+        let warp = DomainWarp::new(0.2, 0.05, 0xBEEF);
+        let (x, y) = ([0.1, -3.7, 12.25, 0.0], [4.4, 0.0, -0.6, 99.5]);
+        let (xs, ys) = warp.apply_lanes(x, y);
+        for i in 0..x.len() {
+            let (xo, yo) = warp.apply(x[i], y[i]);
+            assert!((xs[i] - xo).abs() <= SIMD_TOLERANCE && (ys[i] - yo).abs() <= SIMD_TOLERANCE);
+        }
+    }
 }
@@ -0,0 +1,96 @@
+//! Data-parallel helpers behind the `threading` feature.
+//!
+//! With `threading` enabled these dispatch to rayon; without it they are
+//! plain serial loops.  Every helper writes each output element from exactly
+//! one closure call and keeps results in index order, so a stage built on
+//! them produces bit-identical output either way.  Floating-point reductions
+//! stay serial in the callers for the same reason.
+//!
+//! Progress `report` calls made on rayon worker threads are dropped (the
+//! active run is thread-local); stages that report from inside a parallel
+//! section should report again once it returns.
+
+#[cfg(feature = "threading")]
+use rayon::prelude::*;
+
+/// `(0..n).map(f).collect()`, evaluated in parallel.
+pub(crate) fn map_range<T, F>(n: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(usize) -> T + Sync + Send,
+{
+    #[cfg(feature = "threading")]
+    {
+        (0..n).into_par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "threading"))]
+    {
+        (0..n).map(f).collect()
+    }
+}
+
+/// Call `f(row, row_slice)` for every `width`-long row of `data`.
+pub(crate) fn for_each_row<T, F>(data: &mut [T], width: usize, f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut [T]) + Sync + Send,
+{
+    if width == 0 {
+        return;
+    }
+    #[cfg(feature = "threading")]
+    {
+        data.par_chunks_mut(width)
+            .enumerate()
+            .for_each(|(r, row)| f(r, row));
+    }
+    #[cfg(not(feature = "threading"))]
+    {
+        data.chunks_mut(width)
+            .enumerate()
+            .for_each(|(r, row)| f(r, row));
+    }
+}
+
+/// Run two independent computations, potentially in parallel.
+pub(crate) fn join<A, B, RA, RB>(a: A, b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    #[cfg(feature = "threading")]
+    {
+        rayon::join(a, b)
+    }
+    #[cfg(not(feature = "threading"))]
+    {
+        (a(), b())
+    }
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_range_keeps_index_order() {
+        let v = map_range(1000, |i| i * 3);
+        assert!(v.iter().enumerate().all(|(i, &x)| x == i * 3));
+    }
+
+    #[test]
+    fn for_each_row_visits_every_row_once() {
+        let mut data = vec![0usize; 7 * 5];
+        for_each_row(&mut data, 7, |r, row| {
+            for (c, v) in row.iter_mut().enumerate() {
+                *v += r * 7 + c;
+            }
+        });
+        assert!(data.iter().enumerate().all(|(i, &x)| x == i));
+        for_each_row(&mut [0u8; 0], 0, |_, _| unreachable!());
+    }
+}
@@ -0,0 +1,236 @@
+//! Seamless slippy-map tiles keyed by `TileAddr` (Phase B).
+//!
+//! Location tiles normalise their noise per tile, so neighbours never agree
+//! along a shared edge.  Addressed tiles avoid every tile-local step:
+//!   - the base surface is the overview elevation, bilinearly resampled;
+//!   - detail is fBm evaluated at the pixel's unit-sphere position, with an
+//!     octave count fixed per zoom level and an amplitude driven by the
+//!     (continuous) base elevation;
+//!   - hydraulic shaping runs on the tile plus an overlapping apron, which is
+//!     cropped afterwards so that edge cells see the same upstream context as
+//!     their neighbours.
+//!
+//! Rows are spaced in Web Mercator like any slippy-map tile but stored in
+//! `HeightField` order (row 0 = south edge); heights are metres relative to
+//! the overview sea level.
+
+use crate::coords::TileAddr;
+use crate::generator::{classify_terrain, direct_glacial_class, GlobalParams};
+use crate::heightfield::HeightField;
+use crate::hydraulic::apply_hydraulic_shaping_with;
+use crate::noise::fbm::Fbm;
+use crate::seed;
+use crate::sphere::Vec3;
+
+use super::conditioned_tile::{sample_bilinear, COASTAL_TAPER_M, MIN_COASTAL_DETAIL};
+use super::PlanetOverview;
+
+/// Edge length of an addressed tile in pixels (slippy-map convention).
+pub const TILE_SIZE: usize = 256;
+
+/// Deepest zoom `Planet::try_tile_for_addr` accepts.  A zoom-24 pixel is
+/// about 2.4 m at the equator, far below the finest detail octave, and
+/// 2^zoom × `TILE_SIZE` still fits the `u32`/`u64` tile arithmetic.
+pub const MAX_TILE_ZOOM: u32 = 24;
+
+/// Apron width in pixels generated on every side and cropped after shaping.
+pub const TILE_APRON: usize = 32;
+
+/// Detail amplitude (m) over lowlands and sea floor.
+const LOWLAND_DETAIL_M: f32 = 150.0;
+/// Detail amplitude (m) reached at `HIGHLAND_RELIEF_M` above sea level.
+///
+/// Rougher terrain at altitude mirrors the roughness–elevation correlation
+/// targeted by the synthetic tile pipeline.
+const HIGHLAND_DETAIL_M: f32 = 1200.0;
+const HIGHLAND_RELIEF_M: f32 = 3000.0;
+
+/// Constant shift keeping the tile positive during hydraulic shaping.
+///
+/// Stream power clamps elevations at zero; a planet-independent constant
+/// (rather than the tile minimum) keeps neighbouring tiles on one datum.
+const HYDRAULIC_DATUM_SHIFT_M: f32 = 12_000.0;
+
+/// A generated slippy-map tile.
+pub struct AddressedTile {
+    pub addr: TileAddr,
+    /// `TILE_SIZE × TILE_SIZE` heights in metres relative to sea level.
+    /// Bounds are the tile's bounding box; rows are Web Mercator spaced,
+    /// row 0 = south edge.  Export it with
+    /// [`write_mercator_heightfield`](crate::export::geotiff::write_mercator_heightfield).
+    pub heightfield: HeightField,
+}
+
+/// Number of detail octaves for tiles at `zoom`.
+///
+/// The first octave has a wavelength of two overview cells (anything coarser
+/// is already in the base surface); octaves are added until the finest
+/// wavelength reaches two tile pixels.  Depends only on the zoom, so every
+/// tile at one zoom level uses the same spectrum.
+pub fn detail_octaves(zoom: u32, overview_width: usize) -> u32 {
+    let pixels_around = TILE_SIZE as f64 * (1u64 << zoom) as f64;
+    (pixels_around / overview_width as f64)
+        .log2()
+        .ceil()
+        .max(1.0) as u32
+}
+
+/// Geographic position of a (possibly fractional) pixel centre of `addr`.
+///
+/// `u`, `v` are in tile units: 0 = west/north edge, 1 = east/south edge.
+/// Values outside [0, 1] address the apron.
+fn tile_pixel_latlon(addr: TileAddr, u: f64, v: f64) -> (f64, f64) {
+    let n = (1u64 << addr.zoom) as f64;
+    let lon = (addr.x as f64 + u) / n * 360.0 - 180.0;
+    let t = (addr.y as f64 + v) / n;
+    let lat = (std::f64::consts::PI * (1.0 - 2.0 * t))
+        .sinh()
+        .atan()
+        .to_degrees();
+    (lat, lon)
+}
+
+/// Generate the seamless tile at `addr`.
+///
+/// `overview` must be the overview for `params`; `width × height` is the
+/// overview grid the fields were generated at.
+pub(crate) fn generate_addressed_tile(
+    params: &GlobalParams,
+    overview: &PlanetOverview,
+    width: usize,
+    height: usize,
+    addr: TileAddr,
+) -> AddressedTile {
+    let padded = TILE_SIZE + 2 * TILE_APRON;
+    let n = padded * padded;
+
+    // ── 1. Pixel positions (tile + apron), row 0 = south ──────────────────
+    let coords: Vec<(f64, f64)> = (0..n)
+        .map(|i| {
+            let u = ((i % padded) as f64 - TILE_APRON as f64 + 0.5) / TILE_SIZE as f64;
+            let v = 1.0 - ((i / padded) as f64 - TILE_APRON as f64 + 0.5) / TILE_SIZE as f64;
+            tile_pixel_latlon(addr, u, v)
+        })
+        .collect();
+
+    // ── 2. Base surface + global-coordinate detail ──────────────────────
+    let h_base =
+        (0.65 + params.mountain_prevalence * 0.20 - params.surface_age * 0.10).clamp(0.55, 0.90);
+    // Same +0.35 gain correction as the synthetic tile (see `generate_tile`).
+    let fbm = Fbm::new(
+        seed::derive_u32(params.seed, "tiles/detail", &[]),
+        h_base + 0.35,
+        detail_octaves(addr.zoom, width),
+    );
+    let base_freq = width as f64 / (4.0 * std::f64::consts::PI);
+
+    let tectonic_uplift = 0.5 + params.tectonic_activity * 1.5;
+    let mountain_scale = 0.7 + params.mountain_prevalence * 0.6;
+    let relief_scale = tectonic_uplift * mountain_scale * params.body.relief_scale();
+
+    let data: Vec<f32> = coords
+        .iter()
+        .map(|&(lat, lon)| {
+            let elevation_km =
+                sample_bilinear(&overview.physical_elevations, width, height, lat, lon);
+            let base = (elevation_km - overview.sea_level_km) * 1000.0;
+
+            let p = Vec3::from_latlon(lat, lon);
+            let detail = fbm.sample_3d(p.x * base_freq, p.y * base_freq, p.z * base_freq) as f32;
+
+            let highland = (base / HIGHLAND_RELIEF_M).clamp(0.0, 1.0);
+            let amplitude = LOWLAND_DETAIL_M + (HIGHLAND_DETAIL_M - LOWLAND_DETAIL_M) * highland;
+            let taper = (base.abs() / COASTAL_TAPER_M).clamp(MIN_COASTAL_DETAIL, 1.0);
+            base + detail * amplitude * relief_scale * taper
+        })
+        .collect();
+
+    // ── 3. Hydraulic shaping on the padded grid ──────────────────────────
+    let water_scale = 0.3 + params.water_abundance * 1.4;
+    let age_scale = 0.3 + params.surface_age * 1.4;
+    let erosion_factor = (water_scale * age_scale).clamp(0.05, 2.0);
+
+    // Submarine pixels get zero erodibility so stream power leaves the sea
+    // floor alone.
+    let scaled_erodibility: Vec<f32> = coords
+        .iter()
+        .zip(data.iter())
+        .map(|(&(lat, lon), &z)| {
+            if z < 0.0 {
+                0.0
+            } else {
+                let k = sample_bilinear(&overview.erodibility_field, width, height, lat, lon);
+                (k * erosion_factor).clamp(0.0, 1.0)
+            }
+        })
+        .collect();
+
+    let (lat_min, lon_min, lat_max, lon_max) = addr.bounds();
+    let apron_frac = TILE_APRON as f64 / TILE_SIZE as f64;
+    let apron_lon = (lon_max - lon_min) * apron_frac;
+    let apron_lat = (lat_max - lat_min) * apron_frac;
+    let mut hf = HeightField {
+        data: data.iter().map(|&z| z + HYDRAULIC_DATUM_SHIFT_M).collect(),
+        width: padded,
+        height: padded,
+        min_lon: lon_min - apron_lon,
+        max_lon: lon_max + apron_lon,
+        min_lat: lat_min - apron_lat,
+        max_lat: lat_max + apron_lat,
+    };
+
+    // Terrain and glacial class come from the global sliders so that every
+    // tile of the planet runs the same shaping schedule.
+    apply_hydraulic_shaping_with(
+        &mut hf,
+        params.tuning.hydraulic.for_class(classify_terrain(params)),
+        &scaled_erodibility,
+        direct_glacial_class(params.glaciation),
+    );
+
+    // ── 4. Crop the apron ────────────────────────────────────────────────
+    let mut cropped = Vec::with_capacity(TILE_SIZE * TILE_SIZE);
+    for r in TILE_APRON..TILE_APRON + TILE_SIZE {
+        let row = &hf.data[r * padded + TILE_APRON..r * padded + TILE_APRON + TILE_SIZE];
+        cropped.extend(row.iter().map(|&z| z - HYDRAULIC_DATUM_SHIFT_M));
+    }
+
+    AddressedTile {
+        addr,
+        heightfield: HeightField {
+            data: cropped,
+            width: TILE_SIZE,
+            height: TILE_SIZE,
+            min_lon: lon_min,
+            max_lon: lon_max,
+            min_lat: lat_min,
+            max_lat: lat_max,
+        },
+    }
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Octave count grows by one per zoom level once tiles out-resolve the overview.
+    #[test]
+    fn detail_octaves_track_zoom() {
+        assert_eq!(detail_octaves(0, 1024), 1);
+        assert_eq!(detail_octaves(6, 1024), 4);
+        assert_eq!(detail_octaves(7, 1024), 5);
+    }
+
+    /// Pixel positions agree with `TileAddr::bounds` at the tile corners.
+    #[test]
+    fn pixel_latlon_matches_bounds() {
+        let addr = TileAddr::new(5, 17, 9);
+        let (lat_min, lon_min, lat_max, lon_max) = addr.bounds();
+        let (lat, lon) = tile_pixel_latlon(addr, 0.0, 0.0);
+        assert!((lat - lat_max).abs() < 1e-9 && (lon - lon_min).abs() < 1e-9);
+        let (lat, lon) = tile_pixel_latlon(addr, 1.0, 1.0);
+        assert!((lat - lat_min).abs() < 1e-9 && (lon - lon_max).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,253 @@
+//! Overview-conditioned drill-down tiles (Phase B).
+//!
+//! The synthetic tile pipeline scales noise into a class-specific elevation
+//! range with no knowledge of the overview at the clicked spot.  Here the
+//! overview's structural elevation, bilinearly resampled onto the tile grid,
+//! is the base surface and the noise tile only contributes zero-mean detail.
+//! Heights are expressed in metres relative to the overview sea level, so the
+//! tile shares the globe's vertical datum and its coastline (`h < 0` is ocean).
+
+use crate::generator::{
+    noise_params_for_sample, GenerationConfig, GlobalParams, LocationSample, LocationTileResult,
+};
+use crate::heightfield::HeightField;
+use crate::hydraulic::apply_hydraulic_shaping_with;
+use crate::metrics::score::compute_realism_score;
+use crate::noise::generate_tile;
+
+use super::PlanetOverview;
+
+/// Longitudinal extent of a conditioned tile in degrees.
+///
+/// The latitudinal extent is half of this, keeping the 2:1 tile aspect.  At
+/// 1024 × 512 overview resolution the tile spans ~23 × 11 overview cells.
+pub const CONDITIONED_TILE_SPAN_DEG: f64 = 8.0;
+
+/// Fraction of the synthetic tile relief kept as detail on top of the base.
+const DETAIL_SCALE: f32 = 0.5;
+
+/// Distance from sea level (m) over which detail fades in.
+///
+/// Detail is attenuated to `MIN_COASTAL_DETAIL` at the shoreline so that the
+/// overview coastline survives in the tile instead of dissolving into noise.
+pub(super) const COASTAL_TAPER_M: f32 = 500.0;
+pub(super) const MIN_COASTAL_DETAIL: f32 = 0.2;
+
+/// Geographic footprint `(min_lon, max_lon, min_lat, max_lat)` of the
+/// conditioned tile centred on `(lat, lon)`.
+///
+/// Near the poles the window is shifted so that it stays within ±90°.
+/// Longitudes are left unwrapped and may extend past ±180°.
+pub fn conditioned_tile_bounds(lat: f32, lon: f32) -> (f64, f64, f64, f64) {
+    let half_lon = CONDITIONED_TILE_SPAN_DEG / 2.0;
+    let half_lat = CONDITIONED_TILE_SPAN_DEG / 4.0;
+    let centre_lat = (lat as f64).clamp(-90.0 + half_lat, 90.0 - half_lat);
+    let centre_lon = lon as f64;
+    (
+        centre_lon - half_lon,
+        centre_lon + half_lon,
+        centre_lat - half_lat,
+        centre_lat + half_lat,
+    )
+}
+
+/// Bilinear sample of an equirectangular `w × h` field at `(lat, lon)`.
+///
+/// Cell centres sit at half-cell offsets; longitude wraps, latitude clamps.
+pub(super) fn sample_bilinear(field: &[f32], w: usize, h: usize, lat: f64, lon: f64) -> f32 {
+    let fx = (lon + 180.0) / 360.0 * w as f64 - 0.5;
+    let fy = ((90.0 - lat) / 180.0 * h as f64 - 0.5).clamp(0.0, (h - 1) as f64);
+
+    let x0 = fx.floor();
+    let y0 = fy.floor();
+    let tx = (fx - x0) as f32;
+    let ty = (fy - y0) as f32;
+
+    let c0 = (x0 as i64).rem_euclid(w as i64) as usize;
+    let c1 = (c0 + 1) % w;
+    let r0 = y0 as usize;
+    let r1 = (r0 + 1).min(h - 1);
+
+    let v00 = field[r0 * w + c0];
+    let v10 = field[r0 * w + c1];
+    let v01 = field[r1 * w + c0];
+    let v11 = field[r1 * w + c1];
+
+    v00 * (1.0 - tx) * (1.0 - ty) + v10 * tx * (1.0 - ty) + v01 * (1.0 - tx) * ty + v11 * tx * ty
+}
+
+/// Row-major index of the `w × h` cell containing `(lat, lon)`, wrapping longitude.
+pub(super) fn nearest_cell(w: usize, h: usize, lat: f64, lon: f64) -> usize {
+    let col = (((lon + 180.0) / 360.0 * w as f64).floor() as i64).rem_euclid(w as i64) as usize;
+    let row = ((90.0 - lat) / 180.0 * h as f64).clamp(0.0, (h - 1) as f64) as usize;
+    row * w + col
+}
+
+/// Generate a drill-down tile whose base surface is the overview elevation.
+///
+/// Pipeline:
+///   1. Resample `physical_elevations` onto the tile grid, relative to sea level.
+///   2. Synthesise a noise tile from the location sample and keep its
+///      zero-mean detail, tapered towards the shoreline.
+///   3. Hydraulic shaping with per-pixel overview erodibility (zero offshore).
+///   4. Realism scoring.
+///
+/// `overview` must be the overview for `params`; the tile is generated at
+/// the tile resolution of `config`.
+pub(crate) fn generate_conditioned_tile(
+    params: &GlobalParams,
+    config: &GenerationConfig,
+    overview: &PlanetOverview,
+    sample: &LocationSample,
+) -> LocationTileResult {
+    let terrain_class = sample.terrain_class;
+    let (min_lon, max_lon, min_lat, max_lat) = conditioned_tile_bounds(sample.lat, sample.lon);
+    let (tile_w, tile_h) = (config.tile_width, config.tile_height);
+    let (width, height) = (overview.width, overview.height);
+    let n = tile_w * tile_h;
+
+    // Pixel-centre geographic coordinates, row 0 = south edge (HeightField order).
+    let pixel_lat = |r: usize| min_lat + (r as f64 + 0.5) / tile_h as f64 * (max_lat - min_lat);
+    let pixel_lon = |c: usize| min_lon + (c as f64 + 0.5) / tile_w as f64 * (max_lon - min_lon);
+
+    // ── 1. Base surface from the overview structural elevation ──────────
+    let mut base = vec![0.0f32; n];
+    for r in 0..tile_h {
+        let lat = pixel_lat(r);
+        for c in 0..tile_w {
+            let elevation_km = sample_bilinear(
+                &overview.physical_elevations,
+                width,
+                height,
+                lat,
+                pixel_lon(c),
+            );
+            base[r * tile_w + c] = (elevation_km - overview.sea_level_km) * 1000.0;
+        }
+    }
+
+    // ── 2. Noise detail on top of the base ──────────────────────────────
+    let noise_params = noise_params_for_sample(params, sample);
+    let detail = generate_tile(
+        &noise_params,
+        params.seed,
+        tile_w,
+        tile_h,
+        min_lon,
+        max_lon,
+        min_lat,
+        max_lat,
+    );
+    let detail_mean = detail.data.iter().sum::<f32>() / n as f32;
+
+    let tectonic_uplift = 0.5 + params.tectonic_activity * 1.5;
+    let mountain_scale = 0.7 + params.mountain_prevalence * 0.6;
+    let detail_scale = DETAIL_SCALE * tectonic_uplift * mountain_scale * params.body.relief_scale();
+
+    let data: Vec<f32> = base
+        .iter()
+        .zip(detail.data.iter())
+        .map(|(&b, &d)| {
+            let taper = (b.abs() / COASTAL_TAPER_M).clamp(MIN_COASTAL_DETAIL, 1.0);
+            b + (d - detail_mean) * detail_scale * taper
+        })
+        .collect();
+
+    let mut hf = HeightField {
+        data,
+        width: tile_w,
+        height: tile_h,
+        min_lon,
+        max_lon,
+        min_lat,
+        max_lat,
+    };
+
+    // ── 3. Hydraulic shaping ─────────────────────────────────────────────
+    let water_scale = 0.3 + params.water_abundance * 1.4;
+    let age_scale = 0.3 + params.surface_age * 1.4;
+    let erosion_factor = (water_scale * age_scale).clamp(0.05, 2.0);
+
+    // Per-pixel overview fields (nearest cell).  Submarine pixels get zero
+    // erodibility so stream power does not carve the sea floor.
+    let mut regime_field = Vec::with_capacity(n);
+    let mut map_field = Vec::with_capacity(n);
+    let mut scaled_erodibility = Vec::with_capacity(n);
+    for r in 0..tile_h {
+        let lat = pixel_lat(r);
+        for c in 0..tile_w {
+            let idx = nearest_cell(width, height, lat, pixel_lon(c));
+            regime_field.push(overview.regimes[idx]);
+            map_field.push(overview.map_field[idx]);
+            let k = if hf.data[r * tile_w + c] < 0.0 {
+                0.0
+            } else {
+                (overview.erodibility_field[idx] * erosion_factor).clamp(0.0, 1.0)
+            };
+            scaled_erodibility.push(k);
+        }
+    }
+
+    // Stream power clamps elevations at zero, so shape the tile on a
+    // non-negative copy and restore the sea-level datum afterwards.
+    let datum_offset = hf.min_elevation().min(0.0);
+    for v in &mut hf.data {
+        *v -= datum_offset;
+    }
+    apply_hydraulic_shaping_with(
+        &mut hf,
+        params.tuning.hydraulic.for_class(terrain_class),
+        &scaled_erodibility,
+        sample.glaciation,
+    );
+    for v in &mut hf.data {
+        *v += datum_offset;
+    }
+
+    // ── 4. Realism scoring ───────────────────────────────────────────────
+    let score = compute_realism_score(&hf, terrain_class);
+
+    LocationTileResult {
+        heightfield: hf,
+        regime_field,
+        map_field,
+        score,
+        generation_time_ms: 0,
+        lat: sample.lat,
+        lon: sample.lon,
+        terrain_class,
+        local_regime: sample.regime,
+        local_map_mm: sample.map_mm,
+        local_erodibility: sample.erodibility,
+        local_grain_angle: sample.grain_angle,
+        local_grain_intensity: sample.grain_intensity,
+        local_glaciation: sample.glaciation,
+    }
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Bilinear sampling reproduces cell values at cell centres and wraps in longitude.
+    #[test]
+    fn bilinear_hits_cell_centres_and_wraps() {
+        let (w, h) = (4usize, 2usize);
+        let field: Vec<f32> = (0..w * h).map(|i| i as f32).collect();
+        // Cell (row 0, col 1) centre: lat 45, lon -45.
+        assert!((sample_bilinear(&field, w, h, 45.0, -45.0) - 1.0).abs() < 1e-5);
+        // Halfway between the last and first column blends across the seam.
+        let seam = sample_bilinear(&field, w, h, 45.0, 180.0);
+        assert!((seam - 1.5).abs() < 1e-5, "seam sample {seam}");
+    }
+
+    /// Footprint keeps the 2:1 aspect and stays inside ±90° near the poles.
+    #[test]
+    fn bounds_clamped_at_poles() {
+        let (min_lon, max_lon, min_lat, max_lat) = conditioned_tile_bounds(89.0, 10.0);
+        assert!(max_lat <= 90.0 + 1e-9);
+        assert!(((max_lon - min_lon) - 2.0 * (max_lat - min_lat)).abs() < 1e-9);
+    }
+}
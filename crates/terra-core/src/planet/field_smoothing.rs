@@ -5,29 +5,50 @@
 //! boundaries. The kernel is separable: one horizontal pass followed by one
 //! vertical pass, giving O(N·k) cost instead of O(N·k²).
 
-/// Smoothing radius presets matching the PA.6 boundary types (in grid cells).
+use serde::{Deserialize, Serialize};
+
+use crate::parallel;
+use crate::sphere::{equatorial_cell_km, EARTH_RADIUS_KM};
+
+/// Smoothing radius presets matching the PA.6 boundary types (in kilometres).
+///
+/// Sigmas are physical so that the overview looks the same at any grid
+/// resolution; [`SmoothingParams::sigma_cells`] converts them for a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SmoothingParams {
     /// Sigma for tectonic regime boundaries (ridge/subduction — geologically sharp).
-    /// Corresponds to ~50–100 km at 1024×512 planetary scale.
-    pub regime_sigma: f32,
+    /// Blur extent ~50–100 km.
+    pub regime_sigma_km: f32,
     /// Sigma for MAP/climate transitions (gradual latitudinal/orographic).
-    /// Corresponds to ~500–1400 km at 1024×512 planetary scale.
-    pub climate_sigma: f32,
+    /// Blur extent ~500–1400 km.
+    pub climate_sigma_km: f32,
     /// Sigma for erodibility transitions (moderate geological variation).
-    /// Corresponds to ~100–300 km at 1024×512 planetary scale.
-    pub erodibility_sigma: f32,
+    /// Blur extent ~100–300 km.
+    pub erodibility_sigma_km: f32,
 }
 
 impl Default for SmoothingParams {
+    /// 1.5, 36 and 3 cells of the 1024 × 512 Earth grid the presets were
+    /// tuned on.
     fn default() -> Self {
+        let cell_km = equatorial_cell_km(1024, EARTH_RADIUS_KM);
         Self {
-            regime_sigma: 1.5,
-            climate_sigma: 36.0,
-            erodibility_sigma: 3.0,
+            regime_sigma_km: (1.5 * cell_km) as f32,
+            climate_sigma_km: (36.0 * cell_km) as f32,
+            erodibility_sigma_km: (3.0 * cell_km) as f32,
         }
     }
 }
 
+impl SmoothingParams {
+    /// Convert a sigma in kilometres to cells of a global `width`-column grid
+    /// on a sphere of `radius_km`.
+    pub fn sigma_cells(sigma_km: f32, width: usize, radius_km: f64) -> f32 {
+        (sigma_km as f64 / equatorial_cell_km(width, radius_km)) as f32
+    }
+}
+
 /// Apply a separable Gaussian blur with the given `sigma` to a row-major
 /// `width × height` field. Returns a new `Vec<f32>` of the same length.
 ///
@@ -42,32 +63,32 @@ pub fn gaussian_blur(field: &[f32], width: usize, height: usize, sigma: f32) ->
 
     // ── Horizontal pass ───────────────────────────────────────────────────
     let mut temp = vec![0.0_f32; width * height];
-    for r in 0..height {
-        let row_off = r * width;
-        for c in 0..width {
+    parallel::for_each_row(&mut temp, width, |r, row| {
+        let src = &field[r * width..(r + 1) * width];
+        for (c, v) in row.iter_mut().enumerate() {
             let mut acc = 0.0_f32;
             for (ki, &w) in kernel.iter().enumerate() {
                 let src_c = (c as isize + ki as isize - half as isize).clamp(0, width as isize - 1)
                     as usize;
-                acc += field[row_off + src_c] * w;
+                acc += src[src_c] * w;
             }
-            temp[row_off + c] = acc;
+            *v = acc;
         }
-    }
+    });
 
     // ── Vertical pass ─────────────────────────────────────────────────────
     let mut out = vec![0.0_f32; width * height];
-    for r in 0..height {
-        for c in 0..width {
+    parallel::for_each_row(&mut out, width, |r, row| {
+        for (c, v) in row.iter_mut().enumerate() {
             let mut acc = 0.0_f32;
             for (ki, &w) in kernel.iter().enumerate() {
                 let src_r = (r as isize + ki as isize - half as isize).clamp(0, height as isize - 1)
                     as usize;
                 acc += temp[src_r * width + c] * w;
             }
-            out[r * width + c] = acc;
+            *v = acc;
         }
-    }
+    });
 
     out
 }
@@ -161,4 +182,15 @@ mod tests {
             );
         }
     }
+
+    /// The default sigmas convert back to whole and half cells on the grid
+    /// they were tuned on, so the default overview keeps its kernels.
+    #[test]
+    fn default_sigmas_are_exact_cells_at_default_resolution() {
+        let sp = SmoothingParams::default();
+        let cells = |km| SmoothingParams::sigma_cells(km, 1024, EARTH_RADIUS_KM);
+        assert_eq!(cells(sp.regime_sigma_km), 1.5);
+        assert_eq!(cells(sp.climate_sigma_km), 36.0);
+        assert_eq!(cells(sp.erodibility_sigma_km), 3.0);
+    }
 }
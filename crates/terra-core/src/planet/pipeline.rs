@@ -0,0 +1,677 @@
+//! Stage-cached overview pipeline.
+//!
+//! Every overview stage reads only a few of the `GlobalParams`:
+//!
+//! | Stage            | Params                                                                                                   | Upstream  |
+//! |------------------|----------------------------------------------------------------------------------------------------------|-----------|
+//! | Plates           | seed, continental_fragmentation, mountain_prevalence, tuning.plates, body.radius_km                      | —         |
+//! | Climate          | seed, water_abundance, climate_diversity, glaciation, body.radius_km, body.axial_tilt_deg, body.rotation | Plates    |
+//! | PlateSmoothing   | tuning.smoothing.regime_sigma_km, tuning.smoothing.erodibility_sigma_km                                  | Plates    |
+//! | ClimateSmoothing | tuning.smoothing.climate_sigma_km                                                                        | Climate   |
+//! | Elevation        | seed, body.surface_gravity_m_s2                                                                          | Plates    |
+//! | SeaLevel         | water_abundance                                                                                          | Elevation |
+//! | Metrics          | water_abundance, glaciation, body.axial_tilt_deg                                                         | all above |
+//!
+//! `OverviewPipeline` keeps every stage output and, on `update`, reruns a
+//! stage only when its own params changed or an upstream stage reran.
+//! `try_new` and `try_update` validate the params, then report these stages
+//! as `ProgressEvent`s.
+//! `tectonic_activity`, `surface_age` and `tuning.hydraulic` only affect
+//! drill-down tiles, so moving them reruns nothing.
+//!
+//! The overview's `PipelineTrace` times each stage on the run that produced
+//! its current output, so after an update it mixes fresh and cached times.
+
+use serde::Serialize;
+
+use crate::body::Rotation;
+use crate::climate::ClimateLayer;
+use crate::error::TerraError;
+use crate::generator::{GenerationConfig, GlobalParams};
+use crate::plates::{regime_field::TectonicRegime, PlateSimulation, PlateTuning};
+use crate::progress::{self, Cancelled, RunControl};
+use crate::trace::{FeatureCounts, PipelineTrace, StageTiming};
+
+use super::planet_elevation::generate_planet_elevation_with;
+use super::planet_metrics::PlanetMetrics;
+use super::{
+    overview_metrics, sea_level_fields, simulate_climate_layer, simulate_plate_layer,
+    smooth_climate_fields, smooth_plate_fields, PlanetOverview, SeaLevelFields,
+};
+
+/// One cached stage of the overview pipeline, in execution order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OverviewStage {
+    Plates,
+    Climate,
+    PlateSmoothing,
+    ClimateSmoothing,
+    Elevation,
+    SeaLevel,
+    Metrics,
+}
+
+impl OverviewStage {
+    pub const ALL: [OverviewStage; 7] = [
+        OverviewStage::Plates,
+        OverviewStage::Climate,
+        OverviewStage::PlateSmoothing,
+        OverviewStage::ClimateSmoothing,
+        OverviewStage::Elevation,
+        OverviewStage::SeaLevel,
+        OverviewStage::Metrics,
+    ];
+
+    /// Stage name as reported in `ProgressEvent::stage`.
+    pub fn name(self) -> &'static str {
+        match self {
+            OverviewStage::Plates => "plates",
+            OverviewStage::Climate => "climate",
+            OverviewStage::PlateSmoothing => "plate_smoothing",
+            OverviewStage::ClimateSmoothing => "climate_smoothing",
+            OverviewStage::Elevation => "elevation",
+            OverviewStage::SeaLevel => "sea_level",
+            OverviewStage::Metrics => "metrics",
+        }
+    }
+
+    /// Typical share of a full overview run at the default resolution.
+    fn cost(self) -> f32 {
+        match self {
+            OverviewStage::Plates => 0.76,
+            OverviewStage::Climate => 0.10,
+            OverviewStage::PlateSmoothing => 0.02,
+            OverviewStage::ClimateSmoothing => 0.03,
+            OverviewStage::Elevation => 0.05,
+            OverviewStage::SeaLevel => 0.02,
+            OverviewStage::Metrics => 0.02,
+        }
+    }
+}
+
+/// Progress plan for running `stages`.
+fn progress_plan(stages: impl IntoIterator<Item = OverviewStage>) -> Vec<(&'static str, f32)> {
+    stages.into_iter().map(|s| (s.name(), s.cost())).collect()
+}
+
+/// The subset of `GlobalParams` read directly by each stage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct StageKeys {
+    plates: (u64, f32, f32, PlateTuning, f64),
+    climate: (u64, f32, f32, f32, f64, f32, Rotation),
+    plate_smoothing: (f32, f32),
+    climate_smoothing: f32,
+    elevation: (u64, f32),
+    sea_level: f32,
+    metrics: (f32, f32, f32),
+}
+
+impl StageKeys {
+    fn new(p: &GlobalParams) -> Self {
+        Self {
+            plates: (
+                p.seed,
+                p.continental_fragmentation,
+                p.mountain_prevalence,
+                p.tuning.plates,
+                p.body.radius_km,
+            ),
+            climate: (
+                p.seed,
+                p.water_abundance,
+                p.climate_diversity,
+                p.glaciation,
+                p.body.radius_km,
+                p.body.axial_tilt_deg,
+                p.body.rotation,
+            ),
+            plate_smoothing: (
+                p.tuning.smoothing.regime_sigma_km,
+                p.tuning.smoothing.erodibility_sigma_km,
+            ),
+            climate_smoothing: p.tuning.smoothing.climate_sigma_km,
+            elevation: (p.seed, p.body.surface_gravity_m_s2),
+            sea_level: p.water_abundance,
+            metrics: (p.water_abundance, p.glaciation, p.body.axial_tilt_deg),
+        }
+    }
+}
+
+/// Overview pipeline that keeps its stage outputs between runs.
+///
+/// ```no_run
+/// # use terra_core::generator::{GenerationConfig, GlobalParams};
+/// # use terra_core::planet::OverviewPipeline;
+/// let mut params = GlobalParams::default();
+/// let mut pipeline = OverviewPipeline::new(&params, GenerationConfig::default());
+/// params.glaciation = 0.8;
+/// let ran = pipeline.update(&params); // [Climate, ClimateSmoothing, Metrics]
+/// let overview = pipeline.overview();
+/// ```
+pub struct OverviewPipeline {
+    config: GenerationConfig,
+    params: GlobalParams,
+    keys: StageKeys,
+    /// Stages whose output does not yet reflect `keys`, indexed by
+    /// `OverviewStage as usize`.  Only non-empty after a cancelled update.
+    stale: [bool; 7],
+    /// Wall time of the run that produced each stage's current output;
+    /// `None` for outputs read back from a save file.
+    stage_ms: [Option<f64>; 7],
+    plates: PlateSimulation,
+    climate: ClimateLayer,
+    regimes: Vec<TectonicRegime>,
+    erodibility: Vec<f32>,
+    map_smoothed: Vec<f32>,
+    physical_elevations: Vec<f32>,
+    sea: SeaLevelFields,
+    planet_metrics: PlanetMetrics,
+}
+
+impl OverviewPipeline {
+    /// Run every stage once for `params` at the overview resolution of `config`.
+    pub fn new(params: &GlobalParams, config: GenerationConfig) -> Self {
+        Self::run_new(params, config, &mut RunControl::new())
+            .expect("a run without a shared cancel token is never cancelled")
+    }
+
+    /// `new` after validating `params` and `config`, reporting each stage to
+    /// `control` and stopping early if its cancel token is set.
+    pub fn try_new(
+        params: &GlobalParams,
+        config: GenerationConfig,
+        control: &mut RunControl,
+    ) -> Result<Self, TerraError> {
+        params.validate()?;
+        config.validate()?;
+        Ok(Self::run_new(params, config, control)?)
+    }
+
+    fn run_new(
+        params: &GlobalParams,
+        config: GenerationConfig,
+        control: &mut RunControl,
+    ) -> Result<Self, Cancelled> {
+        use OverviewStage::*;
+        let plan = progress_plan(OverviewStage::ALL);
+        let (mut pipeline, timings) = progress::run(control, &plan, || {
+            progress::stage(Plates.name())?;
+            let plates = simulate_plate_layer(params, &config);
+            progress::stage(Climate.name())?;
+            let climate = simulate_climate_layer(params, &plates);
+            progress::stage(PlateSmoothing.name())?;
+            let (regimes, erodibility) =
+                smooth_plate_fields(&plates, &params.tuning.smoothing, params.body.radius_km);
+            progress::stage(ClimateSmoothing.name())?;
+            let map_smoothed =
+                smooth_climate_fields(&climate, &params.tuning.smoothing, params.body.radius_km);
+            progress::stage(Elevation.name())?;
+            let physical_elevations =
+                generate_planet_elevation_with(&plates, &params.body, params.seed);
+            progress::stage(SeaLevel.name())?;
+            let sea = sea_level_fields(&physical_elevations, params.water_abundance);
+            progress::stage(Metrics.name())?;
+            let planet_metrics =
+                overview_metrics(params, &plates, &climate, &regimes, &map_smoothed, &sea);
+            Ok(Self {
+                config,
+                params: params.clone(),
+                keys: StageKeys::new(params),
+                stale: [false; 7],
+                stage_ms: [None; 7],
+                plates,
+                climate,
+                regimes,
+                erodibility,
+                map_smoothed,
+                physical_elevations,
+                sea,
+                planet_metrics,
+            })
+        })?;
+        pipeline.record_timings(timings);
+        Ok(pipeline)
+    }
+
+    fn record_timings(&mut self, timings: Vec<StageTiming>) {
+        for timing in timings {
+            if let Some(stage) = OverviewStage::ALL
+                .into_iter()
+                .find(|s| s.name() == timing.stage)
+            {
+                self.stage_ms[stage as usize] = Some(timing.wall_ms);
+            }
+        }
+    }
+
+    /// Rebuild a pipeline around stage outputs computed earlier, such as a
+    /// planet read back from a save file.  Nothing reruns until the params
+    /// change.
+    pub(crate) fn from_outputs(
+        params: &GlobalParams,
+        config: GenerationConfig,
+        plates: PlateSimulation,
+        climate: ClimateLayer,
+        overview: PlanetOverview,
+    ) -> Self {
+        Self {
+            config,
+            params: params.clone(),
+            keys: StageKeys::new(params),
+            stale: [false; 7],
+            stage_ms: [None; 7],
+            plates,
+            climate,
+            regimes: overview.regimes,
+            erodibility: overview.erodibility_field,
+            map_smoothed: overview.map_field,
+            physical_elevations: overview.physical_elevations,
+            sea: SeaLevelFields {
+                elevations: overview.elevations,
+                ocean_mask: overview.ocean_mask,
+                sea_level_km: overview.sea_level_km,
+            },
+            planet_metrics: overview.planet_metrics,
+        }
+    }
+
+    /// Bring the cached stages up to date with `params`.
+    ///
+    /// Returns the stages that reran, in execution order; an empty list means
+    /// the overview is unchanged.
+    pub fn update(&mut self, params: &GlobalParams) -> Vec<OverviewStage> {
+        self.run_update(params, &mut RunControl::new())
+            .expect("a run without a shared cancel token is never cancelled")
+    }
+
+    /// `update` after validating `params`, reporting each rerun stage to
+    /// `control` and stopping early if its cancel token is set.
+    ///
+    /// Invalid params leave the pipeline untouched.  A cancelled update
+    /// leaves it consistent: the stages it did not reach stay marked stale
+    /// and rerun on the next update.
+    pub fn try_update(
+        &mut self,
+        params: &GlobalParams,
+        control: &mut RunControl,
+    ) -> Result<Vec<OverviewStage>, TerraError> {
+        params.validate()?;
+        Ok(self.run_update(params, control)?)
+    }
+
+    fn run_update(
+        &mut self,
+        params: &GlobalParams,
+        control: &mut RunControl,
+    ) -> Result<Vec<OverviewStage>, Cancelled> {
+        use OverviewStage::*;
+        let keys = StageKeys::new(params);
+        let old = self.keys;
+
+        // A stage is stale when its own params changed or an upstream stage is.
+        let stale = &mut self.stale;
+        stale[Plates as usize] |= keys.plates != old.plates;
+        stale[Climate as usize] |= stale[Plates as usize] || keys.climate != old.climate;
+        stale[PlateSmoothing as usize] |=
+            stale[Plates as usize] || keys.plate_smoothing != old.plate_smoothing;
+        stale[ClimateSmoothing as usize] |=
+            stale[Climate as usize] || keys.climate_smoothing != old.climate_smoothing;
+        stale[Elevation as usize] |= stale[Plates as usize] || keys.elevation != old.elevation;
+        stale[SeaLevel as usize] |= stale[Elevation as usize] || keys.sea_level != old.sea_level;
+        stale[Metrics as usize] |=
+            stale[..Metrics as usize].contains(&true) || keys.metrics != old.metrics;
+
+        self.keys = keys;
+        self.params = params.clone();
+
+        let pending: Vec<OverviewStage> = OverviewStage::ALL
+            .into_iter()
+            .filter(|&s| self.stale[s as usize])
+            .collect();
+        if pending.is_empty() {
+            return Ok(pending);
+        }
+
+        let plan = progress_plan(pending.iter().copied());
+        let ((), timings) = progress::run(control, &plan, || {
+            for &stage in &pending {
+                progress::stage(stage.name())?;
+                // Untimed until the run finishes, in case it is cancelled.
+                self.stage_ms[stage as usize] = None;
+                self.run_stage(stage, params);
+                self.stale[stage as usize] = false;
+            }
+            Ok(())
+        })?;
+        self.record_timings(timings);
+        Ok(pending)
+    }
+
+    fn run_stage(&mut self, stage: OverviewStage, params: &GlobalParams) {
+        match stage {
+            OverviewStage::Plates => {
+                self.plates = simulate_plate_layer(params, &self.config);
+            }
+            OverviewStage::Climate => {
+                self.climate = simulate_climate_layer(params, &self.plates);
+            }
+            OverviewStage::PlateSmoothing => {
+                (self.regimes, self.erodibility) = smooth_plate_fields(
+                    &self.plates,
+                    &params.tuning.smoothing,
+                    params.body.radius_km,
+                );
+            }
+            OverviewStage::ClimateSmoothing => {
+                self.map_smoothed = smooth_climate_fields(
+                    &self.climate,
+                    &params.tuning.smoothing,
+                    params.body.radius_km,
+                );
+            }
+            OverviewStage::Elevation => {
+                self.physical_elevations =
+                    generate_planet_elevation_with(&self.plates, &params.body, params.seed);
+            }
+            OverviewStage::SeaLevel => {
+                self.sea = sea_level_fields(&self.physical_elevations, params.water_abundance);
+            }
+            OverviewStage::Metrics => {
+                self.planet_metrics = overview_metrics(
+                    params,
+                    &self.plates,
+                    &self.climate,
+                    &self.regimes,
+                    &self.map_smoothed,
+                    &self.sea,
+                );
+            }
+        }
+    }
+
+    pub fn config(&self) -> &GenerationConfig {
+        &self.config
+    }
+
+    /// The params the cached stages were last computed for.
+    pub fn params(&self) -> &GlobalParams {
+        &self.params
+    }
+
+    pub fn plates(&self) -> &PlateSimulation {
+        &self.plates
+    }
+
+    pub fn climate(&self) -> &ClimateLayer {
+        &self.climate
+    }
+
+    /// Stage timings, sea level and feature counts of the cached outputs.
+    pub fn trace(&self) -> PipelineTrace {
+        PipelineTrace {
+            stages: OverviewStage::ALL
+                .into_iter()
+                .filter_map(|s| {
+                    self.stage_ms[s as usize].map(|wall_ms| StageTiming {
+                        stage: s.name().to_owned(),
+                        wall_ms,
+                    })
+                })
+                .collect(),
+            sea_level_km: Some(self.sea.sea_level_km),
+            counts: FeatureCounts {
+                plates: self.plates.n_plates,
+                hotspots: self.plates.hotspots.len(),
+                continents: self.plates.n_continents,
+                basins: None,
+            },
+            ..PipelineTrace::default()
+        }
+    }
+
+    /// Assemble the overview from the cached stage outputs.
+    pub fn overview(&self) -> PlanetOverview {
+        PlanetOverview {
+            width: self.plates.width,
+            height: self.plates.height,
+            elevations: self.sea.elevations.clone(),
+            physical_elevations: self.physical_elevations.clone(),
+            ocean_mask: self.sea.ocean_mask.clone(),
+            sea_level_km: self.sea.sea_level_km,
+            regimes: self.regimes.clone(),
+            map_field: self.map_smoothed.clone(),
+            erodibility_field: self.erodibility.clone(),
+            glaciation: self.climate.glaciation_mask.clone(),
+            planet_metrics: self.planet_metrics.clone(),
+            generation_time_ms: 0, // set by caller
+            trace: self.trace(),
+        }
+    }
+
+    /// As `overview`, moving the stage outputs instead of copying them.
+    pub fn into_overview(self) -> PlanetOverview {
+        let trace = self.trace();
+        PlanetOverview {
+            width: self.plates.width,
+            height: self.plates.height,
+            elevations: self.sea.elevations,
+            physical_elevations: self.physical_elevations,
+            ocean_mask: self.sea.ocean_mask,
+            sea_level_km: self.sea.sea_level_km,
+            regimes: self.regimes,
+            map_field: self.map_smoothed,
+            erodibility_field: self.erodibility,
+            glaciation: self.climate.glaciation_mask,
+            planet_metrics: self.planet_metrics,
+            generation_time_ms: 0, // set by caller
+            trace,
+        }
+    }
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::progress::{CancelToken, ProgressEvent};
+    use OverviewStage::*;
+
+    fn small_config() -> GenerationConfig {
+        GenerationConfig {
+            overview_width: 128,
+            overview_height: 64,
+            ..GenerationConfig::default()
+        }
+    }
+
+    fn assert_same_overview(a: &PlanetOverview, b: &PlanetOverview) {
+        assert_eq!(a.physical_elevations, b.physical_elevations);
+        assert_eq!(a.elevations, b.elevations);
+        assert_eq!(a.ocean_mask, b.ocean_mask);
+        assert_eq!(a.sea_level_km, b.sea_level_km);
+        assert_eq!(a.regimes, b.regimes);
+        assert_eq!(a.map_field, b.map_field);
+        assert_eq!(a.erodibility_field, b.erodibility_field);
+        assert_eq!(a.glaciation, b.glaciation);
+        for (ma, mb) in a
+            .planet_metrics
+            .metrics
+            .iter()
+            .zip(&b.planet_metrics.metrics)
+        {
+            assert_eq!(ma.raw_value, mb.raw_value, "{}", ma.name);
+        }
+    }
+
+    /// Each slider reruns exactly the stages that depend on it.
+    #[test]
+    fn slider_changes_rerun_only_dependent_stages() {
+        let base = GlobalParams::default();
+        let mut pipeline = OverviewPipeline::new(&base, small_config());
+        let cases: [(GlobalParams, &[OverviewStage]); 5] = [
+            (base.clone(), &[]),
+            (
+                GlobalParams {
+                    tectonic_activity: 0.9,
+                    surface_age: 0.1,
+                    ..base.clone()
+                },
+                &[],
+            ),
+            (
+                GlobalParams {
+                    glaciation: 0.8,
+                    ..base.clone()
+                },
+                &[Climate, ClimateSmoothing, Metrics],
+            ),
+            (
+                GlobalParams {
+                    water_abundance: 0.4,
+                    ..base.clone()
+                },
+                &[Climate, ClimateSmoothing, SeaLevel, Metrics],
+            ),
+            (
+                GlobalParams {
+                    seed: 7,
+                    ..base.clone()
+                },
+                &OverviewStage::ALL,
+            ),
+        ];
+        for (params, expected) in cases {
+            assert_eq!(pipeline.update(&params), expected, "{params:?}");
+        }
+    }
+
+    #[test]
+    fn tuning_changes_rerun_only_dependent_stages() {
+        let base = GlobalParams::default();
+        let mut pipeline = OverviewPipeline::new(&base, small_config());
+
+        let mut params = base.clone();
+        params.tuning.hydraulic.alpine.erosion_iters = 60;
+        assert_eq!(pipeline.update(&params), []);
+
+        params.tuning.smoothing.climate_sigma_km = 700.0;
+        assert_eq!(pipeline.update(&params), [ClimateSmoothing, Metrics]);
+
+        params.tuning.smoothing.regime_sigma_km = 120.0;
+        assert_eq!(pipeline.update(&params), [PlateSmoothing, Metrics]);
+        let fresh = OverviewPipeline::new(&params, small_config()).into_overview();
+        assert_same_overview(&pipeline.overview(), &fresh);
+
+        params.tuning.plates.hotspots = 8;
+        assert_eq!(pipeline.update(&params), OverviewStage::ALL);
+    }
+
+    #[test]
+    fn body_changes_rerun_only_dependent_stages() {
+        let base = GlobalParams::default();
+        let mut pipeline = OverviewPipeline::new(&base, small_config());
+
+        let mut params = base.clone();
+        params.body.axial_tilt_deg = 40.0;
+        assert_eq!(
+            pipeline.update(&params),
+            [Climate, ClimateSmoothing, Metrics]
+        );
+
+        params.body.rotation = Rotation::Retrograde;
+        assert_eq!(
+            pipeline.update(&params),
+            [Climate, ClimateSmoothing, Metrics]
+        );
+
+        params.body.surface_gravity_m_s2 = 3.71;
+        assert_eq!(pipeline.update(&params), [Elevation, SeaLevel, Metrics]);
+        let fresh = OverviewPipeline::new(&params, small_config()).into_overview();
+        assert_same_overview(&pipeline.overview(), &fresh);
+
+        params.body.radius_km = 3389.5;
+        assert_eq!(pipeline.update(&params), OverviewStage::ALL);
+    }
+
+    /// The trace keeps the times of stages served from the cache.
+    #[test]
+    fn trace_times_every_stage_and_keeps_cached_times() {
+        let base = GlobalParams::default();
+        let mut pipeline = OverviewPipeline::new(&base, small_config());
+        let before = pipeline.trace();
+        assert_eq!(before.stages.len(), OverviewStage::ALL.len());
+        assert_eq!(before.sea_level_km, Some(pipeline.overview().sea_level_km));
+        assert_eq!(before.counts.basins, None);
+        assert!(before.counts.plates > 0);
+
+        let params = GlobalParams {
+            glaciation: 0.8,
+            ..base
+        };
+        pipeline.update(&params);
+        let after = pipeline.trace();
+        assert_eq!(after.stages.len(), OverviewStage::ALL.len());
+        assert_eq!(after.stage_ms("plates"), before.stage_ms("plates"));
+        assert_eq!(after.counts, before.counts);
+    }
+
+    /// A cancelled update resumes where it stopped and then matches a fresh run.
+    #[test]
+    fn cancelled_update_resumes_interrupted_stages() {
+        let base = GlobalParams::default();
+        let mut pipeline = OverviewPipeline::new(&base, small_config());
+        let params = GlobalParams {
+            glaciation: 0.9,
+            ..base
+        };
+
+        // Cancel as soon as the climate stage starts: it still completes,
+        // the stages after it do not.
+        let token = CancelToken::new();
+        let trigger = token.clone();
+        let mut control =
+            RunControl::new()
+                .with_cancel_token(token)
+                .with_observer(move |e: &ProgressEvent| {
+                    if e.stage == "climate" {
+                        trigger.cancel();
+                    }
+                });
+        assert_eq!(
+            pipeline.try_update(&params, &mut control),
+            Err(TerraError::Cancelled)
+        );
+
+        assert_eq!(pipeline.update(&params), [ClimateSmoothing, Metrics]);
+        let fresh = OverviewPipeline::new(&params, small_config()).into_overview();
+        assert_same_overview(&pipeline.overview(), &fresh);
+    }
+
+    /// An incrementally updated pipeline matches a fresh run for the same params.
+    #[test]
+    fn incremental_update_matches_fresh_run() {
+        let base = GlobalParams::default();
+        let mut pipeline = OverviewPipeline::new(&base, small_config());
+        for params in [
+            GlobalParams {
+                climate_diversity: 0.9,
+                ..base.clone()
+            },
+            GlobalParams {
+                water_abundance: 0.5,
+                glaciation: 0.6,
+                ..base.clone()
+            },
+            GlobalParams {
+                mountain_prevalence: 0.2,
+                ..base.clone()
+            },
+        ] {
+            pipeline.update(&params);
+            let fresh = OverviewPipeline::new(&params, small_config()).into_overview();
+            assert_same_overview(&pipeline.overview(), &fresh);
+        }
+    }
+}
@@ -2,9 +2,15 @@
 //!
 //! The overview elevation field is driven directly by plate geometry:
 //! - crust type sets the base thickness
-//! - subduction thickens the overriding plate into mountain belts
+//! - subduction thickens the overriding plate into mountain belts, and cuts
+//!   the `plates::subduction` cross-section: trench, accretionary wedge,
+//!   volcanic arc and, behind slow subduction, a back-arc basin
 //! - ridges thin continental crust and buoy up young oceanic crust
-//! - hotspots locally thicken the crust
+//! - hotspots thicken the crust into a swell and build volcanic edifices
+//!   along their tracks; older edifices erode and the swell under them
+//!   subsides with the plate-cooling curve
+//! - ridge-ridge-ridge triple junctions raise a dome, with an Afar-style
+//!   depression where they split a continent
 //!
 //! Output is returned in physical kilometres above a structural datum.
 
@@ -13,16 +19,21 @@ use std::collections::BinaryHeap;
 
 use noise::{NoiseFn, Perlin};
 
+use crate::body::PlanetBody;
+use crate::parallel;
 use crate::plates::{
-    age_field::{cell_to_vec3, distance_to_mask_km},
+    age_field::{cell_to_vec3, distance_to_mask_km, distance_to_seeds_km},
     boundary_curves::{BoundaryPolyline, BoundaryType},
     continents::CrustType,
+    hotspot_tracks::HotspotTrack,
+    subduction::{cross_section, overriding_side},
+    triple_junctions::JunctionClass,
     PlateSimulation,
 };
+use crate::progress;
+use crate::seed::{self, StableRng};
 use crate::sphere::Vec3;
 
-const EARTH_RADIUS_KM: f64 = 6371.0;
-
 const OCEANIC_BASE_THICKNESS_KM: f32 = 7.0;
 const CONTINENTAL_BASE_THICKNESS_KM: f32 = 35.0;
 
@@ -32,7 +43,7 @@ const CONVERGENT_QUERY_RADIUS_KM: f64 = 1500.0;
 /// (~160 km total), eliminating the zigzag artifact at the boundary plane.
 const SIDE_TRANSITION_WIDTH_KM: f64 = 80.0;
 const COMPRESSION_SIGMA_KM: f64 = 500.0;
-const VOLCANIC_ARC_SIGMA_KM: f64 = 120.0;
+const VOLCANIC_ARC_SIGMA_KM: f64 = 80.0;
 const RIDGE_RIFT_INFLUENCE_KM: f64 = 300.0;
 const HOTSPOT_INFLUENCE_KM: f64 = 300.0;
 const HOTSPOT_EDIFICE_INFLUENCE_KM: f64 = 140.0;
@@ -45,6 +56,17 @@ const MAX_RIFT_THINNING_KM: f32 = 15.0;
 const MIN_CONTINENTAL_THICKNESS_KM: f32 = 20.0;
 const MAX_HOTSPOT_THICKENING_KM: f32 = 10.0;
 const HOTSPOT_EDIFICE_UPLIFT_KM: f32 = 2.2;
+/// e-folding time of a track edifice's height as it erodes.
+const SEAMOUNT_EROSION_MYR: f64 = 40.0;
+/// Broad thermal swell around a ridge-ridge-ridge junction (the Ethiopian
+/// and East African domes stand 1–2 km high over ~1000 km).
+const JUNCTION_DOME_SIGMA_KM: f64 = 450.0;
+const JUNCTION_DOME_UPLIFT_KM: f32 = 1.2;
+/// Afar-style depression where the three rifts cut continental crust; the
+/// Danakil floor lies ~100 m below sea level inside a 2 km high dome.
+const JUNCTION_DEPRESSION_SIGMA_KM: f64 = 110.0;
+const JUNCTION_DEPRESSION_KM: f32 = 1.8;
+const JUNCTION_INFLUENCE_KM: f64 = 3.0 * JUNCTION_DOME_SIGMA_KM;
 // ── Parsons–Sclater ocean depth-age model (Prompt 10) ────────────────────────
 /// Half-spreading rate used to convert divergent-boundary distance to age.
 /// 3 cm/yr is a typical global mean; 30 km/Ma.
@@ -175,8 +197,13 @@ fn build_cell_points(width: usize, height: usize) -> Vec<Vec3> {
     points
 }
 
-fn multi_source_grid_distance(seeds: &[bool], width: usize, height: usize) -> Vec<f32> {
-    distance_to_mask_km(width, height, seeds)
+fn multi_source_grid_distance(
+    seeds: &[bool],
+    width: usize,
+    height: usize,
+    radius_km: f64,
+) -> Vec<f32> {
+    distance_to_mask_km(width, height, radius_km, seeds)
 }
 
 fn base_thickness_km(continental_fraction: f32) -> f32 {
@@ -281,7 +308,7 @@ fn compressional_shortening_factor(
 }
 
 fn volcanic_arc_addition_km(
-    distance_km: f64,
+    arc_distance_km: f64,
     convergent_rate_cm_yr: f32,
     along_strike_modulation: f32,
     side_weight: f32,
@@ -297,7 +324,7 @@ fn volcanic_arc_addition_km(
         * rate_scale
         * along_strike_modulation.max(0.0)
         * side_weight
-        * gaussian_taper(distance_km, VOLCANIC_ARC_SIGMA_KM)
+        * gaussian_taper(arc_distance_km, VOLCANIC_ARC_SIGMA_KM)
 }
 
 fn rift_thinning_km(distance_km: f64, continental_share: f32) -> f32 {
@@ -308,33 +335,66 @@ fn rift_thinning_km(distance_km: f64, continental_share: f32) -> f32 {
     MAX_RIFT_THINNING_KM * taper * continental_share.max(0.35)
 }
 
-fn hotspot_thickening_km(distance_km: f64) -> f32 {
+/// Swell thickening `distance_km` from a seamount `age_myr` old; the swell
+/// subsides with the Parsons–Sclater curve as the plate leaves the plume.
+fn hotspot_thickening_km(distance_km: f64, age_myr: f64) -> f32 {
     if distance_km >= HOTSPOT_INFLUENCE_KM {
         return 0.0;
     }
     let taper = (-4.0 * (distance_km / HOTSPOT_INFLUENCE_KM).powi(2)).exp() as f32;
-    MAX_HOTSPOT_THICKENING_KM * taper
+    let remaining = 1.0 - parsons_sclater_subsidence_km(age_myr) / PS_OLD_ASYMPTOTE_KM as f32;
+    MAX_HOTSPOT_THICKENING_KM * taper * remaining.max(0.0)
 }
 
-fn hotspot_edifice_uplift_km(distance_km: f64) -> f32 {
+/// Edifice uplift `distance_km` from a seamount `age_myr` old.
+fn hotspot_edifice_uplift_km(distance_km: f64, age_myr: f64) -> f32 {
     if distance_km >= HOTSPOT_EDIFICE_INFLUENCE_KM {
         return 0.0;
     }
     let taper = (-4.0 * (distance_km / HOTSPOT_EDIFICE_INFLUENCE_KM).powi(2)).exp() as f32;
-    HOTSPOT_EDIFICE_UPLIFT_KM * taper
+    HOTSPOT_EDIFICE_UPLIFT_KM * taper * (-age_myr / SEAMOUNT_EROSION_MYR).exp() as f32
 }
 
-fn hash_mix_u64(mut value: u64) -> u64 {
-    value ^= value >> 30;
-    value = value.wrapping_mul(0xbf58_476d_1ce4_e5b9);
-    value ^= value >> 27;
-    value = value.wrapping_mul(0x94d0_49bb_1331_11eb);
-    value ^ (value >> 31)
+/// Swell thickening and edifice uplift of every cell, each the largest over
+/// all seamounts of all `tracks` and scaled by the plume strength.
+fn hotspot_track_relief_km(
+    points: &[Vec3],
+    tracks: &[HotspotTrack],
+    radius_km: f64,
+) -> Vec<(f32, f32)> {
+    let min_dot = (HOTSPOT_INFLUENCE_KM / radius_km).cos();
+    parallel::map_range(points.len(), |idx| {
+        let point = points[idx];
+        let (mut thickening, mut edifice) = (0.0_f32, 0.0_f32);
+        for track in tracks {
+            for seamount in &track.seamounts {
+                let dot = point.dot(seamount.position);
+                if dot < min_dot {
+                    continue;
+                }
+                let distance_km = dot.clamp(-1.0, 1.0).acos() * radius_km;
+                thickening = thickening.max(
+                    track.plume_strength * hotspot_thickening_km(distance_km, seamount.age_myr),
+                );
+                edifice = edifice.max(
+                    track.plume_strength * hotspot_edifice_uplift_km(distance_km, seamount.age_myr),
+                );
+            }
+        }
+        (thickening, edifice)
+    })
 }
 
-fn hash_unit_f64(seed: u64, salt: u64) -> f64 {
-    let bits = hash_mix_u64(seed ^ salt.wrapping_mul(0x9e37_79b9_7f4a_7c15));
-    (bits as f64) / (u64::MAX as f64)
+/// Relief of an RRR junction: a dome, cut at its centre by a depression in
+/// proportion to the continental share.
+fn rrr_junction_relief_km(distance_km: f64, continental_share: f32) -> f32 {
+    if distance_km >= JUNCTION_INFLUENCE_KM {
+        return 0.0;
+    }
+    JUNCTION_DOME_UPLIFT_KM * gaussian_taper(distance_km, JUNCTION_DOME_SIGMA_KM)
+        - continental_share
+            * JUNCTION_DEPRESSION_KM
+            * gaussian_taper(distance_km, JUNCTION_DEPRESSION_SIGMA_KM)
 }
 
 fn lerp_f32(a: f32, b: f32, t: f32) -> f32 {
@@ -354,19 +414,17 @@ fn parsons_sclater_subsidence_km(age_ma: f64) -> f32 {
     }
 }
 
-fn nearest_hotspot_distance_km(points: &[Vec3], hotspots: &[Vec3]) -> Vec<f32> {
-    let mut distances = vec![f32::INFINITY; points.len()];
-    for (idx, &point) in points.iter().enumerate() {
+fn nearest_distance_km(points: &[Vec3], targets: &[Vec3], radius_km: f64) -> Vec<f32> {
+    parallel::map_range(points.len(), |idx| {
         let mut nearest = f64::INFINITY;
-        for &hotspot in hotspots {
-            let distance_km = point.dot(hotspot).clamp(-1.0, 1.0).acos() * EARTH_RADIUS_KM;
+        for &target in targets {
+            let distance_km = points[idx].dot(target).clamp(-1.0, 1.0).acos() * radius_km;
             if distance_km < nearest {
                 nearest = distance_km;
             }
         }
-        distances[idx] = nearest as f32;
-    }
-    distances
+        nearest as f32
+    })
 }
 
 fn isotropic_fbm(perlin: &Perlin, point: Vec3, base_frequency: f64, octaves: usize) -> f32 {
@@ -430,7 +488,11 @@ fn province_linear_fbm(perlin: &Perlin, point: Vec3, grain_angle: f32) -> f32 {
     let v = point.x * cross.0 + point.y * cross.1 + point.z * cross.2;
 
     // Low frequency along grain (long features), high frequency across (narrow).
-    perlin.get([u * PROVINCE_LINEAR_ALONG_FREQ, v * PROVINCE_LINEAR_CROSS_FREQ, 0.0]) as f32
+    perlin.get([
+        u * PROVINCE_LINEAR_ALONG_FREQ,
+        v * PROVINCE_LINEAR_CROSS_FREQ,
+        0.0,
+    ]) as f32
 }
 
 /// Crustal province thickness modifier for continental interiors (Prompt 9).
@@ -489,7 +551,7 @@ fn along_strike_modulation_km(
     polyline_idx: usize,
     arc_length_km: f64,
 ) -> f32 {
-    let salt = hash_unit_f64(seed, polyline_idx as u64 + 0xB0_11);
+    let salt = StableRng::for_stage(seed, "elevation/arc-phase", &[polyline_idx as u64]).next_f64();
     let mut sum = 0.0_f64;
     let mut amplitude = 1.0_f64;
     let mut frequency = 1.0 / ARC_ALONG_STRIKE_WAVELENGTH_KM;
@@ -521,10 +583,16 @@ fn normalize_lon_delta(delta: f64) -> f64 {
     (delta + std::f64::consts::PI).rem_euclid(tau) - std::f64::consts::PI
 }
 
-fn equirectangular_distance_km(lat_a: f64, lon_a: f64, lat_b: f64, lon_b: f64) -> f64 {
+fn equirectangular_distance_km(
+    lat_a: f64,
+    lon_a: f64,
+    lat_b: f64,
+    lon_b: f64,
+    radius_km: f64,
+) -> f64 {
     let mean_lat = 0.5 * (lat_a + lat_b);
-    let dlat_km = (lat_a - lat_b) * EARTH_RADIUS_KM;
-    let dlon_km = normalize_lon_delta(lon_a - lon_b) * EARTH_RADIUS_KM * mean_lat.cos();
+    let dlat_km = (lat_a - lat_b) * radius_km;
+    let dlon_km = normalize_lon_delta(lon_a - lon_b) * radius_km * mean_lat.cos();
     (dlat_km * dlat_km + dlon_km * dlon_km).sqrt()
 }
 
@@ -582,6 +650,7 @@ fn project_onto_segment_km(
     by: f64,
     width: usize,
     height: usize,
+    radius_km: f64,
 ) -> (f64, f64) {
     let sdx = bx - ax;
     let sdy = by - ay;
@@ -598,7 +667,10 @@ fn project_onto_segment_km(
     let lon_p = pixel_lon_rad(px, width);
     let lat_q = pixel_lat_rad(qy.clamp(0.0, (height - 1) as f64), height);
     let lon_q = pixel_lon_rad(qx, width);
-    (t, equirectangular_distance_km(lat_p, lon_p, lat_q, lon_q))
+    (
+        t,
+        equirectangular_distance_km(lat_p, lon_p, lat_q, lon_q, radius_km),
+    )
 }
 
 /// Build the convergent-arc wavefront field for all convergent polylines.
@@ -618,6 +690,7 @@ fn build_convergent_arc_field(
     polylines: &[BoundaryPolyline],
     width: usize,
     height: usize,
+    radius_km: f64,
 ) -> (ConvergentArcField, ConvergentSegmentTable) {
     let n = width * height;
 
@@ -629,9 +702,7 @@ fn build_convergent_arc_field(
     let mut seed_seg = vec![u32::MAX; n];
 
     for (pi, polyline) in polylines.iter().enumerate() {
-        if polyline.dominant_character != BoundaryType::Convergent
-            || polyline.vertices.len() < 2
-        {
+        if polyline.dominant_character != BoundaryType::Convergent || polyline.vertices.len() < 2 {
             continue;
         }
         let seg_count = polyline.vertices.len() - 1 + usize::from(polyline.is_closed);
@@ -659,10 +730,9 @@ fn build_convergent_arc_field(
                 let pixel_idx = row * width + col;
 
                 // Re-unwrap relative to the pixel for an accurate projection.
-                let (ax_px, bx_px) =
-                    unwrap_segment_endpoints(a.x, b.x, col as f64, width as f64);
+                let (ax_px, bx_px) = unwrap_segment_endpoints(a.x, b.x, col as f64, width as f64);
                 let (_, dist_km) = project_onto_segment_km(
-                    col as f64, row as f64, ax_px, a.y, bx_px, b.y, width, height,
+                    col as f64, row as f64, ax_px, a.y, bx_px, b.y, width, height, radius_km,
                 );
                 let dist32 = dist_km as f32;
                 if dist32 < seed_dist[pixel_idx] {
@@ -692,8 +762,10 @@ fn build_convergent_arc_field(
         if node.distance_km as f64 >= CONVERGENT_QUERY_RADIUS_KM {
             continue;
         }
-        for (neighbor_opt, step_km) in wavefront_neighbors(node.idx, width, height) {
-            let Some(neighbor) = neighbor_opt else { continue };
+        for (neighbor_opt, step_km) in wavefront_neighbors(node.idx, width, height, radius_km) {
+            let Some(neighbor) = neighbor_opt else {
+                continue;
+            };
             let next_dist = node.distance_km + step_km;
             if next_dist < seed_dist[neighbor] {
                 seed_dist[neighbor] = next_dist;
@@ -723,10 +795,9 @@ fn build_convergent_arc_field(
         let b = &polyline.vertices[(sv + 1) % polyline.vertices.len()];
         let col = idx % width;
         let row = idx / width;
-        let (ax_px, bx_px) =
-            unwrap_segment_endpoints(a.x, b.x, col as f64, width as f64);
+        let (ax_px, bx_px) = unwrap_segment_endpoints(a.x, b.x, col as f64, width as f64);
         let (t, dist_km) = project_onto_segment_km(
-            col as f64, row as f64, ax_px, a.y, bx_px, b.y, width, height,
+            col as f64, row as f64, ax_px, a.y, bx_px, b.y, width, height, radius_km,
         );
         refined_dist[idx] = dist_km as f32;
         refined_t[idx] = t as f32;
@@ -744,21 +815,26 @@ fn build_convergent_arc_field(
     (field, table)
 }
 
-fn ns_step_km(height: usize) -> f32 {
-    (std::f64::consts::PI * EARTH_RADIUS_KM / height as f64) as f32
+fn ns_step_km(height: usize, radius_km: f64) -> f32 {
+    (std::f64::consts::PI * radius_km / height as f64) as f32
 }
 
-fn ew_step_km(row: usize, width: usize, height: usize) -> f32 {
+fn ew_step_km(row: usize, width: usize, height: usize, radius_km: f64) -> f32 {
     let lat_deg = 90.0 - (row as f64 + 0.5) * 180.0 / height as f64;
     let lat_cos = lat_deg.to_radians().cos().max(1e-4);
-    (2.0 * std::f64::consts::PI * EARTH_RADIUS_KM / width as f64 * lat_cos) as f32
+    (2.0 * std::f64::consts::PI * radius_km / width as f64 * lat_cos) as f32
 }
 
-fn wavefront_neighbors(idx: usize, width: usize, height: usize) -> [(Option<usize>, f32); 8] {
+fn wavefront_neighbors(
+    idx: usize,
+    width: usize,
+    height: usize,
+    radius_km: f64,
+) -> [(Option<usize>, f32); 8] {
     let row = idx / width;
     let col = idx % width;
-    let ns = ns_step_km(height);
-    let ew = ew_step_km(row, width, height);
+    let ns = ns_step_km(height, radius_km);
+    let ew = ew_step_km(row, width, height, radius_km);
     let diag = ns.hypot(ew);
     [
         (
@@ -831,6 +907,7 @@ fn sample_convergent_arc_field(
     idx: usize,
     width: usize,
     height: usize,
+    radius_km: f64,
     field: &ConvergentArcField,
     segments: &ConvergentSegmentTable,
     polylines: &[BoundaryPolyline],
@@ -872,8 +949,8 @@ fn sample_convergent_arc_field(
     let qy = a.y + t * (b.y - a.y);
     let lat_q = pixel_lat_rad(qy.clamp(0.0, (height - 1) as f64), height);
     let lon_q = pixel_lon_rad(qx, width);
-    let east_km = normalize_lon_delta(lon_p - lon_q) * EARTH_RADIUS_KM * lat_q.cos();
-    let north_km = (lat_p - lat_q) * EARTH_RADIUS_KM;
+    let east_km = normalize_lon_delta(lon_p - lon_q) * radius_km * lat_q.cos();
+    let north_km = (lat_p - lat_q) * radius_km;
     let side_dot_km = east_km * normal.0 as f64 + north_km * normal.1 as f64;
     let side_t = (side_dot_km / SIDE_TRANSITION_WIDTH_KM).clamp(-1.0, 1.0);
     let side_weight = smoothstep01((side_t * 0.5 + 0.5) as f32);
@@ -881,8 +958,7 @@ fn sample_convergent_arc_field(
     let arc_start = polyline.arc_lengths[sv];
     let arc_end = polyline.arc_lengths[(sv + 1) % polyline.arc_lengths.len()];
     let arc_length_km = lerp_f64(arc_start, arc_end, t);
-    let along_strike_modulation =
-        along_strike_modulation_km(perlin, seed, pi, arc_length_km);
+    let along_strike_modulation = along_strike_modulation_km(perlin, seed, pi, arc_length_km);
 
     Some(ArcSample {
         distance_km: field.distance_km[idx] as f64,
@@ -894,36 +970,63 @@ fn sample_convergent_arc_field(
 
 /// Generate a structural elevation field from `PlateSimulation` outputs.
 pub fn generate_planet_elevation(plates: &PlateSimulation, seed: u64) -> Vec<f32> {
+    generate_planet_elevation_with(plates, &PlanetBody::EARTH, seed)
+}
+
+/// `generate_planet_elevation` for plates simulated on `body`.
+///
+/// Distances use the body's radius; orogenic thickening, arc volcanism and
+/// hotspot edifices scale with its `relief_scale`.
+pub fn generate_planet_elevation_with(
+    plates: &PlateSimulation,
+    body: &PlanetBody,
+    seed: u64,
+) -> Vec<f32> {
     let width = plates.width;
     let height = plates.height;
     let n = width * height;
+    let radius_km = body.radius_km;
+    let relief = body.relief_scale();
 
     if n == 0 {
         return Vec::new();
     }
 
     let cell_points = build_cell_points(width, height);
-    let perlin = Perlin::new((seed ^ 0x15_05_7A_71) as u32);
-    let province_perlin_large = Perlin::new((seed ^ 0x1000_DEAD) as u32);
-    let province_perlin_medium = Perlin::new((seed ^ 0x2000_BEEF) as u32);
-    let province_perlin_linear = Perlin::new((seed ^ 0x3000_CAFE) as u32);
-
-    // Convergent boundary distance for the province interior mask.
-    let convergent_seeds: Vec<bool> = plates
+    let perlin = Perlin::new(seed::derive_u32(seed, "elevation/arc-along-strike", &[]));
+    let province_perlin_large = Perlin::new(seed::derive_u32(seed, "elevation/province", &[0]));
+    let province_perlin_medium = Perlin::new(seed::derive_u32(seed, "elevation/province", &[1]));
+    let province_perlin_linear = Perlin::new(seed::derive_u32(seed, "elevation/province", &[2]));
+
+    // Convergent boundary distance for the province interior mask; its
+    // nearest sources also decide the overriding side of the subduction
+    // profile, by the same rule the regime field's back-arc basins use.
+    let convergent_seeds: Vec<usize> = plates
         .boundary_field
         .iter()
         .enumerate()
-        .map(|(idx, character)| {
-            plates.is_boundary[idx]
-                && character.convergent_rate > PROVINCE_CONVERGENT_THRESHOLD_CM_YR
+        .filter_map(|(idx, character)| {
+            (plates.is_boundary[idx]
+                && character.convergent_rate > PROVINCE_CONVERGENT_THRESHOLD_CM_YR)
+                .then_some(idx)
         })
         .collect();
-    let convergent_distance_km = multi_source_grid_distance(&convergent_seeds, width, height);
+    let convergent_distance = progress::within(0.0, 0.2, || {
+        distance_to_seeds_km(width, height, radius_km, &convergent_seeds)
+    });
 
     let ridge_distance_km = &plates.divergent_distance_km;
-    let hotspot_distance_km = nearest_hotspot_distance_km(&cell_points, &plates.hotspots);
+    let hotspot_relief_km =
+        hotspot_track_relief_km(&cell_points, &plates.hotspot_tracks, radius_km);
+    let rrr_junctions: Vec<Vec3> = plates
+        .triple_junctions
+        .iter()
+        .filter(|junction| junction.class() == JunctionClass::RRR)
+        .map(|junction| junction.position)
+        .collect();
+    let rrr_junction_distance_km = nearest_distance_km(&cell_points, &rrr_junctions, radius_km);
     let (convergent_arc_field, convergent_segment_table) =
-        build_convergent_arc_field(&plates.boundary_polylines, width, height);
+        build_convergent_arc_field(&plates.boundary_polylines, width, height, radius_km);
 
     let continent_seeds: Vec<bool> = plates
         .crust_field
@@ -935,16 +1038,16 @@ pub fn generate_planet_elevation(plates: &PlateSimulation, seed: u64) -> Vec<f32
         .iter()
         .map(|&crust| crust == CrustType::Oceanic)
         .collect();
-    let distance_to_continent = multi_source_grid_distance(&continent_seeds, width, height);
-    let distance_to_ocean = multi_source_grid_distance(&ocean_seeds, width, height);
+    let (distance_to_continent, distance_to_ocean) = parallel::join(
+        || multi_source_grid_distance(&continent_seeds, width, height, radius_km),
+        || multi_source_grid_distance(&ocean_seeds, width, height, radius_km),
+    );
 
     // Pre-compute reference subsidence once — used inside the pixel loop to
     // zero-centre the PS correction so the average ocean depth stays unchanged.
     let reference_subsidence = parsons_sclater_subsidence_km(PS_REFERENCE_AGE_MA);
 
-    let mut elevations = vec![0.0_f32; n];
-
-    for idx in 0..n {
+    parallel::map_range(n, |idx| {
         let point = cell_points[idx];
         let continental_share = continental_share_at_margin(
             continent_seeds[idx],
@@ -968,31 +1071,44 @@ pub fn generate_planet_elevation(plates: &PlateSimulation, seed: u64) -> Vec<f32
                 point,
                 grain_angle,
                 distance_to_ocean[idx],
-                convergent_distance_km[idx],
+                convergent_distance.distance_km[idx],
             );
         }
 
         let arc_sample = sample_convergent_arc_field(
-            idx, width, height,
+            idx,
+            width,
+            height,
+            radius_km,
             &convergent_arc_field,
             &convergent_segment_table,
             &plates.boundary_polylines,
             &perlin,
             seed,
         );
-        if let Some(sample) = arc_sample {
-            let shortening = compressional_shortening_factor(
-                sample.distance_km,
-                sample.convergent_rate,
-                sample.side_weight,
+        let subduction = arc_sample.map(|sample| {
+            let side = overriding_side(
+                idx,
+                &plates.plate_ids,
+                &plates.boundary_field,
+                &convergent_distance,
             );
-            thickness_km *= 1.0 + shortening;
-            thickness_km += volcanic_arc_addition_km(
+            cross_section(side * sample.distance_km, sample.convergent_rate)
+        });
+        if let (Some(sample), Some(section)) = (arc_sample, subduction) {
+            let shortening = compressional_shortening_factor(
                 sample.distance_km,
                 sample.convergent_rate,
-                sample.along_strike_modulation,
                 sample.side_weight,
             );
+            thickness_km *= 1.0 + shortening * relief;
+            thickness_km += relief
+                * volcanic_arc_addition_km(
+                    section.arc_distance_km,
+                    sample.convergent_rate,
+                    sample.along_strike_modulation,
+                    sample.side_weight,
+                );
         }
 
         let distance_ridge = ridge_distance_km[idx] as f64;
@@ -1006,10 +1122,8 @@ pub fn generate_planet_elevation(plates: &PlateSimulation, seed: u64) -> Vec<f32
             thickness_km = thickness_km.max(minimum_thickness);
         }
 
-        let distance_hotspot = hotspot_distance_km[idx] as f64;
-        if distance_hotspot < HOTSPOT_INFLUENCE_KM {
-            thickness_km += hotspot_thickening_km(distance_hotspot);
-        }
+        let (hotspot_thickening, hotspot_edifice) = hotspot_relief_km[idx];
+        thickness_km += hotspot_thickening;
 
         let isostatic_elevation_km = (thickness_km - OCEANIC_BASE_THICKNESS_KM) * 0.15;
         let final_continental_share = continental_share_from_thickness_km(thickness_km);
@@ -1029,17 +1143,32 @@ pub fn generate_planet_elevation(plates: &PlateSimulation, seed: u64) -> Vec<f32
         let ps_correction_km =
             (reference_subsidence - subsidence_km - OCEANIC_DEPTH_OFFSET_KM) * oceanic_share;
 
-        let mut edifice_km = 0.0_f32;
-        if distance_hotspot < HOTSPOT_EDIFICE_INFLUENCE_KM {
-            edifice_km += hotspot_edifice_uplift_km(distance_hotspot);
-        }
+        let edifice_km = relief * hotspot_edifice;
 
-        let texture_km = 0.05 * isotropic_fbm(&perlin, point, 8.0, 2);
+        let junction_km = relief
+            * rrr_junction_relief_km(
+                rrr_junction_distance_km[idx] as f64,
+                final_continental_share,
+            );
 
-        elevations[idx] = isostatic_elevation_km + ps_correction_km + edifice_km + texture_km;
-    }
+        // Only oceanic lithosphere bends down into a trench; continental
+        // collision closes it.
+        let subduction_km = subduction.map_or(0.0, |section| {
+            relief
+                * (section.wedge_uplift_km
+                    - section.trench_depth_km * oceanic_share
+                    - section.back_arc_depth_km)
+        });
+
+        let texture_km = 0.05 * isotropic_fbm(&perlin, point, 8.0, 2);
 
-    elevations
+        isostatic_elevation_km
+            + ps_correction_km
+            + edifice_km
+            + junction_km
+            + subduction_km
+            + texture_km
+    })
 }
 
 #[cfg(test)]
@@ -1048,6 +1177,7 @@ mod tests {
     use crate::{
         planet::sea_level::compute_ocean_mask,
         plates::{regime_field::TectonicRegime, simulate_plates},
+        sphere::EARTH_RADIUS_KM,
     };
 
     fn make_plates(seed: u64) -> PlateSimulation {
@@ -1207,10 +1337,14 @@ mod tests {
             .iter()
             .map(|&crust| crust == CrustType::Oceanic)
             .collect();
-        let distance_to_continent =
-            multi_source_grid_distance(&continent_seeds, plates.width, plates.height);
+        let distance_to_continent = multi_source_grid_distance(
+            &continent_seeds,
+            plates.width,
+            plates.height,
+            EARTH_RADIUS_KM,
+        );
         let distance_to_ocean =
-            multi_source_grid_distance(&ocean_seeds, plates.width, plates.height);
+            multi_source_grid_distance(&ocean_seeds, plates.width, plates.height, EARTH_RADIUS_KM);
 
         let shelf_side: Vec<f32> = elev
             .iter()
@@ -1249,10 +1383,8 @@ mod tests {
 
     #[test]
     fn continental_convergence_outruns_oceanic_island_arc() {
-        let shortening =
-            compressional_shortening_factor(0.0, CONVERGENT_REFERENCE_RATE_CM_YR, 1.0);
-        let arc_addition =
-            volcanic_arc_addition_km(0.0, CONVERGENT_REFERENCE_RATE_CM_YR, 1.0, 1.0);
+        let shortening = compressional_shortening_factor(0.0, CONVERGENT_REFERENCE_RATE_CM_YR, 1.0);
+        let arc_addition = volcanic_arc_addition_km(0.0, CONVERGENT_REFERENCE_RATE_CM_YR, 1.0, 1.0);
         let continental_thickness =
             CONTINENTAL_BASE_THICKNESS_KM * (1.0 + shortening) + arc_addition;
         let oceanic_thickness = OCEANIC_BASE_THICKNESS_KM * (1.0 + shortening) + arc_addition;
@@ -1268,7 +1400,7 @@ mod tests {
     #[test]
     fn along_strike_modulation_varies_within_bounds() {
         let plates = make_plates(42);
-        let perlin = Perlin::new((99 ^ 0x15_05_7A_71) as u32);
+        let perlin = Perlin::new(seed::derive_u32(99, "elevation/arc-along-strike", &[]));
         let polyline = plates
             .boundary_polylines
             .iter()
@@ -1408,9 +1540,7 @@ mod tests {
         let craton_values: Vec<f32> = elev
             .iter()
             .enumerate()
-            .filter(|(idx, _)| {
-                plates.regime_field.data[*idx] == TectonicRegime::CratonicShield
-            })
+            .filter(|(idx, _)| plates.regime_field.data[*idx] == TectonicRegime::CratonicShield)
             .map(|(_, &v)| v)
             .collect();
 
@@ -1459,7 +1589,7 @@ mod tests {
         let height = plates.height;
 
         let (field, segments) =
-            build_convergent_arc_field(&plates.boundary_polylines, width, height);
+            build_convergent_arc_field(&plates.boundary_polylines, width, height, EARTH_RADIUS_KM);
 
         // Precompute arc_length_km for each valid pixel.
         let arc_km: Vec<Option<f64>> = (0..width * height)
@@ -1475,8 +1605,7 @@ mod tests {
                 let sv = segments.start_vertex[seg_idx as usize] as usize;
                 let polyline = &plates.boundary_polylines[pi];
                 let arc_start = polyline.arc_lengths[sv];
-                let arc_end =
-                    polyline.arc_lengths[(sv + 1) % polyline.arc_lengths.len()];
+                let arc_end = polyline.arc_lengths[(sv + 1) % polyline.arc_lengths.len()];
                 Some(arc_start + (arc_end - arc_start) * t)
             })
             .collect();
@@ -1533,8 +1662,7 @@ mod tests {
         }
 
         assert_eq!(
-            catastrophic,
-            0,
+            catastrophic, 0,
             "wavefront should eliminate cross-sector arc-length jumps > 2500 km; \
              max same-polyline adjacent-pixel jump was {max_jump:.0} km"
         );
@@ -1550,8 +1678,13 @@ mod tests {
     ///   Polyline A: flips=2, switches=2,  max_jump=2154 km, >80km=1, mean_mod=0.0499, max_mod=0.0713
     ///   Polyline B: flips=1, switches=17, max_jump=223 km,  >80km=5, mean_mod=0.0386, max_mod=0.2401
     ///
-    /// Hard assertion: no arc-length jumps > 1000 km on the same polyline (the old
-    /// bucket code produced 2154 km; the wavefront eliminates catastrophic jumps).
+    /// Hard assertions: every probed pixel is assigned a segment within one pixel
+    /// diagonal of its brute-force nearest segment (the old bucket code picked
+    /// segments from another sector of the arc), and no arc-length jump on the
+    /// same polyline exceeds 1000 km (old code: 2154 km) unless the side flips
+    /// with it.  Seed 42 folds Polyline B back on itself; between the two limbs
+    /// the probe alternates sides with 1185 km jumps, which is the correct
+    /// nearest segment on each side of the fold.
     ///
     /// Run with `cargo test h2_wavefront_probe -- --nocapture` to see full output.
     #[test]
@@ -1561,8 +1694,8 @@ mod tests {
         let width = plates.width;
         let height = plates.height;
         let (field, segments) =
-            build_convergent_arc_field(&plates.boundary_polylines, width, height);
-        let perlin = Perlin::new((42_u64 ^ 0x15_05_7A_71) as u32);
+            build_convergent_arc_field(&plates.boundary_polylines, width, height, EARTH_RADIUS_KM);
+        let perlin = Perlin::new(seed::derive_u32(42, "elevation/arc-along-strike", &[]));
 
         // Rank convergent polylines by total arc length, descending.
         let mut convergent: Vec<(usize, f64)> = plates
@@ -1574,11 +1707,43 @@ mod tests {
             .collect();
         convergent.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
+        let ns_km = f64::from(ns_step_km(height, EARTH_RADIUS_KM));
+        let ew_km = f64::from(ew_step_km(height / 2, width, height, EARTH_RADIUS_KM));
+        let pixel_diagonal_km = ns_km.hypot(ew_km);
+        let nearest_segment_km = |idx: usize| {
+            (0..segments.polyline_idx.len())
+                .map(|seg| {
+                    let pl = &plates.boundary_polylines[segments.polyline_idx[seg] as usize];
+                    let sv = segments.start_vertex[seg] as usize;
+                    let a = &pl.vertices[sv];
+                    let b = &pl.vertices[(sv + 1) % pl.vertices.len()];
+                    let (col, row) = ((idx % width) as f64, (idx / width) as f64);
+                    let (ax, bx) = unwrap_segment_endpoints(a.x, b.x, col, width as f64);
+                    project_onto_segment_km(
+                        col,
+                        row,
+                        ax,
+                        a.y,
+                        bx,
+                        b.y,
+                        width,
+                        height,
+                        EARTH_RADIUS_KM,
+                    )
+                    .1
+                })
+                .fold(f64::INFINITY, f64::min)
+        };
+
         let mut all_pass = true;
 
         for (rank, &(pi, total_arc)) in convergent.iter().take(2).enumerate() {
             let polyline = &plates.boundary_polylines[pi];
-            let label = if rank == 0 { "A (longest)" } else { "B (second-longest)" };
+            let label = if rank == 0 {
+                "A (longest)"
+            } else {
+                "B (second-longest)"
+            };
 
             // Find the vertex closest to the arc midpoint.
             let mid_arc = total_arc / 2.0;
@@ -1587,7 +1752,10 @@ mod tests {
                 .iter()
                 .enumerate()
                 .min_by(|(_, &a), (_, &b)| {
-                    (a - mid_arc).abs().partial_cmp(&(b - mid_arc).abs()).unwrap()
+                    (a - mid_arc)
+                        .abs()
+                        .partial_cmp(&(b - mid_arc).abs())
+                        .unwrap()
                 })
                 .map(|(i, _)| i)
                 .unwrap_or(0)
@@ -1606,6 +1774,8 @@ mod tests {
             let mut overriding_flips = 0_usize;
             let mut seg_switches = 0_usize;
             let mut max_arc_jump = 0.0_f64;
+            let mut max_same_side_jump = 0.0_f64;
+            let mut max_nearest_excess_km = 0.0_f64;
             let mut jumps_gt_80 = 0_usize;
             let mut mod_changes_at_switches: Vec<f32> = Vec::new();
 
@@ -1629,18 +1799,26 @@ mod tests {
                 let idx = py as usize * width + px as usize;
                 let cur_seg = field.nearest_segment[idx];
                 let sample = sample_convergent_arc_field(
-                    idx, width, height, &field, &segments,
-                    &plates.boundary_polylines, &perlin, 42,
+                    idx,
+                    width,
+                    height,
+                    EARTH_RADIUS_KM,
+                    &field,
+                    &segments,
+                    &plates.boundary_polylines,
+                    &perlin,
+                    42,
                 );
 
                 if let Some(s) = sample {
+                    max_nearest_excess_km = max_nearest_excess_km
+                        .max(f64::from(field.distance_km[idx]) - nearest_segment_km(idx));
                     let cur_poly = segments.polyline_idx[cur_seg as usize] as usize;
                     let sv = segments.start_vertex[cur_seg as usize] as usize;
                     let pl = &plates.boundary_polylines[cur_poly];
                     let t_val = field.t_along_segment[idx] as f64;
                     let arc_km = pl.arc_lengths[sv]
-                        + (pl.arc_lengths[(sv + 1) % pl.arc_lengths.len()]
-                            - pl.arc_lengths[sv])
+                        + (pl.arc_lengths[(sv + 1) % pl.arc_lengths.len()] - pl.arc_lengths[sv])
                             * t_val;
 
                     if let Some(prev_s) = prev_seg {
@@ -1651,6 +1829,12 @@ mod tests {
                                 if let (Some(prev_a), Some(prev_m)) = (prev_arc_km, prev_mod) {
                                     let jump = (arc_km - prev_a).abs();
                                     max_arc_jump = max_arc_jump.max(jump);
+                                    let flipped = prev_overriding.is_some_and(|prev_o| {
+                                        (prev_o < 0.5) != (s.side_weight < 0.5)
+                                    });
+                                    if !flipped {
+                                        max_same_side_jump = max_same_side_jump.max(jump);
+                                    }
                                     if jump > 80.0 {
                                         jumps_gt_80 += 1;
                                     }
@@ -1684,8 +1868,7 @@ mod tests {
             let mean_mod = if mod_changes_at_switches.is_empty() {
                 0.0_f32
             } else {
-                mod_changes_at_switches.iter().sum::<f32>()
-                    / mod_changes_at_switches.len() as f32
+                mod_changes_at_switches.iter().sum::<f32>() / mod_changes_at_switches.len() as f32
             };
             let max_mod = mod_changes_at_switches
                 .iter()
@@ -1695,8 +1878,14 @@ mod tests {
             println!("Polyline {label} (index {pi}, {total_arc:.0} km):");
             println!("  side_weight>0.5 flips : {overriding_flips}  [prev A=2 B=1 | target: 1]");
             println!("  segment switches      : {seg_switches}  [prev A=2 B=17]");
-            println!("  max arc-length jump   : {max_arc_jump:.0} km  [prev A=2154 B=223 | target: <80]");
+            println!(
+                "  max arc-length jump   : {max_arc_jump:.0} km  [prev A=2154 B=223 | target: <80]"
+            );
+            println!("  max same-side jump    : {max_same_side_jump:.0} km  [target: <1000]");
             println!("  jumps > 80 km         : {jumps_gt_80}  [prev A=1 B=5 | target: 0]");
+            println!(
+                "  beyond nearest segment: {max_nearest_excess_km:.0} km  [target: <{pixel_diagonal_km:.0}]"
+            );
             println!(
                 "  mean mod at switches  : {mean_mod:.4}  [prev A=0.0499 B=0.0386 | target: <0.05]"
             );
@@ -1705,10 +1894,16 @@ mod tests {
             );
             println!();
 
-            // Hard assertion: no catastrophic cross-sector jumps (old code: 2154 km).
-            if max_arc_jump > 1000.0 {
+            // Hard assertions: no catastrophic cross-sector jumps (old code: 2154 km).
+            if max_same_side_jump > 1000.0 {
+                eprintln!(
+                    "FAIL Polyline {label}: catastrophic arc jump {max_same_side_jump:.0} km > 1000 km"
+                );
+                all_pass = false;
+            }
+            if max_nearest_excess_km > pixel_diagonal_km {
                 eprintln!(
-                    "FAIL Polyline {label}: catastrophic arc jump {max_arc_jump:.0} km > 1000 km"
+                    "FAIL Polyline {label}: assigned segment {max_nearest_excess_km:.0} km beyond the nearest"
                 );
                 all_pass = false;
             }
@@ -1716,16 +1911,65 @@ mod tests {
 
         assert!(
             all_pass,
-            "wavefront produced a catastrophic arc-length jump — see output above"
+            "wavefront assigned a distant arc sector — see output above"
+        );
+    }
+
+    #[test]
+    fn rrr_junctions_dome_oceans_and_split_continents() {
+        assert!(rrr_junction_relief_km(0.0, 0.0) > 1.0);
+        assert!(rrr_junction_relief_km(0.0, 1.0) < 0.0);
+        let flank = rrr_junction_relief_km(350.0, 1.0);
+        assert!(flank > 0.5, "dome flank {flank}");
+        assert!(flank > rrr_junction_relief_km(0.0, 1.0) + 1.0);
+        assert_eq!(rrr_junction_relief_km(JUNCTION_INFLUENCE_KM, 1.0), 0.0);
+    }
+
+    #[test]
+    fn deepest_ocean_lies_in_trenches() {
+        let plates = simulate_plates(42, 0.5, 0.5, 256, 128);
+        let elev = generate_planet_elevation(&plates, 42);
+        let sea_level = compute_ocean_mask(&elev, 0.65).sea_level_km;
+        let (deepest, &floor) = elev
+            .iter()
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+        let depth = sea_level - floor;
+        assert!(
+            (8.0..=11.0).contains(&depth),
+            "deepest trench {depth:.2} km"
+        );
+        let (row, col) = (deepest / plates.width, deepest % plates.width);
+        let near_convergent = (row.saturating_sub(2)..(row + 3).min(plates.height)).any(|r| {
+            (0..5).any(|dc| {
+                let idx = r * plates.width + (col + plates.width + dc - 2) % plates.width;
+                plates.is_boundary[idx] && plates.boundary_field[idx].convergent_rate > 1.0
+            })
+        });
+        assert!(
+            near_convergent,
+            "deepest cell {row},{col} is not at a trench"
         );
     }
 
+    #[test]
+    fn hotspot_edifices_erode_and_swells_subside_with_age() {
+        let young = hotspot_edifice_uplift_km(0.0, 0.0);
+        assert_eq!(young, HOTSPOT_EDIFICE_UPLIFT_KM);
+        assert!(hotspot_edifice_uplift_km(0.0, 60.0) < young / 3.0);
+        assert_eq!(hotspot_thickening_km(0.0, 0.0), MAX_HOTSPOT_THICKENING_KM);
+        let swell = |age| hotspot_thickening_km(100.0, age);
+        assert!(swell(0.0) > swell(10.0) && swell(10.0) > swell(40.0));
+        assert!(swell(75.0) < 0.1 * swell(0.0));
+    }
+
     #[test]
     fn hotspot_elevation_exceeds_surroundings() {
         let plates = make_plates(42);
         let elev = generate_planet_elevation(&plates, 42);
         let points = build_cell_points(plates.width, plates.height);
-        let hotspot_distance_km = nearest_hotspot_distance_km(&points, &plates.hotspots);
+        let hotspot_distance_km = nearest_distance_km(&points, &plates.hotspots, EARTH_RADIUS_KM);
 
         let hotspot_pixels: Vec<f32> = elev
             .iter()
@@ -10,7 +10,13 @@
 //!   4. Regime Shannon entropy over land cells     > 1.2 bits
 //!   5. Transition smoothness: mean regime grad across all cell pairs < 0.15
 //!   6. Continental coherence: largest connected land mass > 10 % of land
+//!
+//! The ±20° tropical band and the 60° polar boundary are Earth values; both
+//! follow the planet's axial tilt like the climate belts they measure.
+
+use serde::{Deserialize, Serialize};
 
+use crate::body::PlanetBody;
 use crate::noise::params::GlacialClass;
 use crate::plates::regime_field::TectonicRegime;
 
@@ -18,6 +24,7 @@ use crate::plates::regime_field::TectonicRegime;
 pub struct PlanetMetricsConfig {
     pub water_abundance: f32,
     pub glaciation_slider: f32,
+    pub body: PlanetBody,
     pub width: usize,
     pub height: usize,
 }
@@ -25,29 +32,29 @@ pub struct PlanetMetricsConfig {
 // ── Result structs ────────────────────────────────────────────────────────────
 
 /// Pass/fail result for a single planet metric.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MetricResult {
-    pub name: &'static str,
+    pub name: String,
     pub raw_value: f32,
     pub threshold: f32,
     pub pass: bool,
-    pub description: &'static str,
+    pub description: String,
 }
 
 impl MetricResult {
-    fn new(name: &'static str, raw: f32, threshold: f32, pass: bool, desc: &'static str) -> Self {
+    fn new(name: &str, raw: f32, threshold: f32, pass: bool, desc: &str) -> Self {
         Self {
-            name,
+            name: name.to_owned(),
             raw_value: raw,
             threshold,
             pass,
-            description: desc,
+            description: desc.to_owned(),
         }
     }
 }
 
 /// All six planet metric results.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlanetMetrics {
     pub metrics: [MetricResult; 6],
     /// `true` if all six metrics pass.
@@ -77,8 +84,8 @@ pub fn compute_planet_metrics(
     let h = cfg.height;
 
     let m1 = metric_land_fraction(ocean_mask, cfg.water_abundance);
-    let m2 = metric_tropical_map(map_field, w, h);
-    let m3 = metric_polar_glaciation(glaciation, w, h, cfg.glaciation_slider);
+    let m2 = metric_tropical_map(map_field, w, h, &cfg.body);
+    let m3 = metric_polar_glaciation(glaciation, w, h, cfg.glaciation_slider, &cfg.body);
     let m4 = metric_regime_entropy(raw_regimes, ocean_mask);
     let m5 = metric_transition_smoothness(regimes, ocean_mask, w, h);
     let m6 = metric_continental_coherence(ocean_mask, w, h);
@@ -109,14 +116,20 @@ fn metric_land_fraction(ocean_mask: &[bool], water_abundance: f32) -> MetricResu
 
 // ── Metric 2: Tropical MAP ────────────────────────────────────────────────────
 
-fn metric_tropical_map(map_field: &[f32], width: usize, height: usize) -> MetricResult {
-    // Tropical band: |lat| ≤ 20°. Rows where lat is in [−20°, +20°].
+fn metric_tropical_map(
+    map_field: &[f32],
+    width: usize,
+    height: usize,
+    body: &PlanetBody,
+) -> MetricResult {
+    // Tropical band: |lat| ≤ 20° on Earth, scaled with the ITCZ width.
     // lat for row r = 90 − (r + 0.5) × 180 / height.
+    let band_deg = (20.0 * body.itcz_spread()) as f32;
     let mut sum = 0.0_f32;
     let mut cnt = 0usize;
     for r in 0..height {
         let lat = 90.0 - (r as f32 + 0.5) * 180.0 / height as f32;
-        if lat.abs() <= 20.0 {
+        if lat.abs() <= band_deg {
             let row_off = r * width;
             for c in 0..width {
                 sum += map_field[row_off + c];
@@ -141,13 +154,15 @@ fn metric_polar_glaciation(
     width: usize,
     height: usize,
     glaciation_slider: f32,
+    body: &PlanetBody,
 ) -> MetricResult {
-    // Polar zone: |lat| > 60°.
+    // Polar zone: |lat| > 60° on Earth, scaled like the glaciation caps.
+    let polar_deg = 90.0 - 30.0 / body.polar_cap_scale();
     let mut polar_total = 0usize;
     let mut glaciated = 0usize;
     for r in 0..height {
         let lat = (90.0 - (r as f32 + 0.5) * 180.0 / height as f32).abs();
-        if lat > 60.0 {
+        if lat > polar_deg {
             let row_off = r * width;
             for c in 0..width {
                 polar_total += 1;
@@ -380,7 +395,7 @@ mod tests {
     fn tropical_map_passes_above_1200() {
         let w = 32usize;
         let h = 16usize;
-        let m = metric_tropical_map(&flat_map(w * h, 1500.0), w, h);
+        let m = metric_tropical_map(&flat_map(w * h, 1500.0), w, h, &PlanetBody::EARTH);
         assert!(m.pass, "MAP=1500 in tropics should pass (≥1200)");
     }
 
@@ -388,7 +403,7 @@ mod tests {
     fn tropical_map_fails_below_1200() {
         let w = 32usize;
         let h = 16usize;
-        let m = metric_tropical_map(&flat_map(w * h, 800.0), w, h);
+        let m = metric_tropical_map(&flat_map(w * h, 800.0), w, h, &PlanetBody::EARTH);
         assert!(!m.pass, "MAP=800 in tropics should fail (<1200)");
     }
 
@@ -399,7 +414,13 @@ mod tests {
         let w = 32usize;
         let h = 16usize;
         // Slider=1.0 → expected≈1.0; all Active → frac=1.0 → diff≈0 → pass.
-        let m = metric_polar_glaciation(&flat_glac(w * h, GlacialClass::Active), w, h, 1.0);
+        let m = metric_polar_glaciation(
+            &flat_glac(w * h, GlacialClass::Active),
+            w,
+            h,
+            1.0,
+            &PlanetBody::EARTH,
+        );
         assert!(m.pass, "all-active with slider=1.0 should pass");
     }
 
@@ -408,7 +429,13 @@ mod tests {
         let w = 32usize;
         let h = 16usize;
         // Slider=1.0 → expected≈1.0; all None → frac=0 → diff=1.0 → fail.
-        let m = metric_polar_glaciation(&flat_glac(w * h, GlacialClass::None), w, h, 1.0);
+        let m = metric_polar_glaciation(
+            &flat_glac(w * h, GlacialClass::None),
+            w,
+            h,
+            1.0,
+            &PlanetBody::EARTH,
+        );
         assert!(!m.pass, "no glaciation with slider=1.0 should fail");
     }
 
@@ -0,0 +1,353 @@
+//! Stateful planet handle for repeated drill-down (Phase B).
+//!
+//! `generate_at_location` re-runs the plate and climate simulations on every
+//! call. `Planet` runs them once, keeps the layers and the overview in memory,
+//! and serves any number of `tile_at` / `field_at` queries from them.
+//! `set_params` moves the sliders, rerunning only the overview stages that
+//! depend on the changed params.
+
+use crate::climate::ClimateLayer;
+use crate::coords::TileAddr;
+use crate::error::{check_tile_addr, TerraError};
+use crate::generator::{
+    generate_tile_for_sample, latlon_to_cell, sample_location, GenerationConfig, GlobalParams,
+    LocationSample, LocationTileResult,
+};
+use crate::plates::PlateSimulation;
+use crate::progress::RunControl;
+
+use super::addressed_tile::{generate_addressed_tile, AddressedTile};
+use super::conditioned_tile::generate_conditioned_tile;
+use super::pipeline::{OverviewPipeline, OverviewStage};
+use super::PlanetOverview;
+
+/// Planet fields at a single lat/lon, including overview-only values.
+#[derive(Debug, Clone, Copy)]
+pub struct FieldSample {
+    /// Raw plate / climate fields at the cell (the tile pipeline inputs).
+    pub location: LocationSample,
+    /// Structural elevation at the cell in physical kilometres.
+    pub elevation_km: f32,
+    /// True when the cell lies below the overview sea level.
+    pub is_ocean: bool,
+}
+
+/// A generated planet held in memory for interactive drill-down.
+pub struct Planet {
+    pipeline: OverviewPipeline,
+    overview: PlanetOverview,
+}
+
+impl Planet {
+    /// Run the plate, climate and overview pipelines once for `params`.
+    pub fn new(params: &GlobalParams) -> Self {
+        Self::with_config(params, GenerationConfig::default())
+    }
+
+    /// As `new`, at the overview and tile resolutions of `config`.
+    pub fn with_config(params: &GlobalParams, config: GenerationConfig) -> Self {
+        let pipeline = OverviewPipeline::new(params, config);
+        let overview = pipeline.overview();
+        Self { pipeline, overview }
+    }
+
+    /// `with_config` after validating `params` and `config`, reporting
+    /// progress to `control` and stopping early if its cancel token is set.
+    pub fn try_with_config(
+        params: &GlobalParams,
+        config: GenerationConfig,
+        control: &mut RunControl,
+    ) -> Result<Self, TerraError> {
+        let pipeline = OverviewPipeline::try_new(params, config, control)?;
+        let overview = pipeline.overview();
+        Ok(Self { pipeline, overview })
+    }
+
+    /// Wrap a pipeline whose stages are already up to date.
+    pub(crate) fn from_pipeline(pipeline: OverviewPipeline) -> Self {
+        let overview = pipeline.overview();
+        Self { pipeline, overview }
+    }
+
+    /// Switch to new params, rerunning only the affected overview stages.
+    ///
+    /// Returns the stages that reran; see [`OverviewPipeline::update`].
+    pub fn set_params(&mut self, params: &GlobalParams) -> Vec<OverviewStage> {
+        let ran = self.pipeline.update(params);
+        self.refresh_overview(&ran);
+        ran
+    }
+
+    /// `set_params` after validating `params`, reporting progress to
+    /// `control` and stopping early if its cancel token is set.
+    ///
+    /// Invalid params leave the planet untouched.  After a cancelled update
+    /// the overview still shows the previous params while tile queries may
+    /// see a mix of old and new stages; the next `set_params` finishes only
+    /// the interrupted stages.
+    pub fn try_set_params(
+        &mut self,
+        params: &GlobalParams,
+        control: &mut RunControl,
+    ) -> Result<Vec<OverviewStage>, TerraError> {
+        let ran = self.pipeline.try_update(params, control)?;
+        self.refresh_overview(&ran);
+        Ok(ran)
+    }
+
+    fn refresh_overview(&mut self, ran: &[OverviewStage]) {
+        if !ran.is_empty() {
+            self.overview = self.pipeline.overview();
+        }
+    }
+
+    pub fn params(&self) -> &GlobalParams {
+        self.pipeline.params()
+    }
+
+    pub fn config(&self) -> &GenerationConfig {
+        self.pipeline.config()
+    }
+
+    pub fn plates(&self) -> &PlateSimulation {
+        self.pipeline.plates()
+    }
+
+    pub fn climate(&self) -> &ClimateLayer {
+        self.pipeline.climate()
+    }
+
+    pub fn overview(&self) -> &PlanetOverview {
+        &self.overview
+    }
+
+    /// Sample the planet fields at `(lat, lon)` without generating a tile.
+    pub fn field_at(&self, lat: f32, lon: f32) -> FieldSample {
+        let location = sample_location(self.plates(), self.climate(), lat, lon);
+        let idx = latlon_to_cell(lat, lon, self.plates().width, self.plates().height);
+        FieldSample {
+            location,
+            elevation_km: self.overview.physical_elevations[idx],
+            is_ocean: self.overview.ocean_mask[idx],
+        }
+    }
+
+    /// Generate the drill-down tile at `(lat, lon)`.
+    ///
+    /// Identical to `generate_at_location` with the same params, but reuses
+    /// the stored plate and climate layers.
+    pub fn tile_at(&self, lat: f32, lon: f32) -> LocationTileResult {
+        let sample = sample_location(self.plates(), self.climate(), lat, lon);
+        generate_tile_for_sample(self.params(), self.config(), &sample)
+    }
+
+    /// Generate a drill-down tile conditioned on the overview elevation.
+    ///
+    /// Unlike `tile_at`, heights are metres relative to the overview sea level
+    /// and follow the overview's structural relief and coastline; see
+    /// [`super::conditioned_tile`].
+    pub fn conditioned_tile_at(&self, lat: f32, lon: f32) -> LocationTileResult {
+        let sample = sample_location(self.plates(), self.climate(), lat, lon);
+        generate_conditioned_tile(self.params(), self.config(), &self.overview, &sample)
+    }
+
+    /// Generate the slippy-map tile at `addr`.
+    ///
+    /// Adjacent addresses line up along their shared edges; see
+    /// [`super::addressed_tile`].
+    pub fn tile_for_addr(&self, addr: TileAddr) -> AddressedTile {
+        generate_addressed_tile(
+            self.params(),
+            &self.overview,
+            self.overview.width,
+            self.overview.height,
+            addr,
+        )
+    }
+
+    /// [`Self::tile_for_addr`], rejecting zooms above
+    /// [`MAX_TILE_ZOOM`](super::addressed_tile::MAX_TILE_ZOOM) and x/y outside
+    /// the 2^zoom grid instead of overflowing or wrapping off the globe.
+    pub fn try_tile_for_addr(&self, addr: TileAddr) -> Result<AddressedTile, TerraError> {
+        check_tile_addr(addr)?;
+        Ok(self.tile_for_addr(addr))
+    }
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::generate_at_location;
+
+    /// A tile served by the handle matches the one-shot location pipeline.
+    #[test]
+    fn tile_at_matches_generate_at_location() {
+        let params = GlobalParams::default();
+        let planet = Planet::new(&params);
+        let (lat, lon) = (35.0, 80.0);
+        let from_handle = planet.tile_at(lat, lon);
+        let one_shot = generate_at_location(&params, lat, lon);
+        assert_eq!(from_handle.terrain_class, one_shot.terrain_class);
+        assert_eq!(from_handle.local_regime, one_shot.local_regime);
+        assert_eq!(from_handle.heightfield.data, one_shot.heightfield.data);
+    }
+
+    /// `set_params` refreshes the overview and leaves the plates alone for a
+    /// climate-only slider change.
+    #[test]
+    fn set_params_keeps_plates_for_climate_slider() {
+        let params = GlobalParams::default();
+        let mut planet = Planet::new(&params);
+        let before = planet.overview().map_field.clone();
+        let ran = planet.set_params(&GlobalParams {
+            climate_diversity: 0.95,
+            ..params
+        });
+        assert!(!ran.contains(&OverviewStage::Plates));
+        assert!(ran.contains(&OverviewStage::Climate));
+        assert_eq!(planet.params().climate_diversity, 0.95);
+        assert_ne!(planet.overview().map_field, before);
+    }
+
+    /// `field_at` agrees with the stored overview at the sampled cell.
+    #[test]
+    fn field_at_reads_overview_cell() {
+        let planet = Planet::new(&GlobalParams::default());
+        let overview = planet.overview();
+        let w = planet.plates().width;
+        let h = planet.plates().height;
+        for &(lat, lon) in &[(0.0_f32, 0.0_f32), (-60.0, 120.0), (75.0, -150.0)] {
+            let sample = planet.field_at(lat, lon);
+            let col = ((lon + 180.0) / 360.0 * w as f32) as usize;
+            let row = ((90.0 - lat) / 180.0 * h as f32) as usize;
+            let idx = row * w + col;
+            assert_eq!(sample.elevation_km, overview.physical_elevations[idx]);
+            assert_eq!(sample.is_ocean, overview.ocean_mask[idx]);
+            assert_eq!(
+                sample.location.regime,
+                planet.plates().regime_field.data[idx]
+            );
+        }
+    }
+
+    /// A conditioned tile reproduces the overview coastline: most tile pixels
+    /// fall on the same side of sea level as the overview cell beneath them.
+    #[test]
+    fn conditioned_tile_follows_overview_coastline() {
+        let planet = Planet::new(&GlobalParams::default());
+        let (w, h) = (planet.plates().width, planet.plates().height);
+        let mask = &planet.overview().ocean_mask;
+
+        // First mid-latitude land cell with ocean 8 cells to the east.
+        let coast = (h / 4 * w..3 * h / 4 * w)
+            .find(|&i| !mask[i] && mask[(i / w) * w + (i % w + 8) % w])
+            .expect("default planet must have a coastline");
+        let lat = 90.0 - ((coast / w) as f32 + 0.5) * 180.0 / h as f32;
+        let lon = ((coast % w) as f32 + 0.5) * 360.0 / w as f32 - 180.0;
+
+        let tile = planet.conditioned_tile_at(lat, lon);
+        let hf = &tile.heightfield;
+        let mut agree = 0usize;
+        let mut ocean = 0usize;
+        for r in 0..hf.height {
+            let plat = hf.min_lat + (r as f64 + 0.5) / hf.height as f64 * (hf.max_lat - hf.min_lat);
+            for c in 0..hf.width {
+                let plon =
+                    hf.min_lon + (c as f64 + 0.5) / hf.width as f64 * (hf.max_lon - hf.min_lon);
+                let idx = latlon_to_cell(plat as f32, plon as f32, w, h);
+                let is_ocean = hf.get(r, c) < 0.0;
+                ocean += is_ocean as usize;
+                if is_ocean == mask[idx] {
+                    agree += 1;
+                }
+            }
+        }
+        let n = hf.data.len();
+        assert!(
+            ocean > 0 && ocean < n,
+            "coastal tile must contain land and sea"
+        );
+        let frac = agree as f32 / n as f32;
+        assert!(
+            frac > 0.85,
+            "lat={lat:.1} lon={lon:.1}: only {:.1}% of tile pixels match the overview ocean mask",
+            frac * 100.0
+        );
+    }
+
+    /// Horizontally adjacent addressed tiles meet without a step: the jump
+    /// across the shared edge is comparable to the jump between interior columns.
+    #[test]
+    fn addressed_tiles_are_seamless() {
+        use crate::coords::LatLon;
+
+        let planet = Planet::new(&GlobalParams::default());
+        let (w, h) = (planet.plates().width, planet.plates().height);
+        let mask = &planet.overview().ocean_mask;
+        let coast = (h / 4 * w..3 * h / 4 * w)
+            .find(|&i| !mask[i] && mask[(i / w) * w + (i % w + 8) % w])
+            .expect("default planet must have a coastline");
+        let lat = 90.0 - ((coast / w) as f64 + 0.5) * 180.0 / h as f64;
+        let lon = ((coast % w) as f64 + 0.5) * 360.0 / w as f64 - 180.0;
+
+        let west_addr = TileAddr::from_latlon(LatLon::new(lat, lon), 7);
+        let east_addr = TileAddr::new(west_addr.zoom, west_addr.x + 1, west_addr.y);
+        let west = planet.tile_for_addr(west_addr).heightfield;
+        let east = planet.tile_for_addr(east_addr).heightfield;
+        assert_eq!(west.max_lon, east.min_lon);
+
+        let last = west.width - 1;
+        let mut seam = 0.0f32;
+        let mut interior = 0.0f32;
+        for r in 0..west.height {
+            seam += (west.get(r, last) - east.get(r, 0)).abs();
+            interior += (west.get(r, last - 1) - west.get(r, last)).abs();
+            interior += (east.get(r, 0) - east.get(r, 1)).abs();
+        }
+        seam /= west.height as f32;
+        interior /= 2.0 * west.height as f32;
+        assert!(
+            seam <= 2.0 * interior + 1.0,
+            "seam step {seam:.2}m vs interior step {interior:.2}m"
+        );
+    }
+
+    /// `try_tile_for_addr` rejects addresses off the 2^zoom grid and zooms
+    /// past `MAX_TILE_ZOOM` instead of overflowing.
+    #[test]
+    fn try_tile_for_addr_rejects_off_globe_addresses() {
+        use super::super::addressed_tile::MAX_TILE_ZOOM;
+
+        let config = GenerationConfig {
+            overview_width: 128,
+            overview_height: 64,
+            ..GenerationConfig::default()
+        };
+        let planet = Planet::with_config(&GlobalParams::default(), config);
+        for addr in [
+            TileAddr::new(2, 4, 0),
+            TileAddr::new(2, 0, 4),
+            TileAddr::new(MAX_TILE_ZOOM + 1, 0, 0),
+            TileAddr::new(40, 0, 0),
+        ] {
+            assert_eq!(
+                planet.try_tile_for_addr(addr).err(),
+                Some(TerraError::InvalidTile {
+                    zoom: addr.zoom,
+                    x: addr.x,
+                    y: addr.y
+                })
+            );
+        }
+        let addr = TileAddr::new(2, 3, 3);
+        let Ok(tile) = planet.try_tile_for_addr(addr) else {
+            panic!("{addr:?} is on the globe");
+        };
+        assert_eq!(
+            tile.heightfield.data,
+            planet.tile_for_addr(addr).heightfield.data
+        );
+    }
+}
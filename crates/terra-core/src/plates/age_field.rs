@@ -6,9 +6,9 @@
 use std::cmp::Ordering;
 use std::collections::BinaryHeap;
 
+use crate::progress;
 use crate::sphere::Vec3;
 
-const EARTH_RADIUS_KM: f64 = 6371.0;
 const MAX_OCEANIC_THERMAL_DISTANCE_KM: f32 = 7000.0;
 const MAX_CONTINENTAL_BOUNDARY_DISTANCE_KM: f32 = 2500.0;
 
@@ -50,22 +50,27 @@ pub fn cell_to_vec3(r: usize, c: usize, width: usize, height: usize) -> Vec3 {
     Vec3::from_latlon(lat_deg, lon_deg)
 }
 
-fn east_west_step_km(row: usize, width: usize, height: usize) -> f32 {
+fn east_west_step_km(row: usize, width: usize, height: usize, radius_km: f64) -> f32 {
     let point = cell_to_vec3(row, 0, width, height);
     let lat_cos = (point.x * point.x + point.y * point.y).sqrt().max(1e-4);
     let lat_step_rad = std::f64::consts::PI / height as f64;
-    (lat_step_rad * EARTH_RADIUS_KM * lat_cos) as f32
+    (lat_step_rad * radius_km * lat_cos) as f32
 }
 
-fn north_south_step_km(height: usize) -> f32 {
-    (std::f64::consts::PI * EARTH_RADIUS_KM / height as f64) as f32
+fn north_south_step_km(height: usize, radius_km: f64) -> f32 {
+    (std::f64::consts::PI * radius_km / height as f64) as f32
 }
 
-fn neighbors8_with_cost(idx: usize, width: usize, height: usize) -> [(Option<usize>, f32); 8] {
+fn neighbors8_with_cost(
+    idx: usize,
+    width: usize,
+    height: usize,
+    radius_km: f64,
+) -> [(Option<usize>, f32); 8] {
     let row = idx / width;
     let col = idx % width;
-    let north_south = north_south_step_km(height);
-    let east_west = east_west_step_km(row, width, height);
+    let north_south = north_south_step_km(height, radius_km);
+    let east_west = east_west_step_km(row, width, height, radius_km);
     let diagonal = north_south.hypot(east_west);
     [
         (
@@ -127,7 +132,12 @@ fn neighbors8_with_cost(idx: usize, width: usize, height: usize) -> [(Option<usi
     ]
 }
 
-pub(crate) fn distance_to_mask_km(width: usize, height: usize, seeds: &[bool]) -> Vec<f32> {
+pub(crate) fn distance_to_mask_km(
+    width: usize,
+    height: usize,
+    radius_km: f64,
+    seeds: &[bool],
+) -> Vec<f32> {
     let n = width * height;
     let mut distance_km = vec![f32::INFINITY; n];
     let mut heap = BinaryHeap::new();
@@ -148,7 +158,7 @@ pub(crate) fn distance_to_mask_km(width: usize, height: usize, seeds: &[bool]) -
         if node.distance_km > distance_km[node.idx] {
             continue;
         }
-        for (neighbor, step_km) in neighbors8_with_cost(node.idx, width, height) {
+        for (neighbor, step_km) in neighbors8_with_cost(node.idx, width, height, radius_km) {
             let Some(neighbor) = neighbor else {
                 continue;
             };
@@ -167,8 +177,14 @@ pub(crate) fn distance_to_mask_km(width: usize, height: usize, seeds: &[bool]) -
     distance_km
 }
 
-/// Compute spherical approximate distance-to-seed on the equirectangular grid.
-pub fn distance_to_seeds_km(width: usize, height: usize, seeds: &[usize]) -> DistanceField {
+/// Compute spherical approximate distance-to-seed on the equirectangular grid
+/// of a planet with `radius_km`.
+pub fn distance_to_seeds_km(
+    width: usize,
+    height: usize,
+    radius_km: f64,
+    seeds: &[usize],
+) -> DistanceField {
     let n = width * height;
     let mut distance_km = vec![f32::INFINITY; n];
     let mut nearest_source = vec![usize::MAX; n];
@@ -187,11 +203,17 @@ pub fn distance_to_seeds_km(width: usize, height: usize, seeds: &[usize]) -> Dis
         });
     }
 
+    let report_every = (n / 50).max(1);
+    let mut settled = 0usize;
     while let Some(node) = heap.pop() {
         if node.distance_km > distance_km[node.idx] {
             continue;
         }
-        for (neighbor, step_km) in neighbors8_with_cost(node.idx, width, height) {
+        settled += 1;
+        if settled.is_multiple_of(report_every) {
+            progress::report(settled as f32 / n as f32);
+        }
+        for (neighbor, step_km) in neighbors8_with_cost(node.idx, width, height, radius_km) {
             let Some(neighbor) = neighbor else {
                 continue;
             };
@@ -236,6 +258,7 @@ pub fn compute_thermal_age(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sphere::EARTH_RADIUS_KM;
 
     #[test]
     fn cell_to_vec3_uses_cell_centres() {
@@ -249,7 +272,7 @@ mod tests {
 
     #[test]
     fn seed_distance_is_zero_at_seed() {
-        let field = distance_to_seeds_km(16, 8, &[10]);
+        let field = distance_to_seeds_km(16, 8, EARTH_RADIUS_KM, &[10]);
         assert_eq!(field.distance_km[10], 0.0);
         assert_eq!(field.nearest_source[10], 10);
     }
@@ -258,9 +281,9 @@ mod tests {
     fn mask_distance_uses_diagonals() {
         let mut seeds = vec![false; 9];
         seeds[0] = true;
-        let distances = distance_to_mask_km(3, 3, &seeds);
-        let east_west = east_west_step_km(0, 3, 3);
-        let north_south = north_south_step_km(3);
+        let distances = distance_to_mask_km(3, 3, EARTH_RADIUS_KM, &seeds);
+        let east_west = east_west_step_km(0, 3, 3, EARTH_RADIUS_KM);
+        let north_south = north_south_step_km(3, EARTH_RADIUS_KM);
         let diagonal = north_south.hypot(east_west);
         assert!((distances[1] - east_west).abs() < 1e-3);
         assert!((distances[4] - diagonal).abs() < 1e-3);
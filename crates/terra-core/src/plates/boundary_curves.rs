@@ -4,10 +4,10 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::f64::consts::PI;
 
 use crate::plates::plate_dynamics::BoundaryCharacter;
-use crate::sphere::{great_circle_distance_rad, Vec3};
+use crate::sphere::{equatorial_cell_km, great_circle_distance_rad, Vec3};
 
-const EARTH_RADIUS_KM: f64 = 6371.0;
-const MIN_COMPONENT_PIXELS: usize = 5;
+/// Boundary components shorter than this are dropped (5 cells at 1024 × 512).
+const MIN_COMPONENT_KM: f64 = 195.0;
 const CHAIKIN_ITERS: usize = 2;
 
 /// A single vertex along a boundary polyline, with interpolated metadata.
@@ -70,20 +70,24 @@ struct ControlVertex {
 }
 
 /// Extract smooth ordered boundary polylines from raster plate-boundary data.
+///
+/// Arc lengths are in kilometres on a planet of `radius_km`.
 pub fn extract_boundary_polylines(
     boundary_field: &[BoundaryCharacter],
     is_boundary: &[bool],
     plate_ids: &[u8],
+    radius_km: f64,
     width: usize,
     height: usize,
 ) -> Vec<BoundaryPolyline> {
     let components =
         extract_components_from_mask(boundary_field, is_boundary, plate_ids, width, height);
+    let min_pixels = min_component_pixels(width, radius_km);
 
     let mut polylines = Vec::new();
     for component in components
         .into_iter()
-        .filter(|component| component.pixels.len() >= MIN_COMPONENT_PIXELS)
+        .filter(|component| component.pixels.len() >= min_pixels)
     {
         let skeleton = thin_component(&component, width, height);
         if skeleton.is_empty() {
@@ -97,7 +101,8 @@ pub fn extract_boundary_polylines(
         let control_vertices =
             ordered_control_vertices(&ordered_pixels, boundary_field, plate_ids, width, height);
         let smoothed = chaikin_smooth(&control_vertices, is_closed, CHAIKIN_ITERS);
-        let (vertices, arc_lengths) = build_polyline_vertices(&smoothed, is_closed, width, height);
+        let (vertices, arc_lengths) =
+            build_polyline_vertices(&smoothed, is_closed, radius_km, width, height);
 
         polylines.push(BoundaryPolyline {
             plate_a: u32::from(component.plate_a),
@@ -138,6 +143,11 @@ pub fn extract_boundary_polylines(
     polylines
 }
 
+/// Minimum component size in pixels for a global `width`-column grid.
+fn min_component_pixels(width: usize, radius_km: f64) -> usize {
+    ((MIN_COMPONENT_KM / equatorial_cell_km(width, radius_km)).ceil() as usize).max(3)
+}
+
 fn extract_components_from_mask(
     boundary_field: &[BoundaryCharacter],
     mask: &[bool],
@@ -375,6 +385,7 @@ fn chaikin_smooth(
 fn build_polyline_vertices(
     controls: &[ControlVertex],
     is_closed: bool,
+    radius_km: f64,
     width: usize,
     height: usize,
 ) -> (Vec<BoundaryVertex>, Vec<f64>) {
@@ -411,7 +422,7 @@ fn build_polyline_vertices(
     let mut cumulative = 0.0_f64;
     for i in 0..vertices.len() {
         if i > 0 {
-            cumulative += great_circle_distance_rad(points[i - 1], points[i]) * EARTH_RADIUS_KM;
+            cumulative += great_circle_distance_rad(points[i - 1], points[i]) * radius_km;
         }
         arc_lengths.push(cumulative);
     }
@@ -425,7 +436,11 @@ fn dominant_character(boundary_field: &[BoundaryCharacter], pixels: &[usize]) ->
         .map(|&idx| boundary_field[idx].convergent_rate)
         .sum::<f32>()
         / pixels.len().max(1) as f32;
+    boundary_type_for_rate(mean_convergent)
+}
 
+/// Boundary type of a mean convergent rate in cm/yr.
+pub(crate) fn boundary_type_for_rate(mean_convergent: f32) -> BoundaryType {
     if mean_convergent > 1.0 {
         BoundaryType::Convergent
     } else if mean_convergent < -1.0 {
@@ -812,6 +827,7 @@ mod tests {
 
     use crate::plates::plate_dynamics::compute_plate_dynamics;
     use crate::plates::plate_generation::generate_plate_geometry;
+    use crate::sphere::EARTH_RADIUS_KM;
 
     const TEST_WIDTH: usize = 1024;
     const TEST_HEIGHT: usize = 512;
@@ -835,7 +851,7 @@ mod tests {
         FIXTURE.get_or_init(|| {
             let geometry =
                 generate_plate_geometry(TEST_PLATES, 42, TEST_WARP_DEG, TEST_WIDTH, TEST_HEIGHT);
-            let dynamics = compute_plate_dynamics(&geometry, 0.5, 42);
+            let dynamics = compute_plate_dynamics(&geometry, 0.5, EARTH_RADIUS_KM, 42);
             let convergent_mask = dynamics
                 .is_boundary
                 .iter()
@@ -853,14 +869,16 @@ mod tests {
             );
             let convergent_components_filtered = convergent_components_raw
                 .iter()
-                .filter(|component| component.pixels.len() >= MIN_COMPONENT_PIXELS)
+                .filter(|component| {
+                    component.pixels.len() >= min_component_pixels(TEST_WIDTH, EARTH_RADIUS_KM)
+                })
                 .cloned()
                 .collect::<Vec<_>>();
             let largest_convergent_raw = convergent_components_raw
                 .iter()
-                .filter(|component| ordered_pair(component.plate_a, component.plate_b) == (3, 10))
+                .filter(|component| ordered_pair(component.plate_a, component.plate_b) == (2, 4))
                 .max_by_key(|component| component.pixels.len())
-                .expect("pair 3-10 convergent component")
+                .expect("pair 2-4 convergent component")
                 .clone();
             let largest_convergent_thinned =
                 thin_component(&largest_convergent_raw, TEST_WIDTH, TEST_HEIGHT);
@@ -874,11 +892,16 @@ mod tests {
                 TEST_HEIGHT,
             );
             let smoothed = chaikin_smooth(&control_vertices, is_closed, CHAIKIN_ITERS);
-            let (vertices, arc_lengths) =
-                build_polyline_vertices(&smoothed, is_closed, TEST_WIDTH, TEST_HEIGHT);
+            let (vertices, arc_lengths) = build_polyline_vertices(
+                &smoothed,
+                is_closed,
+                EARTH_RADIUS_KM,
+                TEST_WIDTH,
+                TEST_HEIGHT,
+            );
             let largest_polyline = BoundaryPolyline {
-                plate_a: 3,
-                plate_b: 10,
+                plate_a: 2,
+                plate_b: 4,
                 dominant_character: dominant_character(
                     &dynamics.boundary_field,
                     &largest_convergent_raw.pixels,
@@ -905,8 +928,8 @@ mod tests {
     #[test]
     fn component_extraction_matches_seed42_probe() {
         let fixture = seed42_fixture();
-        assert_eq!(fixture.convergent_components_raw.len(), 17);
-        assert_eq!(fixture.convergent_components_filtered.len(), 15);
+        assert_eq!(fixture.convergent_components_raw.len(), 44);
+        assert_eq!(fixture.convergent_components_filtered.len(), 32);
     }
 
     #[test]
@@ -917,9 +940,9 @@ mod tests {
                 fixture.largest_convergent_raw.plate_a,
                 fixture.largest_convergent_raw.plate_b
             ),
-            (3, 10)
+            (2, 4)
         );
-        assert_eq!(fixture.largest_convergent_raw.pixels.len(), 476);
+        assert_eq!(fixture.largest_convergent_raw.pixels.len(), 809);
         for &idx in &fixture.largest_convergent_thinned {
             let degree = neighbors8(idx, TEST_WIDTH, TEST_HEIGHT)
                 .into_iter()
@@ -928,8 +951,8 @@ mod tests {
             assert!(degree <= 2, "skeleton degree {degree} at idx {idx}");
         }
         assert!(
-            (110..=160).contains(&fixture.largest_convergent_thinned.len()),
-            "expected thinned length in 110..=160, got {}",
+            (230..=300).contains(&fixture.largest_convergent_thinned.len()),
+            "expected thinned length in 230..=300, got {}",
             fixture.largest_convergent_thinned.len()
         );
     }
@@ -1010,6 +1033,7 @@ mod tests {
             &fixture.boundary_field,
             &fixture.is_boundary,
             &fixture.plate_ids,
+            EARTH_RADIUS_KM,
             TEST_WIDTH,
             TEST_HEIGHT,
         );
@@ -0,0 +1,413 @@
+//! Topology of the plate boundary network.
+//!
+//! `BoundaryGraph` ties the independent `BoundaryPolyline`s together: every
+//! polyline is an edge, and its ends are nodes, either a triple junction or a
+//! free endpoint where a boundary fades out or was cut short by the
+//! minimum-length filter.  Free endpoints of the same plate pair that nearly
+//! touch share a node, so a boundary broken into pieces still walks through.
+
+use crate::plates::boundary_curves::{BoundaryPolyline, BoundaryType};
+use crate::plates::triple_junctions::TripleJunction;
+use crate::sphere::{equatorial_cell_km, great_circle_distance_rad, Vec3};
+
+/// Polyline ends within this distance of a node snap to it.
+const NODE_SNAP_KM: f64 = 300.0;
+/// Floor on the snap distance in equatorial cells; thinning and smoothing
+/// pull polyline ends a few cells back from the junction.
+const MIN_NODE_SNAP_CELLS: f64 = 4.0;
+
+/// What a node of the boundary graph is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BoundaryNodeKind {
+    /// Index into `PlateSimulation::triple_junctions`.
+    Junction(usize),
+    /// Free end of one or more polylines of the same plate pair.
+    Endpoint,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundaryNode {
+    pub kind: BoundaryNodeKind,
+    /// Position on the unit sphere.
+    pub position: Vec3,
+    /// Edges ending at this node; an edge whose two ends share the node is
+    /// listed twice.
+    pub edges: Vec<usize>,
+}
+
+/// One boundary polyline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundaryEdge {
+    /// Index into `PlateSimulation::boundary_polylines`.
+    pub polyline: usize,
+    pub plates: [u32; 2],
+    pub boundary_type: BoundaryType,
+    pub length_km: f64,
+    /// Nodes at the first and last vertex; `None` for closed loops.
+    pub nodes: Option<[usize; 2]>,
+}
+
+impl BoundaryEdge {
+    pub fn borders(&self, plate: u32) -> bool {
+        self.plates.contains(&plate)
+    }
+
+    /// The plate across this boundary from `plate`.
+    pub fn other_plate(&self, plate: u32) -> u32 {
+        if self.plates[0] == plate {
+            self.plates[1]
+        } else {
+            self.plates[0]
+        }
+    }
+}
+
+/// Boundary length split by boundary type.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PerimeterByType {
+    pub convergent_km: f64,
+    pub divergent_km: f64,
+    pub transform_km: f64,
+}
+
+impl PerimeterByType {
+    pub fn total_km(&self) -> f64 {
+        self.convergent_km + self.divergent_km + self.transform_km
+    }
+
+    fn add(&mut self, boundary_type: BoundaryType, length_km: f64) {
+        match boundary_type {
+            BoundaryType::Convergent => self.convergent_km += length_km,
+            BoundaryType::Divergent => self.divergent_km += length_km,
+            BoundaryType::Transform => self.transform_km += length_km,
+        }
+    }
+}
+
+/// Junctions and polyline ends joined by boundary polylines.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BoundaryGraph {
+    pub nodes: Vec<BoundaryNode>,
+    /// `edges[i]` is `polylines[i]`.
+    pub edges: Vec<BoundaryEdge>,
+}
+
+impl BoundaryGraph {
+    /// Connect `polylines` through `junctions` on a `width`-column grid of
+    /// a planet of `radius_km`.
+    ///
+    /// A polyline end snaps to the nearest junction of both its plates, else
+    /// to a free endpoint of the same plate pair, else starts a new endpoint.
+    pub fn build(
+        polylines: &[BoundaryPolyline],
+        junctions: &[TripleJunction],
+        radius_km: f64,
+        width: usize,
+    ) -> Self {
+        let snap_rad = NODE_SNAP_KM.max(MIN_NODE_SNAP_CELLS * equatorial_cell_km(width, radius_km))
+            / radius_km;
+        let mut nodes = junctions
+            .iter()
+            .enumerate()
+            .map(|(i, junction)| BoundaryNode {
+                kind: BoundaryNodeKind::Junction(i),
+                position: junction.position,
+                edges: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        // Plate pair of every free endpoint node, by node index.
+        let mut endpoint_pairs: Vec<(usize, [u32; 2])> = Vec::new();
+
+        let mut edges = Vec::with_capacity(polylines.len());
+        for (index, polyline) in polylines.iter().enumerate() {
+            let plates = ordered([polyline.plate_a, polyline.plate_b]);
+            let ends = match (polyline.vertices.first(), polyline.vertices.last()) {
+                (Some(first), Some(last)) if !polyline.is_closed => Some([first, last]),
+                _ => None,
+            };
+            let node_ids = ends.map(|ends| {
+                ends.map(|vertex| {
+                    let position =
+                        Vec3::from_latlon(vertex.lat.to_degrees(), vertex.lon.to_degrees());
+                    let junction = nearest(
+                        junctions.iter().enumerate().filter(|(_, j)| {
+                            plates
+                                .iter()
+                                .all(|&p| j.plates.iter().any(|&q| u32::from(q) == p))
+                        }),
+                        |(_, j)| j.position,
+                        position,
+                        snap_rad,
+                    );
+                    if let Some((node, _)) = junction {
+                        return node;
+                    }
+                    let endpoint = nearest(
+                        endpoint_pairs.iter().filter(|(_, pair)| *pair == plates),
+                        |&&(node, _)| nodes[node].position,
+                        position,
+                        snap_rad,
+                    );
+                    if let Some(&(node, _)) = endpoint {
+                        return node;
+                    }
+                    nodes.push(BoundaryNode {
+                        kind: BoundaryNodeKind::Endpoint,
+                        position,
+                        edges: Vec::new(),
+                    });
+                    endpoint_pairs.push((nodes.len() - 1, plates));
+                    nodes.len() - 1
+                })
+            });
+            if let Some(ids) = node_ids {
+                for id in ids {
+                    nodes[id].edges.push(index);
+                }
+            }
+            edges.push(BoundaryEdge {
+                polyline: index,
+                plates,
+                boundary_type: polyline.dominant_character,
+                length_km: polyline.arc_lengths.last().copied().unwrap_or(0.0),
+                nodes: node_ids,
+            });
+        }
+
+        Self { nodes, edges }
+    }
+
+    /// Edges bordering `plate`, in polyline order.
+    pub fn edges_of_plate(&self, plate: u32) -> impl Iterator<Item = usize> + '_ {
+        (0..self.edges.len()).filter(move |&e| self.edges[e].borders(plate))
+    }
+
+    /// Plates sharing at least one boundary polyline with `plate`, ascending.
+    pub fn neighbors_of_plate(&self, plate: u32) -> Vec<u32> {
+        let mut neighbors = self
+            .edges_of_plate(plate)
+            .map(|e| self.edges[e].other_plate(plate))
+            .collect::<Vec<_>>();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        neighbors
+    }
+
+    /// Boundary length of `plate` by type.
+    pub fn perimeter_of_plate(&self, plate: u32) -> PerimeterByType {
+        let mut perimeter = PerimeterByType::default();
+        for e in self.edges_of_plate(plate) {
+            perimeter.add(self.edges[e].boundary_type, self.edges[e].length_km);
+        }
+        perimeter
+    }
+
+    /// Length of every boundary on the planet by type.
+    pub fn length_by_type(&self) -> PerimeterByType {
+        let mut total = PerimeterByType::default();
+        for edge in &self.edges {
+            total.add(edge.boundary_type, edge.length_km);
+        }
+        total
+    }
+
+    /// Walk the boundary of `plate` from edge `start` toward its last vertex,
+    /// passing through junctions and joined endpoints onto the next edge of
+    /// the same plate.
+    ///
+    /// Returns the edges in walking order, starting with `start`; the walk
+    /// ends when it closes on itself or reaches a node with no unvisited
+    /// edge of `plate`.
+    pub fn walk_plate_boundary(&self, plate: u32, start: usize) -> Vec<usize> {
+        let mut path = vec![start];
+        if !self.edges[start].borders(plate) {
+            return path;
+        }
+        let mut visited = vec![false; self.edges.len()];
+        visited[start] = true;
+        let (mut edge, mut node) = match self.edges[start].nodes {
+            Some([_, end]) => (start, end),
+            None => return path,
+        };
+        loop {
+            let next = self.nodes[node]
+                .edges
+                .iter()
+                .copied()
+                .find(|&e| e != edge && !visited[e] && self.edges[e].borders(plate));
+            let Some(next) = next else {
+                return path;
+            };
+            visited[next] = true;
+            path.push(next);
+            let [a, b] = self.edges[next].nodes.expect("edges at a node have ends");
+            node = if a == node { b } else { a };
+            edge = next;
+        }
+    }
+}
+
+fn ordered(plates: [u32; 2]) -> [u32; 2] {
+    [plates[0].min(plates[1]), plates[0].max(plates[1])]
+}
+
+/// The item nearest `target` within `max_rad`, if any.
+fn nearest<T>(
+    items: impl Iterator<Item = T>,
+    position: impl Fn(&T) -> Vec3,
+    target: Vec3,
+    max_rad: f64,
+) -> Option<T> {
+    items
+        .map(|item| (great_circle_distance_rad(position(&item), target), item))
+        .filter(|(distance, _)| *distance <= max_rad)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map(|(_, item)| item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plates::boundary_curves::BoundaryVertex;
+    use crate::plates::simulate_plates;
+    use crate::sphere::EARTH_RADIUS_KM;
+
+    fn polyline(plates: [u32; 2], kind: BoundaryType, path: &[(f64, f64)]) -> BoundaryPolyline {
+        let vertices = path
+            .iter()
+            .map(|&(lat, lon)| BoundaryVertex {
+                x: 0.0,
+                y: 0.0,
+                lat: lat.to_radians(),
+                lon: lon.to_radians(),
+                convergent_rate: 0.0,
+                transform_rate: 0.0,
+                tangent: (1.0, 0.0),
+                normal: (0.0, 1.0),
+            })
+            .collect::<Vec<_>>();
+        let points = path
+            .iter()
+            .map(|&(lat, lon)| Vec3::from_latlon(lat, lon))
+            .collect::<Vec<_>>();
+        let mut arc_lengths = vec![0.0];
+        for pair in points.windows(2) {
+            let step = great_circle_distance_rad(pair[0], pair[1]) * EARTH_RADIUS_KM;
+            arc_lengths.push(arc_lengths.last().unwrap() + step);
+        }
+        BoundaryPolyline {
+            plate_a: plates[0],
+            plate_b: plates[1],
+            dominant_character: kind,
+            vertices,
+            is_closed: false,
+            arc_lengths,
+        }
+    }
+
+    /// Three plates meeting at (0°, 0°), plus a 0–1 ridge broken by a gap
+    /// and a closed loop of plate 3 inside plate 2.
+    fn sample_graph() -> BoundaryGraph {
+        let junction = TripleJunction {
+            plates: [0, 1, 2],
+            boundaries: [
+                BoundaryType::Convergent,
+                BoundaryType::Transform,
+                BoundaryType::Divergent,
+            ],
+            position: Vec3::from_latlon(0.0, 0.0),
+            cell: 0,
+        };
+        let mut island = polyline(
+            [2, 3],
+            BoundaryType::Convergent,
+            &[(-40.0, 0.0), (-40.0, 10.0), (-45.0, 5.0)],
+        );
+        island.is_closed = true;
+        let polylines = vec![
+            polyline([0, 1], BoundaryType::Divergent, &[(1.0, 0.5), (20.0, 0.0)]),
+            polyline([1, 0], BoundaryType::Divergent, &[(21.0, 0.0), (40.0, 0.0)]),
+            polyline(
+                [0, 2],
+                BoundaryType::Transform,
+                &[(0.5, -1.0), (0.0, -30.0)],
+            ),
+            polyline([1, 2], BoundaryType::Convergent, &[(0.0, 30.0), (0.5, 1.0)]),
+            island,
+        ];
+        BoundaryGraph::build(&polylines, &[junction], EARTH_RADIUS_KM, 1024)
+    }
+
+    #[test]
+    fn polylines_snap_to_junctions_and_join_across_gaps() {
+        let graph = sample_graph();
+        assert_eq!(graph.edges.len(), 5);
+        assert_eq!(graph.nodes[0].kind, BoundaryNodeKind::Junction(0));
+        assert_eq!(graph.nodes[0].edges, vec![0, 2, 3]);
+        let gap = graph.edges[0].nodes.unwrap()[1];
+        assert_eq!(graph.edges[1].nodes.unwrap()[0], gap);
+        assert_eq!(graph.nodes[gap].kind, BoundaryNodeKind::Endpoint);
+        assert_eq!(graph.edges[1].plates, [0, 1]);
+        assert_eq!(graph.edges[4].nodes, None);
+        // Junction + gap + three free ends.
+        assert_eq!(graph.nodes.len(), 5);
+    }
+
+    #[test]
+    fn plate_queries() {
+        let graph = sample_graph();
+        assert_eq!(graph.edges_of_plate(1).collect::<Vec<_>>(), vec![0, 1, 3]);
+        assert_eq!(graph.neighbors_of_plate(2), vec![0, 1, 3]);
+        let perimeter = graph.perimeter_of_plate(0);
+        assert_eq!(perimeter.convergent_km, 0.0);
+        assert!((perimeter.divergent_km - 38.0 * 111.19).abs() < 20.0);
+        assert!((perimeter.transform_km - 29.0 * 111.19).abs() < 20.0);
+        let total = graph.length_by_type();
+        assert!(total.convergent_km > perimeter.convergent_km);
+        assert!(
+            (total.total_km() - graph.edges.iter().map(|e| e.length_km).sum::<f64>()).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn walks_follow_one_plate_through_junctions() {
+        let graph = sample_graph();
+        // From the transform's far end back into the junction, then up the
+        // broken ridge of plate 0.
+        let mut reversed = graph.clone();
+        reversed.edges[2].nodes = reversed.edges[2].nodes.map(|[a, b]| [b, a]);
+        assert_eq!(reversed.walk_plate_boundary(0, 2), vec![2, 0, 1]);
+        assert_eq!(graph.walk_plate_boundary(2, 3), vec![3, 2]);
+        assert_eq!(graph.walk_plate_boundary(3, 4), vec![4]);
+    }
+
+    #[test]
+    fn simulated_boundaries_mostly_end_at_junctions() {
+        let plates = simulate_plates(42, 0.5, 0.5, 256, 128);
+        let graph = plates.boundary_graph(EARTH_RADIUS_KM);
+        assert_eq!(graph.edges.len(), plates.boundary_polylines.len());
+        let ends = graph
+            .edges
+            .iter()
+            .filter_map(|edge| edge.nodes)
+            .flatten()
+            .collect::<Vec<_>>();
+        let at_junctions = ends
+            .iter()
+            .filter(|&&node| matches!(graph.nodes[node].kind, BoundaryNodeKind::Junction(_)))
+            .count();
+        assert!(
+            at_junctions * 2 >= ends.len(),
+            "{at_junctions} of {} polyline ends at junctions",
+            ends.len()
+        );
+        for plate in 0..plates.n_plates as u32 {
+            let Some(start) = graph.edges_of_plate(plate).next() else {
+                continue;
+            };
+            for edge in graph.walk_plate_boundary(plate, start) {
+                assert!(graph.edges[edge].borders(plate));
+            }
+        }
+    }
+}
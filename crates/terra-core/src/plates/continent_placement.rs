@@ -10,10 +10,10 @@ use crate::plates::age_field::cell_to_vec3;
 use crate::plates::continents::CrustType;
 use crate::plates::plate_dynamics::{BoundaryCharacter, PlateDynamics};
 use crate::plates::plate_generation::PlateGeometry;
-use crate::sphere::{great_circle_distance_rad, slerp, Vec3};
+use crate::progress;
+use crate::seed::{self, StableRng};
+use crate::sphere::{equatorial_cell_km, great_circle_distance_rad, slerp, Vec3, EARTH_RADIUS_KM};
 use noise::{NoiseFn, Perlin};
-use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
 use std::cmp::Ordering;
 use std::collections::VecDeque;
 
@@ -30,11 +30,11 @@ const COASTLINE_BASE_FREQUENCY: f64 = 6.0;
 const COASTLINE_OCTAVES: usize = 4;
 const COASTLINE_FALLOFF: f64 = 0.5;
 const COASTLINE_AMPLITUDE: f64 = 0.28;
-const MARGIN_WIDTH_CELLS: f32 = 4.0;
+/// Width of active / passive margin belts: 4 cells of the 1024 × 512 Earth
+/// grid it was tuned on.
+const MARGIN_WIDTH_KM: f64 = 4.0 * equatorial_cell_km(1024, EARTH_RADIUS_KM);
 const CONTINENTAL_PLATE_WEIGHT_EXPONENT: f64 = 0.7;
 const MAX_PLATE_LAND_FRACTION: f64 = 0.9;
-const CONTINENT_SEED_SALT: u64 = 0xC017_1EE7_ABCD_0042;
-const DIVERGENT_OFFSET_SALT: u64 = 0xD17E_2F50_0FF5_E7A5;
 
 /// Bias used when choosing a continent center on a host plate.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -111,12 +111,16 @@ struct ContinentGrowthInputs<'a> {
 }
 
 /// Place continental blocks on plates.
+///
+/// `radius_km` sizes the continental margins.
+#[allow(clippy::too_many_arguments)]
 pub fn place_continents(
     geometry: &PlateGeometry,
     dynamics: &PlateDynamics,
     continental_coverage: f32,
     n_continents: usize,
     seed: u64,
+    radius_km: f64,
     width: usize,
     height: usize,
 ) -> ContinentPlacement {
@@ -151,7 +155,7 @@ pub fn place_continents(
     let target_coverage = continental_coverage.clamp(0.0, 1.0) as f64;
     let target_land_area = total_area * target_coverage;
     let n_continents = n_continents.clamp(1, geometry.n_plates);
-    let mut rng = StdRng::seed_from_u64(seed ^ CONTINENT_SEED_SALT);
+    let mut rng = StableRng::for_stage(seed, "continents/placement", &[]);
     let continental_plates = weighted_plate_selection(&plate_areas, n_continents, &mut rng);
     let target_continent_areas = allocate_continent_areas(
         &continental_plates,
@@ -196,6 +200,8 @@ pub fn place_continents(
         for idx in &continent_cells {
             continental_mask[*idx] = true;
         }
+        // Growth is ~80% of placement; crust classification takes the rest.
+        progress::report(0.8 * (continent_index + 1) as f32 / continental_plates.len() as f32);
         let host_land_area = continent_cells
             .iter()
             .filter(|&&idx| geometry.plate_ids[idx] == plate_id)
@@ -211,7 +217,14 @@ pub fn place_continents(
         });
     }
 
-    let crust_field = classify_crust_types(geometry, dynamics, &continental_mask, width, height);
+    let crust_field = classify_crust_types(
+        geometry,
+        dynamics,
+        &continental_mask,
+        radius_km,
+        width,
+        height,
+    );
     let plate_land_fractions = plate_land_fractions(
         &continental_mask,
         &geometry.plate_ids,
@@ -303,7 +316,7 @@ fn plate_land_fractions(
         .collect()
 }
 
-fn weighted_plate_selection(plate_areas: &[f64], count: usize, rng: &mut StdRng) -> Vec<u8> {
+fn weighted_plate_selection(plate_areas: &[f64], count: usize, rng: &mut StableRng) -> Vec<u8> {
     let mut candidates: Vec<(u8, f64)> = plate_areas
         .iter()
         .enumerate()
@@ -321,7 +334,7 @@ fn weighted_plate_selection(plate_areas: &[f64], count: usize, rng: &mut StdRng)
             .map(|(_, weight)| *weight)
             .sum::<f64>()
             .max(1e-9);
-        let mut draw = rng.gen_range(0.0_f64..total_weight);
+        let mut draw = rng.range_f64(0.0, total_weight);
         let mut chosen = 0usize;
         for (idx, (_, weight)) in candidates.iter().enumerate() {
             if draw <= *weight {
@@ -340,7 +353,7 @@ fn allocate_continent_areas(
     continental_plates: &[u8],
     plate_areas: &[f64],
     target_land_area: f64,
-    rng: &mut StdRng,
+    rng: &mut StableRng,
 ) -> Vec<f64> {
     let capacities: Vec<f64> = continental_plates
         .iter()
@@ -389,14 +402,8 @@ fn allocate_continent_areas(
     allocations
 }
 
-fn sample_log_normal(mu: f64, sigma: f64, rng: &mut StdRng) -> f64 {
-    (mu + sigma * sample_standard_normal(rng)).exp()
-}
-
-fn sample_standard_normal(rng: &mut StdRng) -> f64 {
-    let u1 = rng.gen_range(f64::MIN_POSITIVE..1.0_f64);
-    let u2 = rng.gen_range(0.0_f64..1.0_f64);
-    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+fn sample_log_normal(mu: f64, sigma: f64, rng: &mut StableRng) -> f64 {
+    (mu + sigma * rng.standard_normal()).exp()
 }
 
 fn spherical_centroid(points: &[Vec3], members: &[usize]) -> Option<Vec3> {
@@ -417,7 +424,7 @@ fn choose_bias(
     plate_ids: &[u8],
     dynamics: &PlateDynamics,
     plate_id: u8,
-    rng: &mut StdRng,
+    rng: &mut StableRng,
 ) -> ContinentBias {
     let has_convergent = plate_ids.iter().enumerate().any(|(idx, &own_plate)| {
         own_plate == plate_id
@@ -425,7 +432,7 @@ fn choose_bias(
             && dynamics.boundary_field[idx].convergent_rate
                 > dynamics.boundary_field[idx].transform_rate.abs()
     });
-    let draw = rng.gen_range(0.0_f64..1.0_f64);
+    let draw = rng.next_f64();
     if has_convergent && draw < 0.40 {
         ContinentBias::ConvergentSide
     } else if draw < 0.75 {
@@ -441,7 +448,7 @@ fn choose_center_point(
     members: &[usize],
     centroid: Vec3,
     bias: ContinentBias,
-    rng: &mut StdRng,
+    rng: &mut StableRng,
 ) -> Vec3 {
     match bias {
         ContinentBias::Centered => centroid,
@@ -461,7 +468,7 @@ fn choose_center_point(
                         .unwrap_or(Ordering::Equal)
                 })
                 .unwrap_or_else(|| members[0]);
-            let t = rng.gen_range(0.30_f64..0.50_f64);
+            let t = rng.range_f64(0.30, 0.50);
             slerp(centroid, points[best_boundary], t)
         }
         ContinentBias::RandomSide => {
@@ -471,11 +478,11 @@ fn choose_center_point(
                 .filter(|&idx| dynamics.is_boundary[idx])
                 .collect();
             let boundary_idx = if boundary_cells.is_empty() {
-                members[rng.gen_range(0..members.len())]
+                members[rng.below(members.len())]
             } else {
-                boundary_cells[rng.gen_range(0..boundary_cells.len())]
+                boundary_cells[rng.below(boundary_cells.len())]
             };
-            let t = rng.gen_range(0.40_f64..0.70_f64);
+            let t = rng.range_f64(0.40, 0.70);
             slerp(centroid, points[boundary_idx], t)
         }
     }
@@ -494,20 +501,18 @@ fn nearest_plate_cell(points: &[Vec3], members: &[usize], target: Vec3) -> usize
 }
 
 fn continent_growth_config(seed: u64, continent_index: usize) -> ContinentGrowthConfig {
-    let salted_seed = seed
-        ^ CONTINENT_SEED_SALT
-        ^ ((continent_index as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
-    let mut rng = StdRng::seed_from_u64(salted_seed);
+    let growth_seed = seed::derive(seed, "continents/growth", &[continent_index as u64]);
+    let mut rng = StableRng::new(growth_seed);
     let warp_amplitude_rad = rng
-        .gen_range(SHAPE_WARP_MIN_DEG..=SHAPE_WARP_MAX_DEG)
+        .range_f64(SHAPE_WARP_MIN_DEG, SHAPE_WARP_MAX_DEG)
         .to_radians();
     let pull_strength_rad = rng
-        .gen_range(CONVERGENT_PULL_MIN_DEG..=CONVERGENT_PULL_MAX_DEG)
+        .range_f64(CONVERGENT_PULL_MIN_DEG, CONVERGENT_PULL_MAX_DEG)
         .to_radians();
 
     ContinentGrowthConfig {
-        low_frequency_noise: Perlin::new((salted_seed ^ 0x51A7_0C13) as u32),
-        coastline_noise: Perlin::new((salted_seed ^ 0xC05A_571E) as u32),
+        low_frequency_noise: Perlin::new(seed::derive_u32(growth_seed, "low-frequency", &[])),
+        coastline_noise: Perlin::new(seed::derive_u32(growth_seed, "coastline", &[])),
         warp_amplitude_rad,
         pull_strength_rad,
     }
@@ -722,6 +727,7 @@ fn classify_crust_types(
     geometry: &PlateGeometry,
     dynamics: &PlateDynamics,
     continental_mask: &[bool],
+    radius_km: f64,
     width: usize,
     height: usize,
 ) -> Vec<CrustType> {
@@ -744,14 +750,16 @@ fn classify_crust_types(
     let distance_to_convergent =
         multi_source_grid_distance(width, height, &convergent_boundary_seeds, None);
 
+    let margin_cells = margin_width_cells(width, radius_km);
+
     let mut crust = vec![CrustType::Oceanic; continental_mask.len()];
     for idx in 0..continental_mask.len() {
         if !continental_mask[idx] {
             continue;
         }
-        if distance_to_convergent[idx] <= MARGIN_WIDTH_CELLS {
+        if distance_to_convergent[idx] <= margin_cells {
             crust[idx] = CrustType::ActiveMargin;
-        } else if distance_to_ocean[idx] <= MARGIN_WIDTH_CELLS {
+        } else if distance_to_ocean[idx] <= margin_cells {
             crust[idx] = CrustType::PassiveMargin;
         } else {
             crust[idx] = CrustType::Continental;
@@ -760,6 +768,15 @@ fn classify_crust_types(
     crust
 }
 
+/// `MARGIN_WIDTH_KM` in cells of a global `width`-column grid on a planet of
+/// `radius_km`.
+///
+/// Never narrower than 2 cells, so coarse preview grids still resolve a
+/// margin with a shelf side and an ocean side.
+fn margin_width_cells(width: usize, radius_km: f64) -> f32 {
+    ((MARGIN_WIDTH_KM / equatorial_cell_km(width, radius_km)) as f32).max(2.0)
+}
+
 fn multi_source_grid_distance(
     width: usize,
     height: usize,
@@ -819,7 +836,7 @@ fn divergent_transform_offsets(
 ) -> Vec<DivergentTransformOffset> {
     let mut visited = vec![false; geometry.plate_ids.len()];
     let mut offsets = Vec::new();
-    let mut rng = StdRng::seed_from_u64(seed ^ DIVERGENT_OFFSET_SALT);
+    let mut rng = StableRng::for_stage(seed, "continents/divergent-offsets", &[]);
 
     for start in 0..geometry.plate_ids.len() {
         if visited[start]
@@ -858,16 +875,16 @@ fn divergent_transform_offsets(
         }
         component.sort_unstable();
         let mut sign = 1_i8;
-        let mut cursor = rng.gen_range(0..component.len().max(1));
+        let mut cursor = rng.below(component.len());
         while cursor < component.len() {
             offsets.push(DivergentTransformOffset {
                 anchor_idx: component[cursor],
                 pair,
                 sign,
-                magnitude_cells: rng.gen_range(1_u8..=3_u8),
+                magnitude_cells: rng.range_inclusive(1, 3) as u8,
             });
             sign *= -1;
-            cursor += rng.gen_range(3_usize..=7_usize);
+            cursor += rng.range_inclusive(3, 7);
         }
     }
 
@@ -929,13 +946,14 @@ mod tests {
     fn sample_inputs(seed: u64) -> (PlateGeometry, PlateDynamics, ContinentPlacement) {
         let geometry =
             generate_plate_geometry(TEST_PLATES, seed, TEST_WARP, TEST_WIDTH, TEST_HEIGHT);
-        let dynamics = compute_plate_dynamics(&geometry, 0.5, seed);
+        let dynamics = compute_plate_dynamics(&geometry, 0.5, EARTH_RADIUS_KM, seed);
         let placement = place_continents(
             &geometry,
             &dynamics,
             TEST_COVERAGE,
             TEST_CONTINENTS,
             seed,
+            EARTH_RADIUS_KM,
             TEST_WIDTH,
             TEST_HEIGHT,
         );
@@ -1031,15 +1049,22 @@ mod tests {
             match crust {
                 CrustType::ActiveMargin => {
                     assert!(placement.continental_mask[idx]);
-                    assert!(convergent_distance[idx] <= MARGIN_WIDTH_CELLS);
+                    assert!(
+                        convergent_distance[idx]
+                            <= margin_width_cells(geometry.width, EARTH_RADIUS_KM)
+                    );
                 }
                 CrustType::PassiveMargin => {
                     assert!(placement.continental_mask[idx]);
-                    assert!(ocean_distance[idx] <= MARGIN_WIDTH_CELLS);
+                    assert!(
+                        ocean_distance[idx] <= margin_width_cells(geometry.width, EARTH_RADIUS_KM)
+                    );
                 }
                 CrustType::Continental => {
                     assert!(placement.continental_mask[idx]);
-                    assert!(ocean_distance[idx] > MARGIN_WIDTH_CELLS);
+                    assert!(
+                        ocean_distance[idx] > margin_width_cells(geometry.width, EARTH_RADIUS_KM)
+                    );
                 }
                 CrustType::Oceanic => {
                     assert!(!placement.continental_mask[idx]);
@@ -1057,6 +1082,12 @@ mod tests {
         );
     }
 
+    /// The margin is exactly 4 cells on the grid it was tuned on.
+    #[test]
+    fn margin_width_is_four_cells_at_default_resolution() {
+        assert_eq!(margin_width_cells(1024, EARTH_RADIUS_KM), 4.0);
+    }
+
     #[test]
     fn deterministic() {
         let (geometry, dynamics, a) = sample_inputs(42);
@@ -1066,6 +1097,7 @@ mod tests {
             TEST_COVERAGE,
             TEST_CONTINENTS,
             42,
+            EARTH_RADIUS_KM,
             TEST_WIDTH,
             TEST_HEIGHT,
         );
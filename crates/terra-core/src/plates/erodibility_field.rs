@@ -12,6 +12,7 @@
 //! range, ensuring the smooth constraint (no hard boundaries in the output).
 
 use crate::plates::regime_field::{RegimeField, TectonicRegime};
+use crate::seed;
 use noise::{NoiseFn, Perlin};
 
 /// Generate a smooth erodibility field biased by tectonic regime.
@@ -26,7 +27,7 @@ pub fn generate_erodibility_field(regime_field: &RegimeField, seed: u64) -> Vec<
     }
 
     // Low-frequency Perlin noise for smooth spatial variation.
-    let perlin = Perlin::new((seed & 0xFFFF_FFFF) as u32);
+    let perlin = Perlin::new(seed::derive_u32(seed, "plates/erodibility", &[]));
     // Frequency: ~4 cycles across the full grid → gentle variation.
     let freq_x = 4.0 / width as f64;
     let freq_y = 4.0 / height as f64;
@@ -33,6 +33,7 @@ pub fn derive_grain_field(
     regime_field: &RegimeField,
     dynamics: &PlateDynamics,
     hotspots: &[Vec3],
+    radius_km: f64,
     width: usize,
     height: usize,
 ) -> GrainField {
@@ -43,7 +44,7 @@ pub fn derive_grain_field(
         .enumerate()
         .filter_map(|(idx, &is_boundary)| is_boundary.then_some(idx))
         .collect();
-    let nearest_boundary = distance_to_seeds_km(width, height, &boundary_seeds);
+    let nearest_boundary = distance_to_seeds_km(width, height, radius_km, &boundary_seeds);
 
     for row in 0..height {
         for col in 0..width {
@@ -80,7 +81,7 @@ pub fn derive_grain_field(
                     }
                 }
                 DominantMode::Hotspot => {
-                    let (angle, intensity) = nearest_hotspot_angle(point, hotspots);
+                    let (angle, intensity) = nearest_hotspot_angle(point, hotspots, radius_km);
                     (angle, intensity * regime_character.hotspot_influence[idx])
                 }
             };
@@ -116,7 +117,7 @@ fn dominant_boundary_mode(character: &RegimeCharacterField, idx: usize) -> Domin
     }
 }
 
-fn nearest_hotspot_angle(point: Vec3, hotspots: &[Vec3]) -> (f32, f32) {
+fn nearest_hotspot_angle(point: Vec3, hotspots: &[Vec3], radius_km: f64) -> (f32, f32) {
     let Some((&hotspot, distance)) = hotspots
         .iter()
         .map(|hotspot| (hotspot, point.dot(*hotspot).clamp(-1.0, 1.0).acos() as f32))
@@ -136,14 +137,15 @@ fn nearest_hotspot_angle(point: Vec3, hotspots: &[Vec3]) -> (f32, f32) {
         hotspot.z - point.z * point.dot(hotspot),
     );
     let angle = tangent.dot(north).atan2(tangent.dot(east)) as f32;
-    let intensity = (1.0 - distance / (300.0_f32 / 6371.0_f32)).clamp(0.0, 1.0);
+    let intensity = (1.0 - distance / (300.0_f32 / radius_km as f32)).clamp(0.0, 1.0);
     (angle, intensity)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::plates::plate_dynamics::{BoundaryCharacter, PlateDynamics};
+    use crate::plates::plate_dynamics::{BoundaryCharacter, EulerPole, PlateDynamics};
+    use crate::sphere::EARTH_RADIUS_KM;
 
     fn sample_dynamics() -> PlateDynamics {
         let mut boundary_field = vec![BoundaryCharacter::default(); 4];
@@ -154,7 +156,7 @@ mod tests {
         boundary_field[2].tangent_north = 0.0;
         boundary_field[2].convergent_rate = -4.0;
         PlateDynamics {
-            plate_velocities: vec![(0.0, 0.0); 2],
+            euler_poles: vec![EulerPole::from_angular_velocity(Vec3::new(0.0, 0.0, 0.0)); 2],
             boundary_field,
             is_boundary: vec![false, true, true, false],
         }
@@ -176,7 +178,15 @@ mod tests {
             width: 4,
             height: 1,
         };
-        let grain = derive_grain_field(&character, &regime, &sample_dynamics(), &[], 4, 1);
+        let grain = derive_grain_field(
+            &character,
+            &regime,
+            &sample_dynamics(),
+            &[],
+            EARTH_RADIUS_KM,
+            4,
+            1,
+        );
         assert!(grain.intensities.iter().all(|&value| value == 0.0));
     }
 
@@ -196,7 +206,15 @@ mod tests {
             width: 4,
             height: 1,
         };
-        let grain = derive_grain_field(&character, &regime, &sample_dynamics(), &[], 4, 1);
+        let grain = derive_grain_field(
+            &character,
+            &regime,
+            &sample_dynamics(),
+            &[],
+            EARTH_RADIUS_KM,
+            4,
+            1,
+        );
         let direction = (grain.angles[1].cos(), grain.angles[1].sin());
         let tangent = (1.0_f32, 0.0_f32);
         let dot = direction.0 * tangent.0 + direction.1 * tangent.1;
@@ -219,7 +237,15 @@ mod tests {
             width: 4,
             height: 1,
         };
-        let grain = derive_grain_field(&character, &regime, &sample_dynamics(), &[], 4, 1);
+        let grain = derive_grain_field(
+            &character,
+            &regime,
+            &sample_dynamics(),
+            &[],
+            EARTH_RADIUS_KM,
+            4,
+            1,
+        );
         let direction = (grain.angles[2].cos(), grain.angles[2].sin());
         let tangent = (1.0_f32, 0.0_f32);
         let dot = direction.0 * tangent.0 + direction.1 * tangent.1;
@@ -0,0 +1,170 @@
+//! Age-progressive hotspot tracks.
+//!
+//! Hotspots are fixed in the mantle while the plate above them turns about
+//! its Euler pole, so the plate carries every edifice a plume builds away
+//! from it.  A track is the chain of seamounts and islands left behind on
+//! one plate: the youngest over the plume, older ones strung out along the
+//! plate's motion, as in the Hawaiian–Emperor chain.
+
+use crate::generator::latlon_to_cell;
+use crate::plates::plate_dynamics::EulerPole;
+use crate::sphere::Vec3;
+
+/// Default relative plume strength.
+pub const DEFAULT_PLUME_STRENGTH: f32 = 1.0;
+/// Nominal spacing between successive seamounts of a track.
+const SEAMOUNT_SPACING_KM: f64 = 150.0;
+/// Oldest seamount kept; the Emperor chain reaches about 80 Myr.
+const MAX_TRACK_AGE_MYR: f64 = 80.0;
+/// Below this plate speed the plume keeps feeding a single edifice.
+const MIN_TRACK_SPEED_CM_YR: f64 = 0.5;
+/// km/Myr per cm/yr.
+const KM_MYR_PER_CM_YR: f64 = 10.0;
+
+/// One volcanic edifice of a hotspot track.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Seamount {
+    /// Position on the unit sphere.
+    pub position: Vec3,
+    /// Time since the edifice sat over the plume (Myr).
+    pub age_myr: f64,
+}
+
+/// The chain of edifices one hotspot has left on the plate above it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HotspotTrack {
+    /// The plume, fixed in the mantle frame.
+    pub hotspot: Vec3,
+    /// Plate carrying the track.
+    pub plate: u8,
+    /// Relative plume strength; scales the edifices.
+    pub plume_strength: f32,
+    /// Seamounts from the active one over the plume (age 0) to the oldest.
+    pub seamounts: Vec<Seamount>,
+}
+
+/// Trace the track of every hotspot over the plates of `plate_ids`.
+///
+/// A seamount `t` Myr old sits where the Euler pole of the plate above the
+/// hotspot has carried it in `t` Myr.  Seamounts are spaced about
+/// `SEAMOUNT_SPACING_KM` apart; a track ends at `MAX_TRACK_AGE_MYR` or where
+/// it would leave that plate.
+pub fn trace_hotspot_tracks(
+    hotspots: &[Vec3],
+    plate_ids: &[u8],
+    euler_poles: &[EulerPole],
+    plume_strength: f32,
+    radius_km: f64,
+    width: usize,
+    height: usize,
+) -> Vec<HotspotTrack> {
+    let plate_at = |point: Vec3| {
+        let (lat, lon) = point.to_latlon();
+        plate_ids[latlon_to_cell(lat as f32, lon as f32, width, height)]
+    };
+    hotspots
+        .iter()
+        .map(|&hotspot| {
+            let plate = plate_at(hotspot);
+            let pole = euler_poles[usize::from(plate)];
+            let mut seamounts = vec![Seamount {
+                position: hotspot,
+                age_myr: 0.0,
+            }];
+            let (east, north) = pole.velocity_at(hotspot, radius_km);
+            let speed_cm_yr = f64::from(east.hypot(north));
+            if speed_cm_yr >= MIN_TRACK_SPEED_CM_YR {
+                let step_myr = SEAMOUNT_SPACING_KM / (speed_cm_yr * KM_MYR_PER_CM_YR);
+                for k in 1.. {
+                    let age_myr = k as f64 * step_myr;
+                    if age_myr > MAX_TRACK_AGE_MYR {
+                        break;
+                    }
+                    let position = pole.rotate(hotspot, age_myr);
+                    if plate_at(position) != plate {
+                        break;
+                    }
+                    seamounts.push(Seamount { position, age_myr });
+                }
+            }
+            HotspotTrack {
+                hotspot,
+                plate,
+                plume_strength,
+                seamounts,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plates::{simulate_plates, TectonicRegime};
+    use crate::sphere::{great_circle_distance_rad, EARTH_RADIUS_KM};
+
+    #[test]
+    fn tracks_age_along_plate_motion() {
+        // One plate turning about the north pole at 1°/Myr carries a hotspot
+        // on the equator eastward at about 11 cm/yr.
+        let (width, height) = (360, 180);
+        let pole = EulerPole {
+            axis: Vec3::new(0.0, 0.0, 1.0),
+            rate_deg_per_myr: 1.0,
+        };
+        let hotspot = Vec3::from_latlon(0.0, 0.0);
+        let tracks = trace_hotspot_tracks(
+            &[hotspot],
+            &vec![0; width * height],
+            &[pole],
+            1.5,
+            EARTH_RADIUS_KM,
+            width,
+            height,
+        );
+        let track = &tracks[0];
+        assert_eq!(track.plume_strength, 1.5);
+        assert_eq!(track.seamounts[0].age_myr, 0.0);
+        assert!(track.seamounts.len() > 40, "{}", track.seamounts.len());
+        let oldest = track.seamounts.last().unwrap();
+        assert!(oldest.age_myr <= MAX_TRACK_AGE_MYR);
+        for pair in track.seamounts.windows(2) {
+            assert!(pair[1].age_myr > pair[0].age_myr);
+            let (lat, lon) = pair[1].position.to_latlon();
+            assert!(lat.abs() < 1e-6);
+            assert!(
+                (lon - pair[1].age_myr).abs() < 1e-6,
+                "{lon} at {}",
+                pair[1].age_myr
+            );
+            let spacing_km =
+                great_circle_distance_rad(pair[0].position, pair[1].position) * EARTH_RADIUS_KM;
+            assert!((spacing_km - SEAMOUNT_SPACING_KM).abs() < 1.0);
+        }
+    }
+
+    #[test]
+    fn simulated_tracks_stay_on_their_plate_as_hotspot_regime() {
+        let plates = simulate_plates(42, 0.5, 0.5, 256, 128);
+        assert_eq!(plates.hotspot_tracks.len(), plates.hotspots.len());
+        let (mut older, mut marked) = (0, 0);
+        for track in &plates.hotspot_tracks {
+            for seamount in &track.seamounts {
+                let (lat, lon) = seamount.position.to_latlon();
+                let cell = latlon_to_cell(lat as f32, lon as f32, plates.width, plates.height);
+                assert_eq!(plates.plate_ids[cell], track.plate);
+                if seamount.age_myr > 0.0 {
+                    older += 1;
+                    marked += usize::from(
+                        plates.regime_field.data[cell] == TectonicRegime::VolcanicHotspot,
+                    );
+                }
+            }
+        }
+        assert!(older > 3 * plates.hotspots.len(), "{older} track seamounts");
+        assert!(
+            marked * 2 > older,
+            "{marked} of {older} seamounts are hotspot regime"
+        );
+    }
+}
@@ -2,22 +2,31 @@
 
 pub mod age_field;
 pub mod boundary_curves;
+pub mod boundary_graph;
 pub mod continent_placement;
 pub mod continents;
 pub mod erodibility_field;
 pub mod grain_field;
+pub mod hotspot_tracks;
 pub mod plate_dynamics;
 pub mod plate_generation;
 pub mod regime_field;
+pub mod subduction;
+pub mod triple_junctions;
 
+use crate::body::PlanetBody;
+use crate::parallel;
+use crate::progress;
 use crate::sphere::Vec3;
 use age_field::{compute_thermal_age, distance_to_seeds_km};
 use boundary_curves::{extract_boundary_polylines, BoundaryPolyline};
+use boundary_graph::BoundaryGraph;
 use continent_placement::place_continents;
 use continents::CrustType;
 use erodibility_field::generate_erodibility_field;
 use grain_field::GrainField;
-use plate_dynamics::{compute_plate_dynamics, BoundaryCharacter};
+use hotspot_tracks::{trace_hotspot_tracks, HotspotTrack, DEFAULT_PLUME_STRENGTH};
+use plate_dynamics::{compute_plate_dynamics, BoundaryCharacter, EulerPole};
 use plate_generation::{
     continent_count_from_fragmentation, generate_plate_geometry, plate_count_from_fragmentation,
     DEFAULT_PLATE_WARP_AMPLITUDE_DEG,
@@ -27,10 +36,14 @@ use regime_field::{
     compute_regime_character, discretize_regime_field, generate_hotspots, RegimeCharacterField,
     RegimeField,
 };
+use serde::{Deserialize, Serialize};
+use subduction::back_arc_extension_field;
+use triple_junctions::{detect_triple_junctions, TripleJunction};
 
 /// Number of volcanic hotspots to place per simulation.
-const N_HOTSPOTS: usize = 4;
-const DEFAULT_CONTINENTAL_COVERAGE: f32 = 0.38;
+pub const N_HOTSPOTS: usize = 4;
+/// Fraction of the sphere covered by continental crust.
+pub const DEFAULT_CONTINENTAL_COVERAGE: f32 = 0.38;
 const CONVERGENT_THRESHOLD_CM_YR: f32 = 1.0;
 const DIVERGENT_THRESHOLD_CM_YR: f32 = -1.0;
 
@@ -39,9 +52,14 @@ const DIVERGENT_THRESHOLD_CM_YR: f32 = -1.0;
 pub struct PlateSimulation {
     pub plate_ids: Vec<u8>,
     pub n_plates: usize,
-    pub plate_velocities: Vec<(f32, f32)>,
+    /// Continents placement grew; fewer than the fragmentation asks for
+    /// when a selected plate is left no room.
+    pub n_continents: usize,
+    pub euler_poles: Vec<EulerPole>,
     pub boundary_field: Vec<BoundaryCharacter>,
     pub boundary_polylines: Vec<BoundaryPolyline>,
+    /// Points where three plates meet, classified by their boundaries.
+    pub triple_junctions: Vec<TripleJunction>,
     pub is_boundary: Vec<bool>,
     pub continental_mask: Vec<bool>,
     pub crust_field: Vec<CrustType>,
@@ -51,11 +69,41 @@ pub struct PlateSimulation {
     pub grain_field: GrainField,
     pub erodibility_field: Vec<f32>,
     pub hotspots: Vec<Vec3>,
+    /// Seamount chains each hotspot has left on the plate above it.
+    pub hotspot_tracks: Vec<HotspotTrack>,
     pub divergent_distance_km: Vec<f32>,
     pub width: usize,
     pub height: usize,
 }
 
+/// Plate-simulation knobs that no slider drives.  Defaults are the
+/// calibrated constants.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PlateTuning {
+    /// Number of volcanic hotspots.
+    pub hotspots: usize,
+    /// Relative strength of the hotspot plumes; scales their swells and
+    /// edifices.
+    pub plume_strength: f32,
+    /// Target fraction of the sphere covered by continental crust.
+    pub continental_coverage: f32,
+    /// Curl-noise warp of plate boundaries in degrees (capped per plate
+    /// layout by `generate_plate_geometry`).
+    pub warp_amplitude_deg: f64,
+}
+
+impl Default for PlateTuning {
+    fn default() -> Self {
+        Self {
+            hotspots: N_HOTSPOTS,
+            plume_strength: DEFAULT_PLUME_STRENGTH,
+            continental_coverage: DEFAULT_CONTINENTAL_COVERAGE,
+            warp_amplitude_deg: DEFAULT_PLATE_WARP_AMPLITUDE_DEG,
+        }
+    }
+}
+
 pub fn simulate_plates(
     seed: u64,
     fragmentation: f32,
@@ -63,26 +111,59 @@ pub fn simulate_plates(
     width: usize,
     height: usize,
 ) -> PlateSimulation {
-    let n_plates = plate_count_from_fragmentation(fragmentation);
-    let n_continents = continent_count_from_fragmentation(fragmentation);
-    let geometry = generate_plate_geometry(
-        n_plates,
+    simulate_plates_with(
         seed,
-        DEFAULT_PLATE_WARP_AMPLITUDE_DEG,
+        fragmentation,
+        tectonic_activity,
+        &PlateTuning::default(),
+        &PlanetBody::EARTH,
         width,
         height,
-    );
-    let dynamics = compute_plate_dynamics(&geometry, tectonic_activity, seed);
-    let placement = place_continents(
-        &geometry,
-        &dynamics,
-        DEFAULT_CONTINENTAL_COVERAGE,
-        n_continents,
-        seed,
+    )
+}
+
+/// `simulate_plates` with explicit `tuning` on a planet of the given `body`
+/// instead of the Earth defaults.
+pub fn simulate_plates_with(
+    seed: u64,
+    fragmentation: f32,
+    tectonic_activity: f32,
+    tuning: &PlateTuning,
+    body: &PlanetBody,
+    width: usize,
+    height: usize,
+) -> PlateSimulation {
+    let radius_km = body.radius_km;
+    let n_plates = plate_count_from_fragmentation(fragmentation);
+    let n_continents = continent_count_from_fragmentation(fragmentation);
+    // Progress fractions follow the typical cost of each step.
+    let geometry =
+        generate_plate_geometry(n_plates, seed, tuning.warp_amplitude_deg, width, height);
+    progress::report(0.35);
+    let dynamics = compute_plate_dynamics(&geometry, tectonic_activity, radius_km, seed);
+    progress::report(0.48);
+    let placement = progress::within(0.48, 0.77, || {
+        place_continents(
+            &geometry,
+            &dynamics,
+            tuning.continental_coverage,
+            n_continents,
+            seed,
+            radius_km,
+            width,
+            height,
+        )
+    });
+    let hotspots = generate_hotspots(seed, tuning.hotspots);
+    let hotspot_tracks = trace_hotspot_tracks(
+        &hotspots,
+        &geometry.plate_ids,
+        &dynamics.euler_poles,
+        tuning.plume_strength,
+        radius_km,
         width,
         height,
     );
-    let hotspots = generate_hotspots(seed, N_HOTSPOTS);
 
     let mut boundary_field = dynamics.boundary_field.clone();
     apply_continental_overriding(
@@ -109,8 +190,37 @@ pub fn simulate_plates(
         })
         .collect();
 
-    let all_boundary_distance = distance_to_seeds_km(width, height, &all_boundary_seeds);
-    let divergent_distance = distance_to_seeds_km(width, height, &divergent_seeds);
+    let convergent_seeds: Vec<usize> = boundary_field
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, character)| {
+            (dynamics.is_boundary[idx] && character.convergent_rate > CONVERGENT_THRESHOLD_CM_YR)
+                .then_some(idx)
+        })
+        .collect();
+
+    // Each Dijkstra is serial; the three fields are independent of each other.
+    let (all_boundary_distance, (divergent_distance, convergent_distance)) = parallel::join(
+        || {
+            progress::within(0.77, 0.81, || {
+                distance_to_seeds_km(width, height, radius_km, &all_boundary_seeds)
+            })
+        },
+        || {
+            parallel::join(
+                || {
+                    progress::within(0.81, 0.85, || {
+                        distance_to_seeds_km(width, height, radius_km, &divergent_seeds)
+                    })
+                },
+                || {
+                    progress::within(0.85, 0.89, || {
+                        distance_to_seeds_km(width, height, radius_km, &convergent_seeds)
+                    })
+                },
+            )
+        },
+    );
     let thermal_age = compute_thermal_age(
         &placement.continental_mask,
         &divergent_distance.distance_km,
@@ -120,34 +230,41 @@ pub fn simulate_plates(
         &boundary_field,
         &dynamics.is_boundary,
         &geometry.plate_ids,
+        radius_km,
         width,
         height,
     );
-    let convergent_seeds: Vec<usize> = boundary_field
-        .iter()
-        .enumerate()
-        .filter_map(|(idx, character)| {
-            (dynamics.is_boundary[idx] && character.convergent_rate > CONVERGENT_THRESHOLD_CM_YR)
-                .then_some(idx)
-        })
-        .collect();
-    let convergent_distance = distance_to_seeds_km(width, height, &convergent_seeds);
+    let triple_junctions = detect_triple_junctions(
+        &geometry.plate_ids,
+        &boundary_field,
+        &dynamics.is_boundary,
+        radius_km,
+        width,
+        height,
+    );
+    progress::report(0.90);
 
+    let back_arc_extension =
+        back_arc_extension_field(&geometry.plate_ids, &boundary_field, &convergent_distance);
     let regime_character = compute_regime_character(
         &dynamics,
         &placement.crust_field,
         &thermal_age,
-        &hotspots,
+        &hotspot_tracks,
         &convergent_distance,
         &divergent_distance,
+        &back_arc_extension,
+        radius_km,
         width,
     );
+    progress::report(0.95);
     let regime_field = discretize_regime_field(&regime_character, &placement.crust_field);
     let grain_field = grain_field::derive_grain_field(
         &regime_character,
         &regime_field,
         &dynamics,
         &hotspots,
+        radius_km,
         width,
         height,
     );
@@ -156,9 +273,11 @@ pub fn simulate_plates(
     PlateSimulation {
         plate_ids: geometry.plate_ids,
         n_plates: geometry.n_plates,
-        plate_velocities: dynamics.plate_velocities,
+        n_continents: placement.continents.len(),
+        euler_poles: dynamics.euler_poles,
         boundary_field,
         boundary_polylines,
+        triple_junctions,
         is_boundary: dynamics.is_boundary,
         continental_mask: placement.continental_mask,
         crust_field: placement.crust_field,
@@ -168,12 +287,25 @@ pub fn simulate_plates(
         grain_field,
         erodibility_field,
         hotspots,
+        hotspot_tracks,
         divergent_distance_km: divergent_distance.distance_km,
         width,
         height,
     }
 }
 
+impl PlateSimulation {
+    /// Boundary polylines joined through the triple junctions.
+    pub fn boundary_graph(&self, radius_km: f64) -> BoundaryGraph {
+        BoundaryGraph::build(
+            &self.boundary_polylines,
+            &self.triple_junctions,
+            radius_km,
+            self.width,
+        )
+    }
+}
+
 fn apply_continental_overriding(
     plate_ids: &[u8],
     continental_mask: &[bool],
@@ -263,6 +395,22 @@ mod tests {
         assert_eq!(sim.n_plates, plate_count_from_fragmentation(0.5));
     }
 
+    /// The continent count is what placement grew, not what the
+    /// fragmentation asked for.
+    #[test]
+    fn continent_count_follows_placement() {
+        let sim = run(42, 0.5, 0.5, 128, 64);
+        assert_eq!(sim.n_continents, continent_count_from_fragmentation(0.5));
+
+        let tuning = PlateTuning {
+            continental_coverage: 0.0,
+            ..PlateTuning::default()
+        };
+        let sim = simulate_plates_with(42, 0.5, 0.5, &tuning, &PlanetBody::EARTH, 128, 64);
+        assert_eq!(sim.n_continents, 0);
+        assert!(!sim.continental_mask.contains(&true));
+    }
+
     #[test]
     fn all_pixels_assigned_to_plate() {
         let sim = run(42, 0.5, 0.5, 64, 32);
@@ -4,11 +4,11 @@
 //! for diagnostic evaluation. It is intentionally not wired into the main plate
 //! simulation pipeline yet.
 
+use crate::parallel;
 use crate::plates::age_field::cell_to_vec3;
+use crate::seed::{self, StableRng};
 use crate::sphere::{great_circle_distance_rad, Vec3};
 use noise::{NoiseFn, Perlin};
-use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
 use std::collections::VecDeque;
 
 const MIN_PLATES: usize = 7;
@@ -25,7 +25,6 @@ const CURL_OCTAVES: usize = 1;
 const CURL_OCTAVE_FALLOFF: f64 = 0.5;
 const CURL_DIFF_STEP_DEG: f64 = 3.0;
 const CURL_MAGNITUDE_NORMALIZER: f64 = 1.5;
-const CURL_SEED_SALT: u32 = 0xC011_AA77;
 const HANGING_CHAD_PASSES: usize = 3;
 pub const DEFAULT_PLATE_WARP_AMPLITUDE_DEG: f64 = 7.0;
 
@@ -139,7 +138,7 @@ pub fn plate_generation_parameters() -> PlateGenerationParameters {
     }
 }
 
-fn grid_points(width: usize, height: usize) -> Vec<Vec3> {
+pub(crate) fn grid_points(width: usize, height: usize) -> Vec<Vec3> {
     let mut points = Vec::with_capacity(width * height);
     for r in 0..height {
         for c in 0..width {
@@ -156,7 +155,7 @@ fn generate_raw_plate_geometry_with_weights(
     height: usize,
 ) -> (PlateGeometry, Vec<f64>) {
     let n_plates = n_plates.clamp(MIN_PLATES, MAX_PLATES);
-    let mut rng = StdRng::seed_from_u64(seed ^ 0x50A7_E6E0_134C_0B91);
+    let mut rng = StableRng::for_stage(seed, "plates/seed-points", &[]);
     let points = grid_points(width, height);
     let mut seed_points = generate_uniform_seed_points(n_plates, &mut rng);
     let mut weights = generate_seed_weights(n_plates, &mut rng);
@@ -184,7 +183,11 @@ fn generate_raw_plate_geometry_with_weights(
     (geometry, weights)
 }
 
-fn compute_plate_centroids(points: &[Vec3], plate_ids: &[u8], n_plates: usize) -> Vec<Vec3> {
+pub(crate) fn compute_plate_centroids(
+    points: &[Vec3],
+    plate_ids: &[u8],
+    n_plates: usize,
+) -> Vec<Vec3> {
     let mut members = vec![Vec::new(); n_plates];
     for (idx, &plate_id) in plate_ids.iter().enumerate() {
         members[usize::from(plate_id)].push(idx);
@@ -198,7 +201,7 @@ fn compute_plate_centroids(points: &[Vec3], plate_ids: &[u8], n_plates: usize) -
         .collect()
 }
 
-fn generate_uniform_seed_points(n_plates: usize, rng: &mut StdRng) -> Vec<Vec3> {
+fn generate_uniform_seed_points(n_plates: usize, rng: &mut StableRng) -> Vec<Vec3> {
     let mut seed_points = Vec::with_capacity(n_plates);
     let min_separation_rad = MIN_SEED_SEPARATION_DEG.to_radians();
     while seed_points.len() < n_plates {
@@ -213,10 +216,10 @@ fn generate_uniform_seed_points(n_plates: usize, rng: &mut StdRng) -> Vec<Vec3>
     seed_points
 }
 
-fn generate_seed_weights(n_plates: usize, rng: &mut StdRng) -> Vec<f64> {
+fn generate_seed_weights(n_plates: usize, rng: &mut StableRng) -> Vec<f64> {
     let mut weights = Vec::with_capacity(n_plates);
     for _ in 0..n_plates {
-        let z = sample_standard_normal(rng);
+        let z = rng.standard_normal();
         let weight = (WEIGHT_MU + WEIGHT_SIGMA * z)
             .exp()
             .clamp(MIN_WEIGHT, MAX_WEIGHT);
@@ -334,19 +337,13 @@ fn plate_centroid(points: &[Vec3], indices: &[usize]) -> Option<Vec3> {
     Some(sum.normalize())
 }
 
-fn random_sphere_point(rng: &mut StdRng) -> Vec3 {
-    let z = rng.gen_range(-1.0_f64..=1.0_f64);
-    let theta = rng.gen_range(0.0_f64..std::f64::consts::TAU);
+fn random_sphere_point(rng: &mut StableRng) -> Vec3 {
+    let z = rng.range_f64(-1.0, 1.0);
+    let theta = rng.range_f64(0.0, std::f64::consts::TAU);
     let radius = (1.0_f64 - z * z).max(0.0).sqrt();
     Vec3::new(radius * theta.cos(), radius * theta.sin(), z)
 }
 
-fn sample_standard_normal(rng: &mut StdRng) -> f64 {
-    let u1 = rng.gen_range(f64::MIN_POSITIVE..1.0_f64);
-    let u2 = rng.gen_range(0.0_f64..1.0_f64);
-    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
-}
-
 fn plate_counts(plate_ids: &[u8], n_plates: usize) -> Vec<usize> {
     let mut counts = vec![0usize; n_plates];
     for &plate_id in plate_ids {
@@ -377,15 +374,11 @@ fn assign_warped_points_to_seeds(
     seed: u64,
     warp_amplitude_rad: f64,
 ) -> Vec<u8> {
-    let perlin = Perlin::new((seed ^ u64::from(CURL_SEED_SALT)) as u32);
-    let mut plate_ids = vec![0u8; points.len()];
-
-    for (idx, &point) in points.iter().enumerate() {
-        let warped_point = warp_point(point, &perlin, warp_amplitude_rad);
-        plate_ids[idx] = nearest_weighted_seed_id(warped_point, seed_points, weights);
-    }
-
-    plate_ids
+    let perlin = Perlin::new(seed::derive_u32(seed, "plates/curl-warp", &[]));
+    parallel::map_range(points.len(), |idx| {
+        let warped_point = warp_point(points[idx], &perlin, warp_amplitude_rad);
+        nearest_weighted_seed_id(warped_point, seed_points, weights)
+    })
 }
 
 fn warp_point(point: Vec3, perlin: &Perlin, warp_amplitude_rad: f64) -> Vec3 {
@@ -748,41 +741,75 @@ mod tests {
 
     #[test]
     fn size_distribution_is_plate_like() {
-        let geometry = generate_plate_geometry(15, 42, TEST_WARP_DEG, TEST_WIDTH, TEST_HEIGHT);
-        let mut areas = vec![0.0_f64; geometry.n_plates];
-        for row in 0..geometry.height {
-            let point = cell_to_vec3(row, 0, geometry.width, geometry.height);
-            let row_weight = (point.x * point.x + point.y * point.y).sqrt();
-            for col in 0..geometry.width {
-                let idx = row * geometry.width + col;
-                areas[usize::from(geometry.plate_ids[idx])] += row_weight;
+        // Plate sizes are seed-dependent: over seeds 0..64 the largest plate
+        // spans 0.12-0.26 of the sphere and one seed in thirty has no plate
+        // below 0.02.  Hold the spread over a fixed seed set instead.  Over
+        // 0..64 the medians are largest 0.18 (16-seed windows 0.17-0.19,
+        // Earth's Pacific ~0.20), smallest 0.008 and ratio 21 (windows
+        // 17-25), with the lowest single ratio 6.2.
+        let mut largest_fractions = Vec::new();
+        let mut smallest_fractions = Vec::new();
+        let mut size_ratios = Vec::new();
+        for seed in std::iter::once(42).chain(1..16) {
+            let geometry =
+                generate_plate_geometry(15, seed, TEST_WARP_DEG, TEST_WIDTH, TEST_HEIGHT);
+            let mut areas = vec![0.0_f64; geometry.n_plates];
+            let mut cells = vec![0usize; geometry.n_plates];
+            for row in 0..geometry.height {
+                let point = cell_to_vec3(row, 0, geometry.width, geometry.height);
+                let row_weight = (point.x * point.x + point.y * point.y).sqrt();
+                for col in 0..geometry.width {
+                    let plate = usize::from(geometry.plate_ids[row * geometry.width + col]);
+                    areas[plate] += row_weight;
+                    cells[plate] += 1;
+                }
             }
+            let total = areas.iter().sum::<f64>();
+            let largest_fraction =
+                areas.iter().copied().reduce(f64::max).unwrap_or(0.0) / total.max(1e-9);
+            let smallest_fraction =
+                areas.iter().copied().reduce(f64::min).unwrap_or(0.0) / total.max(1e-9);
+            // The repair floor counts cells, not area.
+            let smallest_cell_fraction =
+                cells.iter().copied().min().unwrap_or(0) as f64 / geometry.plate_ids.len() as f64;
+            println!(
+                "seed={seed}: largest={largest_fraction:.3} smallest={smallest_fraction:.4} \
+                 smallest_cells={smallest_cell_fraction:.4}"
+            );
+            assert!(
+                smallest_cell_fraction >= 0.005,
+                "seed={seed}: smallest plate cell fraction {smallest_cell_fraction:.4} below repair floor"
+            );
+            largest_fractions.push(largest_fraction);
+            smallest_fractions.push(smallest_fraction);
+            size_ratios.push(largest_fraction / smallest_fraction.max(1e-9));
         }
-        let total = areas.iter().sum::<f64>();
-        let largest_fraction =
-            areas.iter().copied().reduce(f64::max).unwrap_or(0.0) / total.max(1e-9);
-        let smallest_fraction =
-            areas.iter().copied().reduce(f64::min).unwrap_or(0.0) / total.max(1e-9);
-        let size_ratio = largest_fraction / smallest_fraction.max(1e-9);
+        largest_fractions.sort_by(f64::total_cmp);
+        smallest_fractions.sort_by(f64::total_cmp);
+        size_ratios.sort_by(f64::total_cmp);
+        let median_largest = largest_fractions[largest_fractions.len() / 2];
+        let median_smallest = smallest_fractions[smallest_fractions.len() / 2];
+        let median_ratio = size_ratios[size_ratios.len() / 2];
+        println!(
+            "median largest={median_largest:.3} ratio={median_ratio:.2} min ratio={:.2}",
+            size_ratios[0]
+        );
         assert!(
-            largest_fraction >= 0.18,
-            "largest plate fraction {:.3} below plate-like spread target",
-            largest_fraction
+            median_largest >= 0.16,
+            "median largest plate fraction {median_largest:.3} below plate-like spread target"
         );
         assert!(
-            smallest_fraction <= 0.02,
-            "smallest plate fraction {:.3} above target",
-            smallest_fraction
+            median_smallest <= 0.015,
+            "median smallest plate fraction {median_smallest:.4} above target"
         );
         assert!(
-            smallest_fraction >= 0.005,
-            "smallest plate fraction {:.3} below repair floor",
-            smallest_fraction
+            median_ratio >= 12.0,
+            "median largest/smallest ratio {median_ratio:.2} below plate-like target"
         );
         assert!(
-            size_ratio >= 8.0,
-            "largest/smallest ratio {:.2} below plate-like target",
-            size_ratio
+            size_ratios[0] >= 5.0,
+            "largest/smallest ratio {:.2} below plate-like floor",
+            size_ratios[0]
         );
     }
 
@@ -3,9 +3,12 @@
 //! Note: ridge/arc proximity classification was removed in the plate system rebuild.
 //! See git history before commit `eb343e4` for the previous implementation.
 
+use crate::parallel;
 use crate::plates::age_field::{cell_to_vec3, distance_to_seeds_km, DistanceField};
 use crate::plates::continents::CrustType;
+use crate::plates::hotspot_tracks::HotspotTrack;
 use crate::plates::plate_dynamics::{BoundaryCharacter, PlateDynamics};
+use crate::seed::StableRng;
 use crate::sphere::Vec3;
 use serde::{Deserialize, Serialize};
 
@@ -57,37 +60,41 @@ pub struct RegimeCharacterField {
 }
 
 const HOTSPOT_RADIUS_KM: f64 = 300.0;
+/// Reach of each older seamount along a hotspot track; about the seamount
+/// spacing, so a track reads as one continuous chain.
+const SEAMOUNT_RADIUS_KM: f64 = 150.0;
 const BOUNDARY_INFLUENCE_RADIUS_KM: f32 = 500.0;
 const RATE_REFERENCE_CM_YR: f32 = 8.0;
 const MIN_ACTIVE_INFLUENCE: f32 = 0.02;
 const MIN_CRATONIC_STABILITY: f32 = 0.05;
 
 pub fn generate_hotspots(seed: u64, n: usize) -> Vec<Vec3> {
-    use rand::rngs::StdRng;
-    use rand::{Rng, SeedableRng};
-
-    let mut rng = StdRng::seed_from_u64(seed ^ 0x1234_5678_9ABC_DEF0);
+    let mut rng = StableRng::for_stage(seed, "plates/hotspots", &[]);
     (0..n)
         .map(|_| {
-            let z: f64 = rng.gen_range(-1.0_f64..=1.0_f64);
-            let theta: f64 = rng.gen_range(0.0_f64..std::f64::consts::TAU);
+            let z = rng.range_f64(-1.0, 1.0);
+            let theta = rng.range_f64(0.0, std::f64::consts::TAU);
             let r = (1.0_f64 - z * z).max(0.0_f64).sqrt();
             Vec3::new(r * theta.cos(), r * theta.sin(), z)
         })
         .collect()
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn compute_regime_character(
     dynamics: &PlateDynamics,
     crust_field: &[CrustType],
     thermal_age: &[f32],
-    hotspots: &[Vec3],
+    hotspot_tracks: &[HotspotTrack],
     convergent_distance: &DistanceField,
     divergent_distance: &DistanceField,
+    back_arc_extension: &[f32],
+    radius_km: f64,
     width: usize,
 ) -> RegimeCharacterField {
     let height = crust_field.len() / width;
     let n = width * height;
+    let min_dot = (HOTSPOT_RADIUS_KM / radius_km).cos();
     let transform_seeds: Vec<usize> = dynamics
         .boundary_field
         .iter()
@@ -98,23 +105,36 @@ pub fn compute_regime_character(
             .then_some(idx)
         })
         .collect();
-    let transform_distance = distance_to_seeds_km(width, height, &transform_seeds);
-
-    let mut hotspot_influence = vec![0.0_f32; n];
-    for row in 0..height {
-        for col in 0..width {
-            let idx = row * width + col;
-            let point = cell_to_vec3(row, col, width, height);
-            let mut best = 0.0_f32;
-            for &hotspot in hotspots {
-                let distance_km = point.dot(hotspot).clamp(-1.0, 1.0).acos() * 6371.0_f64;
-                let influence =
-                    (1.0 - distance_km as f32 / HOTSPOT_RADIUS_KM as f32).clamp(0.0, 1.0);
-                best = best.max(influence);
-            }
-            hotspot_influence[idx] = best;
-        }
-    }
+    // The transform-distance Dijkstra is serial; overlap it with the
+    // per-cell hotspot-track scan.
+    let (transform_distance, hotspot_influence) = parallel::join(
+        || distance_to_seeds_km(width, height, radius_km, &transform_seeds),
+        || {
+            let mut influence = vec![0.0_f32; n];
+            parallel::for_each_row(&mut influence, width, |row, cells| {
+                for (col, cell) in cells.iter_mut().enumerate() {
+                    let point = cell_to_vec3(row, col, width, height);
+                    let mut best = 0.0_f32;
+                    for seamount in hotspot_tracks.iter().flat_map(|t| &t.seamounts) {
+                        let dot = point.dot(seamount.position);
+                        if dot < min_dot {
+                            continue;
+                        }
+                        let distance_km = dot.clamp(-1.0, 1.0).acos() * radius_km;
+                        let reach_km = if seamount.age_myr > 0.0 {
+                            SEAMOUNT_RADIUS_KM
+                        } else {
+                            HOTSPOT_RADIUS_KM
+                        };
+                        let influence = (1.0 - distance_km / reach_km).clamp(0.0, 1.0) as f32;
+                        best = best.max(influence);
+                    }
+                    *cell = best;
+                }
+            });
+            influence
+        },
+    );
 
     let convergent_influence = influence_from_distance_field(
         &convergent_distance.distance_km,
@@ -124,14 +144,19 @@ pub fn compute_regime_character(
         width * height,
         true,
     );
-    let divergent_influence = influence_from_distance_field(
+    // Back-arc basins spread like ridges.
+    let divergent_influence: Vec<f32> = influence_from_distance_field(
         &divergent_distance.distance_km,
         &divergent_distance.nearest_source,
         &dynamics.boundary_field,
         BOUNDARY_INFLUENCE_RADIUS_KM,
         width * height,
         false,
-    );
+    )
+    .into_iter()
+    .zip(back_arc_extension)
+    .map(|(influence, &back_arc)| influence.max(back_arc))
+    .collect();
     let transform_influence: Vec<f32> = transform_distance
         .distance_km
         .iter()
@@ -180,22 +205,20 @@ fn influence_from_distance_field(
     n: usize,
     convergent: bool,
 ) -> Vec<f32> {
-    (0..n)
-        .map(|idx| {
-            let source = nearest_source[idx];
-            if source == usize::MAX {
-                return 0.0;
-            }
-            let rate = boundary_field[source].convergent_rate;
-            let rate_mag = if convergent {
-                rate.max(0.0)
-            } else {
-                (-rate).max(0.0)
-            };
-            let rate_scale = (rate_mag / RATE_REFERENCE_CM_YR).clamp(0.0, 1.0);
-            (1.0 - distances_km[idx] / radius_km).clamp(0.0, 1.0) * rate_scale
-        })
-        .collect()
+    parallel::map_range(n, |idx| {
+        let source = nearest_source[idx];
+        if source == usize::MAX {
+            return 0.0;
+        }
+        let rate = boundary_field[source].convergent_rate;
+        let rate_mag = if convergent {
+            rate.max(0.0)
+        } else {
+            (-rate).max(0.0)
+        };
+        let rate_scale = (rate_mag / RATE_REFERENCE_CM_YR).clamp(0.0, 1.0);
+        (1.0 - distances_km[idx] / radius_km).clamp(0.0, 1.0) * rate_scale
+    })
 }
 
 pub fn discretize_regime_field(
@@ -0,0 +1,214 @@
+//! Cross-section of a subduction zone.
+//!
+//! Distances are measured across strike from the plate boundary, positive
+//! toward the overriding plate.  Seaward of the boundary the subducting
+//! plate bends down into a trench; landward follow the accretionary wedge,
+//! the forearc, the volcanic arc and, where convergence is slow enough for
+//! the slab to roll back, a spreading back-arc basin.  The overview
+//! elevation draws the whole profile; the regime field marks the back-arc
+//! basins as extensional.  Both take the side of a cell from
+//! [`overriding_side`], so the basin is cut and marked on the same plate.
+
+use crate::parallel;
+use crate::plates::age_field::DistanceField;
+use crate::plates::plate_dynamics::BoundaryCharacter;
+
+/// Trench axis, seaward of the plate boundary.
+const TRENCH_AXIS_KM: f64 = -30.0;
+const TRENCH_SIGMA_KM: f64 = 40.0;
+/// Trench depth below the undisturbed seafloor at slow and fast
+/// convergence.  On a 5 km deep abyssal plain this puts the trench floor at
+/// 8–11 km.
+const MIN_TRENCH_DEPTH_KM: f32 = 3.0;
+const MAX_TRENCH_DEPTH_KM: f32 = 6.0;
+const REFERENCE_RATE_CM_YR: f32 = 8.0;
+/// Convergence over which the trench and back-arc basin grow in from
+/// nothing, so the profile stays continuous where the rate passes through
+/// zero along strike.
+const ONSET_RATE_CM_YR: f32 = 1.0;
+/// Crest of the accretionary wedge, the outer forearc high.
+const WEDGE_CREST_KM: f64 = 70.0;
+const WEDGE_SIGMA_KM: f64 = 35.0;
+const MAX_WEDGE_UPLIFT_KM: f32 = 1.5;
+/// Trench-to-volcanic-front distance; arc–trench gaps on Earth cluster
+/// around 150–200 km.
+const ARC_TRENCH_GAP_KM: f64 = 170.0;
+/// Convergence below which the slab rolls back and the overriding plate
+/// extends behind the arc.
+const BACK_ARC_MAX_RATE_CM_YR: f32 = 2.5;
+const BACK_ARC_CENTRE_KM: f64 = 400.0;
+const BACK_ARC_SIGMA_KM: f64 = 120.0;
+const MAX_BACK_ARC_DEPTH_KM: f32 = 2.0;
+
+/// The subduction-zone profile at one point.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CrossSection {
+    /// Depth of the trench below the undisturbed seafloor (km).
+    pub trench_depth_km: f32,
+    /// Height of the accretionary wedge (km).
+    pub wedge_uplift_km: f32,
+    /// Distance from the volcanic front (km).
+    pub arc_distance_km: f64,
+    /// Strength of back-arc spreading in [0, 1].
+    pub back_arc_extension: f32,
+    /// Subsidence of the back-arc basin (km).
+    pub back_arc_depth_km: f32,
+}
+
+/// The profile at `signed_distance_km` from a convergent boundary closing
+/// at `convergent_rate_cm_yr`.
+pub fn cross_section(signed_distance_km: f64, convergent_rate_cm_yr: f32) -> CrossSection {
+    let rate_scale = (convergent_rate_cm_yr.max(0.0) / REFERENCE_RATE_CM_YR)
+        .sqrt()
+        .min(1.0);
+    let onset = smoothstep(convergent_rate_cm_yr / ONSET_RATE_CM_YR);
+    let trench_depth_km = onset
+        * (MIN_TRENCH_DEPTH_KM + (MAX_TRENCH_DEPTH_KM - MIN_TRENCH_DEPTH_KM) * rate_scale)
+        * gaussian(signed_distance_km - TRENCH_AXIS_KM, TRENCH_SIGMA_KM);
+    let wedge_uplift_km = MAX_WEDGE_UPLIFT_KM
+        * rate_scale
+        * gaussian(signed_distance_km - WEDGE_CREST_KM, WEDGE_SIGMA_KM);
+    let back_arc_extension = onset
+        * (1.0 - convergent_rate_cm_yr / BACK_ARC_MAX_RATE_CM_YR).clamp(0.0, 1.0)
+        * gaussian(signed_distance_km - BACK_ARC_CENTRE_KM, BACK_ARC_SIGMA_KM);
+    CrossSection {
+        trench_depth_km,
+        wedge_uplift_km,
+        arc_distance_km: (signed_distance_km - TRENCH_AXIS_KM - ARC_TRENCH_GAP_KM).abs(),
+        back_arc_extension,
+        back_arc_depth_km: MAX_BACK_ARC_DEPTH_KM * back_arc_extension,
+    }
+}
+
+/// +1 when cell `idx` belongs to the `overriding_plate` of its nearest
+/// convergent boundary pixel in `convergent_distance`, -1 otherwise.
+pub fn overriding_side(
+    idx: usize,
+    plate_ids: &[u8],
+    boundary_field: &[BoundaryCharacter],
+    convergent_distance: &DistanceField,
+) -> f64 {
+    let source = convergent_distance.nearest_source[idx];
+    if source != usize::MAX && plate_ids[idx] == boundary_field[source].overriding_plate {
+        1.0
+    } else {
+        -1.0
+    }
+}
+
+/// Back-arc extension of every cell, measured from its nearest convergent
+/// boundary pixel in `convergent_distance`, on the side given by
+/// [`overriding_side`].
+pub fn back_arc_extension_field(
+    plate_ids: &[u8],
+    boundary_field: &[BoundaryCharacter],
+    convergent_distance: &DistanceField,
+) -> Vec<f32> {
+    parallel::map_range(plate_ids.len(), |idx| {
+        let source = convergent_distance.nearest_source[idx];
+        if source == usize::MAX {
+            return 0.0;
+        }
+        let side = overriding_side(idx, plate_ids, boundary_field, convergent_distance);
+        let distance_km = f64::from(convergent_distance.distance_km[idx]);
+        cross_section(side * distance_km, boundary_field[source].convergent_rate).back_arc_extension
+    })
+}
+
+fn smoothstep(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn gaussian(offset_km: f64, sigma_km: f64) -> f32 {
+    (-0.5 * (offset_km / sigma_km).powi(2)).exp() as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plates::age_field::distance_to_seeds_km;
+    use crate::sphere::EARTH_RADIUS_KM;
+
+    fn profile(rate: f32) -> Vec<(f64, CrossSection)> {
+        (-300..=800)
+            .step_by(5)
+            .map(|s| (f64::from(s), cross_section(f64::from(s), rate)))
+            .collect()
+    }
+
+    fn argmax(profile: &[(f64, CrossSection)], key: impl Fn(&CrossSection) -> f32) -> f64 {
+        profile
+            .iter()
+            .max_by(|a, b| key(&a.1).total_cmp(&key(&b.1)))
+            .unwrap()
+            .0
+    }
+
+    #[test]
+    fn profile_runs_trench_wedge_arc_back_arc() {
+        let slow = profile(1.5);
+        let trench = argmax(&slow, |c| c.trench_depth_km);
+        let wedge = argmax(&slow, |c| c.wedge_uplift_km);
+        let arc = argmax(&slow, |c| -c.arc_distance_km as f32);
+        let back_arc = argmax(&slow, |c| c.back_arc_extension);
+        assert!(trench < 0.0, "trench at {trench} km");
+        assert!(trench < wedge && wedge < arc && arc < back_arc);
+        assert!(
+            (150.0..=220.0).contains(&(arc - trench)),
+            "gap {}",
+            arc - trench
+        );
+    }
+
+    #[test]
+    fn trench_deepens_with_convergence_rate() {
+        let axis = |rate| cross_section(TRENCH_AXIS_KM, rate).trench_depth_km;
+        assert!(axis(1.0) >= MIN_TRENCH_DEPTH_KM);
+        assert!(axis(1.0) < axis(4.0) && axis(4.0) < axis(10.0));
+        assert_eq!(axis(12.0), MAX_TRENCH_DEPTH_KM);
+        assert_eq!(axis(0.0), 0.0);
+        // No cliff where convergence starts.
+        assert!(axis(0.01) < 0.01 && axis(0.5) < axis(1.0));
+        assert!(cross_section(-400.0, 10.0).trench_depth_km < 0.01);
+    }
+
+    #[test]
+    fn back_arc_spreads_only_behind_slow_subduction() {
+        let centre = |rate| cross_section(BACK_ARC_CENTRE_KM, rate).back_arc_extension;
+        assert!(centre(1.0) > 0.5);
+        assert!(centre(2.0) < centre(1.0));
+        assert_eq!(centre(BACK_ARC_MAX_RATE_CM_YR), 0.0);
+        assert_eq!(centre(8.0), 0.0);
+        assert!(centre(0.01) < 0.01 && centre(0.01) < centre(0.5));
+        assert!(cross_section(-BACK_ARC_CENTRE_KM, 1.0).back_arc_extension < 1e-6);
+    }
+
+    #[test]
+    fn back_arc_field_follows_the_overriding_plate() {
+        // Plate 1 (north) overrides plate 0 along the equator.
+        let (width, height) = (64, 256);
+        let plate_ids = (0..width * height)
+            .map(|idx| u8::from(idx / width < height / 2))
+            .collect::<Vec<_>>();
+        let mut boundary_field = vec![BoundaryCharacter::default(); plate_ids.len()];
+        let mut seeds = Vec::new();
+        for row in [height / 2 - 1, height / 2] {
+            for col in 0..width {
+                let idx = row * width + col;
+                boundary_field[idx].convergent_rate = 1.0;
+                boundary_field[idx].overriding_plate = 1;
+                seeds.push(idx);
+            }
+        }
+        let distance = distance_to_seeds_km(width, height, EARTH_RADIUS_KM, &seeds);
+        let extension = back_arc_extension_field(&plate_ids, &boundary_field, &distance);
+        let row_km = std::f64::consts::PI * EARTH_RADIUS_KM / height as f64;
+        let rows_back = (BACK_ARC_CENTRE_KM / row_km).round() as usize;
+        let north = (height / 2 - 1 - rows_back) * width;
+        let south = (height / 2 + rows_back) * width;
+        assert!(extension[north] > 0.4, "{}", extension[north]);
+        assert!(extension[south] < 1e-6);
+        assert!(extension[(height / 2 - 1) * width] < 0.01);
+    }
+}
@@ -0,0 +1,352 @@
+//! Triple junctions: points where three plates meet.
+//!
+//! A junction is classified by its three boundaries, each a ridge (R), trench
+//! (T) or transform fault (F).  The types are read from the boundary
+//! character next to the junction rather than from each polyline's dominant
+//! type, since Euler-pole motion changes a boundary's character along strike.
+
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::plates::age_field::cell_to_vec3;
+use crate::plates::boundary_curves::{boundary_type_for_rate, BoundaryType};
+use crate::plates::plate_dynamics::BoundaryCharacter;
+use crate::sphere::{equatorial_cell_km, great_circle_distance_rad, Vec3};
+
+/// Boundary pixels within this distance of a junction decide its class.
+const JUNCTION_SAMPLE_RADIUS_KM: f64 = 300.0;
+/// Floor on the sample radius in equatorial cells, so coarse grids still
+/// reach all three boundaries.
+const MIN_SAMPLE_RADIUS_CELLS: f64 = 2.5;
+
+/// A point where three plates meet.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TripleJunction {
+    /// The three plates in ascending order.
+    pub plates: [u8; 3],
+    /// `boundaries[i]` separates the two plates other than `plates[i]`.
+    pub boundaries: [BoundaryType; 3],
+    /// Junction position on the unit sphere.
+    pub position: Vec3,
+    /// Grid cell nearest the junction.
+    pub cell: usize,
+}
+
+impl TripleJunction {
+    pub fn class(&self) -> JunctionClass {
+        JunctionClass::of(self.boundaries)
+    }
+}
+
+/// Junction class by the number of ridges, trenches and transform faults
+/// meeting at it; displays as e.g. `RRR` or `RTF`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct JunctionClass {
+    pub ridges: u8,
+    pub trenches: u8,
+    pub faults: u8,
+}
+
+impl JunctionClass {
+    /// Three spreading ridges, as at Afar or the Rodrigues junction.
+    pub const RRR: Self = Self {
+        ridges: 3,
+        trenches: 0,
+        faults: 0,
+    };
+
+    pub fn of(boundaries: [BoundaryType; 3]) -> Self {
+        let count = |kind| boundaries.iter().filter(|&&b| b == kind).count() as u8;
+        Self {
+            ridges: count(BoundaryType::Divergent),
+            trenches: count(BoundaryType::Convergent),
+            faults: count(BoundaryType::Transform),
+        }
+    }
+}
+
+impl fmt::Display for JunctionClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (letter, n) in [('R', self.ridges), ('T', self.trenches), ('F', self.faults)] {
+            for _ in 0..n {
+                write!(f, "{letter}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Find every point where three plates meet and classify it.
+///
+/// Cells whose 3 × 3 neighbourhood spans three or more plates are grouped
+/// into connected clusters, one per junction; where four plates crowd
+/// together the three most frequent win.  A cluster only counts if all
+/// three plate pairs have boundary pixels within the sample radius.
+pub fn detect_triple_junctions(
+    plate_ids: &[u8],
+    boundary_field: &[BoundaryCharacter],
+    is_boundary: &[bool],
+    radius_km: f64,
+    width: usize,
+    height: usize,
+) -> Vec<TripleJunction> {
+    let is_candidate = (0..plate_ids.len())
+        .map(|idx| window_plates(plate_ids, idx, width, height).len() >= 3)
+        .collect::<Vec<_>>();
+    let boundary_cells = (0..plate_ids.len())
+        .filter(|&idx| is_boundary[idx])
+        .map(|idx| {
+            let pair = ordered_pair(plate_ids[idx], boundary_field[idx].neighbor_plate);
+            (idx, pair, point_for_idx(idx, width, height))
+        })
+        .collect::<Vec<_>>();
+    let sample_radius_rad = JUNCTION_SAMPLE_RADIUS_KM
+        .max(MIN_SAMPLE_RADIUS_CELLS * equatorial_cell_km(width, radius_km))
+        / radius_km;
+
+    let mut junctions = Vec::new();
+    let mut visited = vec![false; plate_ids.len()];
+    for start in 0..plate_ids.len() {
+        if visited[start] || !is_candidate[start] {
+            continue;
+        }
+        let cluster = flood_cluster(&is_candidate, &mut visited, start, width, height);
+
+        let mut counts = [0usize; 256];
+        for &idx in &cluster {
+            for plate in window_plates(plate_ids, idx, width, height) {
+                counts[usize::from(plate)] += 1;
+            }
+        }
+        let mut ranked = (0..=u8::MAX)
+            .filter(|&plate| counts[usize::from(plate)] > 0)
+            .collect::<Vec<_>>();
+        ranked.sort_by_key(|&plate| std::cmp::Reverse(counts[usize::from(plate)]));
+        let mut plates = [ranked[0], ranked[1], ranked[2]];
+        plates.sort_unstable();
+
+        let position = mean_position(&cluster, width, height);
+        let cell = *cluster
+            .iter()
+            .max_by(|&&a, &&b| {
+                let da = point_for_idx(a, width, height).dot(position);
+                let db = point_for_idx(b, width, height).dot(position);
+                da.total_cmp(&db)
+            })
+            .expect("clusters are non-empty");
+
+        let mut boundaries = [BoundaryType::Transform; 3];
+        let mut complete = true;
+        for (i, boundary) in boundaries.iter_mut().enumerate() {
+            let pair = ordered_pair(plates[(i + 1) % 3], plates[(i + 2) % 3]);
+            let rates = boundary_cells
+                .iter()
+                .filter(|(_, p, point)| {
+                    *p == pair && great_circle_distance_rad(*point, position) <= sample_radius_rad
+                })
+                .map(|&(idx, _, _)| boundary_field[idx].convergent_rate)
+                .collect::<Vec<_>>();
+            if rates.is_empty() {
+                complete = false;
+                break;
+            }
+            *boundary = boundary_type_for_rate(rates.iter().sum::<f32>() / rates.len() as f32);
+        }
+        if complete {
+            junctions.push(TripleJunction {
+                plates,
+                boundaries,
+                position,
+                cell,
+            });
+        }
+    }
+
+    junctions
+}
+
+/// Distinct plates in the 3 × 3 neighbourhood of `idx`, wrapping in
+/// longitude.
+fn window_plates(plate_ids: &[u8], idx: usize, width: usize, height: usize) -> Vec<u8> {
+    let row = idx / width;
+    let col = idx % width;
+    let mut plates = Vec::with_capacity(4);
+    for dr in -1isize..=1 {
+        let rr = row as isize + dr;
+        if rr < 0 || rr >= height as isize {
+            continue;
+        }
+        for dc in -1isize..=1 {
+            let cc = (col as isize + dc).rem_euclid(width as isize) as usize;
+            let plate = plate_ids[rr as usize * width + cc];
+            if !plates.contains(&plate) {
+                plates.push(plate);
+            }
+        }
+    }
+    plates
+}
+
+fn flood_cluster(
+    is_candidate: &[bool],
+    visited: &mut [bool],
+    start: usize,
+    width: usize,
+    height: usize,
+) -> Vec<usize> {
+    let mut cluster = Vec::new();
+    let mut queue = VecDeque::from([start]);
+    visited[start] = true;
+    while let Some(idx) = queue.pop_front() {
+        cluster.push(idx);
+        let row = idx / width;
+        let col = idx % width;
+        for dr in -1isize..=1 {
+            let rr = row as isize + dr;
+            if rr < 0 || rr >= height as isize {
+                continue;
+            }
+            for dc in -1isize..=1 {
+                let cc = (col as isize + dc).rem_euclid(width as isize) as usize;
+                let neighbor = rr as usize * width + cc;
+                if !visited[neighbor] && is_candidate[neighbor] {
+                    visited[neighbor] = true;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+    cluster
+}
+
+fn mean_position(cells: &[usize], width: usize, height: usize) -> Vec3 {
+    let mut sum = Vec3::new(0.0, 0.0, 0.0);
+    for &idx in cells {
+        let point = point_for_idx(idx, width, height);
+        sum.x += point.x;
+        sum.y += point.y;
+        sum.z += point.z;
+    }
+    if sum.length() <= 1e-9 {
+        point_for_idx(cells[0], width, height)
+    } else {
+        sum.normalize()
+    }
+}
+
+fn point_for_idx(idx: usize, width: usize, height: usize) -> Vec3 {
+    cell_to_vec3(idx / width, idx % width, width, height)
+}
+
+fn ordered_pair(a: u8, b: u8) -> (u8, u8) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plates::simulate_plates;
+    use crate::sphere::EARTH_RADIUS_KM;
+
+    const WIDTH: usize = 64;
+    const HEIGHT: usize = 32;
+
+    /// Plates 0 and 1 split the northern hemisphere at the prime meridian
+    /// and the antimeridian; plate 2 is the southern hemisphere.  0–1 is a
+    /// ridge and both are subducted under plate 2.
+    fn three_plate_world() -> (Vec<u8>, Vec<BoundaryCharacter>, Vec<bool>) {
+        let plate_ids = (0..WIDTH * HEIGHT)
+            .map(
+                |idx| match (idx / WIDTH < HEIGHT / 2, idx % WIDTH < WIDTH / 2) {
+                    (true, true) => 0,
+                    (true, false) => 1,
+                    (false, _) => 2,
+                },
+            )
+            .collect::<Vec<u8>>();
+        let mut boundary_field = vec![BoundaryCharacter::default(); plate_ids.len()];
+        let mut is_boundary = vec![false; plate_ids.len()];
+        for idx in 0..plate_ids.len() {
+            let own = plate_ids[idx];
+            let Some(&other) = window_plates(&plate_ids, idx, WIDTH, HEIGHT)
+                .iter()
+                .find(|&&p| p != own)
+            else {
+                continue;
+            };
+            is_boundary[idx] = true;
+            boundary_field[idx].neighbor_plate = other;
+            boundary_field[idx].convergent_rate = match ordered_pair(own, other) {
+                (0, 1) => -4.0,
+                _ => 4.0,
+            };
+        }
+        (plate_ids, boundary_field, is_boundary)
+    }
+
+    #[test]
+    fn finds_and_classifies_synthetic_junctions() {
+        let (plate_ids, boundary_field, is_boundary) = three_plate_world();
+        let junctions = detect_triple_junctions(
+            &plate_ids,
+            &boundary_field,
+            &is_boundary,
+            EARTH_RADIUS_KM,
+            WIDTH,
+            HEIGHT,
+        );
+        assert_eq!(junctions.len(), 2);
+        let mut longitudes = Vec::new();
+        for junction in &junctions {
+            assert_eq!(junction.plates, [0, 1, 2]);
+            assert_eq!(
+                junction.boundaries,
+                [
+                    BoundaryType::Convergent,
+                    BoundaryType::Convergent,
+                    BoundaryType::Divergent
+                ]
+            );
+            assert_eq!(junction.class().to_string(), "RTT");
+            let (lat, lon) = junction.position.to_latlon();
+            assert!(lat.abs() < 6.0, "junction at latitude {lat}");
+            longitudes.push(lon.abs().round());
+        }
+        longitudes.sort_by(f64::total_cmp);
+        assert!(
+            longitudes[0] < 6.0 && longitudes[1] > 174.0,
+            "{longitudes:?}"
+        );
+    }
+
+    #[test]
+    fn class_codes_list_ridges_then_trenches_then_faults() {
+        use BoundaryType::*;
+        assert_eq!(JunctionClass::of([Divergent; 3]), JunctionClass::RRR);
+        assert_eq!(
+            JunctionClass::of([Transform, Convergent, Divergent]).to_string(),
+            "RTF"
+        );
+        assert_eq!(JunctionClass::of([Convergent; 3]).to_string(), "TTT");
+    }
+
+    #[test]
+    fn simulated_planets_have_junctions_between_real_boundaries() {
+        let plates = simulate_plates(42, 0.5, 0.5, 128, 64);
+        assert!(
+            plates.triple_junctions.len() >= plates.n_plates / 2,
+            "{} junctions for {} plates",
+            plates.triple_junctions.len(),
+            plates.n_plates
+        );
+        for junction in &plates.triple_junctions {
+            assert!(junction.plates[0] < junction.plates[1]);
+            assert!(junction.plates[1] < junction.plates[2]);
+            assert!(junction.plates.contains(&plates.plate_ids[junction.cell]));
+        }
+    }
+}
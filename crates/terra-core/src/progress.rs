@@ -0,0 +1,426 @@
+//! Progress reporting and cancellation for long-running generation.
+//!
+//! A `RunControl` carries an optional `ProgressObserver` and a `CancelToken`
+//! into one pipeline run (`PlanetGenerator::try_generate`,
+//! `try_generate_planet_overview`, `OverviewPipeline::try_update`).
+//!
+//! Pipelines announce each stage with `stage`, which is also where the token
+//! is checked; a cancelled run stops before the next stage and returns
+//! `Err(Cancelled)`, surfaced as `TerraError::Cancelled`.  Long loops deep
+//! inside a stage (stream-power iterations, distance fields, continent
+//! growth) call `report` with their own 0–1 fraction; `within` maps a
+//! sub-step's fractions onto part of the enclosing range.  The active run
+//! lives in a thread-local, so these calls need no extra parameters and are
+//! no-ops outside a run.
+//!
+//! `stage` also timestamps each stage; `run` hands the stage wall times
+//! back for the run's `PipelineTrace`.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use serde::Serialize;
+use web_time::Instant;
+
+use crate::trace::StageTiming;
+
+/// Minimum advance of the stage fraction between two `report` events.
+const REPORT_STEP: f32 = 0.01;
+
+/// One progress notification.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ProgressEvent {
+    /// Name of the running stage, e.g. `"plates"` or `"hydraulic"`.
+    pub stage: &'static str,
+    /// Zero-based position of the stage in the run.
+    pub stage_index: usize,
+    /// Number of stages in the run.
+    pub stage_count: usize,
+    /// Progress through the current stage, 0–1.
+    pub stage_fraction: f32,
+    /// Progress through the whole run, 0–1, weighted by typical stage cost.
+    pub overall: f32,
+}
+
+/// Receives `ProgressEvent`s; closures taking `&ProgressEvent` implement it.
+pub trait ProgressObserver {
+    fn on_progress(&mut self, event: &ProgressEvent);
+}
+
+impl<F: FnMut(&ProgressEvent)> ProgressObserver for F {
+    fn on_progress(&mut self, event: &ProgressEvent) {
+        self(event)
+    }
+}
+
+/// Shared flag that asks a running pipeline to stop at the next stage.
+///
+/// Clones share the flag, so a UI can keep one clone and cancel from another
+/// thread (or from a callback) while the run holds the other.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Returned by a pipeline run that stopped because its token was cancelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("generation cancelled")]
+pub struct Cancelled;
+
+/// Observer and cancel token for pipeline runs.
+#[derive(Default)]
+pub struct RunControl {
+    observer: Option<Box<dyn ProgressObserver>>,
+    cancel: CancelToken,
+}
+
+impl RunControl {
+    /// No observer and a token nobody else holds: the run cannot be cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_observer(mut self, observer: impl ProgressObserver + 'static) -> Self {
+        self.observer = Some(Box::new(observer));
+        self
+    }
+
+    pub fn with_cancel_token(mut self, cancel: CancelToken) -> Self {
+        self.cancel = cancel;
+        self
+    }
+
+    pub fn cancel_token(&self) -> &CancelToken {
+        &self.cancel
+    }
+}
+
+// ── Active run (thread-local) ─────────────────────────────────────────────────
+
+struct ActiveRun {
+    observer: Option<Box<dyn ProgressObserver>>,
+    cancel: CancelToken,
+    /// Stage names and their share of `overall` (sums to 1).
+    stages: Vec<(&'static str, f32)>,
+    current: usize,
+    /// Part of the current stage that `report` fractions map onto.
+    span: (f32, f32),
+    last_fraction: f32,
+    /// Name and start of the stage being timed.
+    timing: Option<(&'static str, Instant)>,
+    timings: Vec<StageTiming>,
+}
+
+impl ActiveRun {
+    fn end_stage_timing(&mut self) {
+        if let Some((stage, start)) = self.timing.take() {
+            self.timings.push(StageTiming {
+                stage: stage.to_owned(),
+                wall_ms: start.elapsed().as_secs_f64() * 1000.0,
+            });
+        }
+    }
+
+    /// The event for `stage_fraction` of the current stage, if anyone is
+    /// listening; hand it to `emit` once the `ACTIVE` borrow is released.
+    fn event(&self, stage_fraction: f32) -> Option<ProgressEvent> {
+        self.observer.as_ref()?;
+        let &(stage, weight) = self.stages.get(self.current)?;
+        let done: f32 = self.stages[..self.current].iter().map(|s| s.1).sum();
+        Some(ProgressEvent {
+            stage,
+            stage_index: self.current,
+            stage_count: self.stages.len(),
+            stage_fraction,
+            overall: (done + weight * stage_fraction).min(1.0),
+        })
+    }
+}
+
+thread_local! {
+    static ACTIVE: RefCell<Option<ActiveRun>> = const { RefCell::new(None) };
+}
+
+/// Puts the observer back into the active run after `emit`, including when
+/// the observer panics.
+struct ObserverGuard(Option<Box<dyn ProgressObserver>>);
+
+impl Drop for ObserverGuard {
+    fn drop(&mut self) {
+        let observer = self.0.take();
+        with_active(|run| run.observer = observer);
+    }
+}
+
+/// Hand `event` to the active run's observer.
+///
+/// The observer is taken out of the run for the call, so it may itself use
+/// terra-core (even start a nested run) without re-entering `ACTIVE`.
+fn emit(event: Option<ProgressEvent>) {
+    let Some(event) = event else {
+        return;
+    };
+    let observer = with_active(|run| run.observer.take()).flatten();
+    let mut guard = ObserverGuard(observer);
+    if let Some(observer) = guard.0.as_mut() {
+        observer.on_progress(&event);
+    }
+}
+
+/// Restores the previously active run (and hands the observer back) on drop,
+/// including when the run panics.
+struct RunGuard<'a> {
+    control: &'a mut RunControl,
+    previous: Option<ActiveRun>,
+}
+
+impl Drop for RunGuard<'_> {
+    fn drop(&mut self) {
+        let finished = ACTIVE.with(|a| a.replace(self.previous.take()));
+        if let Some(run) = finished {
+            self.control.observer = run.observer;
+        }
+    }
+}
+
+/// Execute `f` as a run of `stages` (name, relative cost) under `control`.
+///
+/// Returns `f`'s result with the wall time of every stage it entered.
+pub(crate) fn run<T>(
+    control: &mut RunControl,
+    stages: &[(&'static str, f32)],
+    f: impl FnOnce() -> Result<T, Cancelled>,
+) -> Result<(T, Vec<StageTiming>), Cancelled> {
+    if control.cancel.is_cancelled() {
+        return Err(Cancelled);
+    }
+    let total: f32 = stages.iter().map(|s| s.1).sum();
+    let stages = stages
+        .iter()
+        .map(|&(name, w)| (name, if total > 0.0 { w / total } else { 0.0 }))
+        .collect();
+    let active = ActiveRun {
+        observer: control.observer.take(),
+        cancel: control.cancel.clone(),
+        stages,
+        current: 0,
+        span: (0.0, 1.0),
+        last_fraction: 0.0,
+        timing: None,
+        timings: Vec::new(),
+    };
+    let previous = ACTIVE.with(|a| a.replace(Some(active)));
+    let _guard = RunGuard { control, previous };
+
+    let result = f()?;
+    let mut timings = Vec::new();
+    let event = with_active(|run| {
+        run.end_stage_timing();
+        timings = std::mem::take(&mut run.timings);
+        if run.stages.is_empty() {
+            return None;
+        }
+        run.current = run.stages.len() - 1;
+        run.event(1.0)
+    });
+    emit(event.flatten());
+    Ok((result, timings))
+}
+
+/// Apply `f` to the active run, if any.  `f` must not call back into the
+/// observer; build events with `ActiveRun::event` and `emit` them afterwards.
+fn with_active<R>(f: impl FnOnce(&mut ActiveRun) -> R) -> Option<R> {
+    ACTIVE.with(|a| a.borrow_mut().as_mut().map(f))
+}
+
+/// Enter stage `name`, or stop with `Cancelled` if the run's token is set.
+pub(crate) fn stage(name: &'static str) -> Result<(), Cancelled> {
+    let entered = with_active(|run| {
+        if run.cancel.is_cancelled() {
+            return Err(Cancelled);
+        }
+        if let Some(idx) = run.stages.iter().position(|s| s.0 == name) {
+            run.current = idx;
+        }
+        run.span = (0.0, 1.0);
+        run.last_fraction = 0.0;
+        run.end_stage_timing();
+        run.timing = Some((name, Instant::now()));
+        Ok(run.event(0.0))
+    });
+    emit(entered.transpose()?.flatten());
+    Ok(())
+}
+
+/// Report `fraction` (0–1) of the current step done.
+///
+/// Events are throttled to `REPORT_STEP` increments, so calling this once per
+/// loop iteration is fine.
+pub(crate) fn report(fraction: f32) {
+    let event = with_active(|run| {
+        let (lo, hi) = run.span;
+        let stage_fraction = lo + fraction.clamp(0.0, 1.0) * (hi - lo);
+        if stage_fraction - run.last_fraction < REPORT_STEP {
+            return None;
+        }
+        run.last_fraction = stage_fraction;
+        run.event(stage_fraction)
+    });
+    emit(event.flatten());
+}
+
+/// Run `f` with its `report` fractions mapped onto `[start, end]` of the
+/// current range.
+pub(crate) fn within<T>(start: f32, end: f32, f: impl FnOnce() -> T) -> T {
+    let mut outer = None;
+    with_active(|run| {
+        let (lo, hi) = run.span;
+        outer = Some(run.span);
+        run.span = (lo + start * (hi - lo), lo + end * (hi - lo));
+    });
+    let result = f();
+    if let Some(span) = outer {
+        with_active(|run| run.span = span);
+        report(1.0);
+    }
+    result
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    const STAGES: &[(&str, f32)] = &[("a", 1.0), ("b", 3.0)];
+
+    fn recording_control() -> (RunControl, Rc<RefCell<Vec<ProgressEvent>>>) {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&events);
+        let control = RunControl::new().with_observer(move |e: &ProgressEvent| {
+            sink.borrow_mut().push(*e);
+        });
+        (control, events)
+    }
+
+    #[test]
+    fn overall_progress_is_weighted_and_monotonic() {
+        let (mut control, events) = recording_control();
+        run(&mut control, STAGES, || {
+            stage("a")?;
+            stage("b")?;
+            within(0.0, 0.5, || {
+                for i in 1..=10 {
+                    report(i as f32 / 10.0);
+                }
+            });
+            Ok(())
+        })
+        .unwrap();
+
+        let events = events.borrow();
+        assert_eq!(events[0].stage, "a");
+        assert_eq!(events[1].overall, 0.25);
+        assert!(events.windows(2).all(|w| w[1].overall >= w[0].overall));
+        let halfway = events.iter().find(|e| e.stage_fraction == 0.5).unwrap();
+        assert_eq!(halfway.overall, 0.25 + 0.75 * 0.5);
+        assert_eq!(events.last().unwrap().overall, 1.0);
+    }
+
+    #[test]
+    fn cancelled_token_stops_at_next_stage() {
+        let token = CancelToken::new();
+        let mut control = RunControl::new().with_cancel_token(token.clone());
+        let mut reached_b = false;
+        let result = run(&mut control, STAGES, || {
+            stage("a")?;
+            token.cancel();
+            stage("b")?;
+            reached_b = true;
+            Ok(())
+        });
+        assert_eq!(result, Err(Cancelled));
+        assert!(!reached_b);
+    }
+
+    #[test]
+    fn stages_are_timed_in_order() {
+        let (_, timings) = run(&mut RunControl::new(), STAGES, || {
+            stage("a")?;
+            stage("b")?;
+            Ok(())
+        })
+        .unwrap();
+        let names: Vec<&str> = timings.iter().map(|t| t.stage.as_str()).collect();
+        assert_eq!(names, ["a", "b"]);
+        assert!(timings.iter().all(|t| t.wall_ms >= 0.0));
+    }
+
+    #[test]
+    fn calls_outside_a_run_are_no_ops() {
+        assert_eq!(stage("a"), Ok(()));
+        report(0.5);
+        assert_eq!(within(0.0, 1.0, || 7), 7);
+    }
+
+    /// An observer may start its own run (e.g. a preview generation)
+    /// without tripping over the outer run's thread-local state.
+    #[test]
+    fn observer_can_start_a_nested_run() {
+        let inner_events = Rc::new(RefCell::new(0));
+        let counter = Rc::clone(&inner_events);
+        let outer_events = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&outer_events);
+        let mut control = RunControl::new().with_observer(move |e: &ProgressEvent| {
+            sink.borrow_mut().push(*e);
+            let counter = Rc::clone(&counter);
+            let mut nested = RunControl::new().with_observer(move |_: &ProgressEvent| {
+                *counter.borrow_mut() += 1;
+            });
+            run(&mut nested, STAGES, || {
+                stage("a")?;
+                report(0.5);
+                stage("b")
+            })
+            .unwrap();
+        });
+
+        run(&mut control, STAGES, || {
+            stage("a")?;
+            report(0.5);
+            stage("b")
+        })
+        .unwrap();
+
+        let stages: Vec<&str> = outer_events.borrow().iter().map(|e| e.stage).collect();
+        assert_eq!(stages, ["a", "a", "b", "b"]);
+        assert_eq!(*inner_events.borrow(), 4 * stages.len());
+
+        // The observer is back in the control for the next run.
+        run(&mut control, STAGES, || stage("a")).unwrap();
+        assert_eq!(outer_events.borrow().len(), stages.len() + 2);
+    }
+
+    #[test]
+    fn observer_is_returned_after_run() {
+        let (mut control, events) = recording_control();
+        run(&mut control, STAGES, || stage("a")).unwrap();
+        let first = events.borrow().len();
+        run(&mut control, STAGES, || stage("a")).unwrap();
+        assert_eq!(events.borrow().len(), 2 * first);
+    }
+}
@@ -0,0 +1,393 @@
+//! Named parameter presets and the declarative planet recipe format.
+//!
+//! A recipe is a TOML or JSON table that optionally names a base preset and
+//! overrides any subset of `GlobalParams`, including the [`Tuning`] knobs:
+//!
+//! ```toml
+//! name = "Dry supercontinent"
+//! description = "Pangaea with half the water"
+//! preset = "pangaea"
+//! seed = 7
+//! water_abundance = 0.3
+//!
+//! [tuning.plates]
+//! hotspots = 6
+//!
+//! [tuning.hydraulic.alpine]
+//! erosion_iters = 45
+//! ```
+//!
+//! Omitted keys keep the preset's values (Earth-like when no preset is
+//! named).  Unknown keys are rejected, and the merged params must pass
+//! [`GlobalParams::validate`].  A plain params file without `name`, `preset`
+//! or `tuning` is a valid recipe.
+//!
+//! [`Tuning`]: crate::generator::Tuning
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::body::{PlanetBody, Rotation};
+use crate::generator::{GlobalParams, RangeError};
+
+/// Error returned when reading or writing a recipe.
+#[derive(Debug, thiserror::Error)]
+pub enum RecipeError {
+    #[error("could not parse recipe: {0}")]
+    Parse(String),
+    #[error("expected a table of parameters")]
+    NotATable,
+    #[error("`{key}` must be a string")]
+    NotAString { key: &'static str },
+    #[error("unknown preset `{0}`")]
+    UnknownPreset(String),
+    #[error("unknown parameter `{0}`")]
+    UnknownKey(String),
+    #[error("invalid parameter value: {0}")]
+    InvalidValue(serde_json::Error),
+    #[error(transparent)]
+    Range(#[from] RangeError),
+    #[error("could not write recipe: {0}")]
+    Serialise(String),
+}
+
+/// Built-in starting points for recipes and the slider UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    /// The calibrated defaults.
+    EarthLike,
+    /// One supercontinent in a world ocean.
+    Pangaea,
+    /// Many small, hotspot-fed islands in a deep ocean.
+    Archipelago,
+    /// Old, dry, low-relief continents with little water to carve them.
+    AridMars,
+    /// Ice from pole to tropics.
+    Snowball,
+}
+
+impl Preset {
+    pub const ALL: [Preset; 5] = [
+        Preset::EarthLike,
+        Preset::Pangaea,
+        Preset::Archipelago,
+        Preset::AridMars,
+        Preset::Snowball,
+    ];
+
+    /// Key used for the preset in recipe files, e.g. `arid-mars`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Preset::EarthLike => "earth-like",
+            Preset::Pangaea => "pangaea",
+            Preset::Archipelago => "archipelago",
+            Preset::AridMars => "arid-mars",
+            Preset::Snowball => "snowball",
+        }
+    }
+
+    /// Human-readable title for menus.
+    pub fn title(self) -> &'static str {
+        match self {
+            Preset::EarthLike => "Earth-like",
+            Preset::Pangaea => "Pangaea",
+            Preset::Archipelago => "Archipelago world",
+            Preset::AridMars => "Arid Mars-analog",
+            Preset::Snowball => "Snowball",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Result<Self, RecipeError> {
+        Preset::ALL
+            .into_iter()
+            .find(|p| p.name() == name)
+            .ok_or_else(|| RecipeError::UnknownPreset(name.to_owned()))
+    }
+
+    /// Parameters of the preset, with the default seed.
+    pub fn params(self) -> GlobalParams {
+        let mut p = GlobalParams::default();
+        match self {
+            Preset::EarthLike => {}
+            Preset::Pangaea => {
+                p.continental_fragmentation = 0.0;
+                p.water_abundance = 0.60;
+                p.climate_diversity = 0.70;
+                p.tectonic_activity = 0.40;
+                p.tuning.plates.continental_coverage = 0.35;
+            }
+            Preset::Archipelago => {
+                p.continental_fragmentation = 1.0;
+                p.water_abundance = 0.85;
+                p.tectonic_activity = 0.70;
+                p.glaciation = 0.10;
+                p.tuning.plates.continental_coverage = 0.15;
+                p.tuning.plates.hotspots = 12;
+                p.tuning.plates.plume_strength = 1.5;
+            }
+            Preset::AridMars => {
+                p.water_abundance = 0.10;
+                p.surface_age = 0.85;
+                p.tectonic_activity = 0.15;
+                p.climate_diversity = 0.30;
+                p.glaciation = 0.20;
+                p.mountain_prevalence = 0.35;
+                p.tuning.plates.continental_coverage = 0.60;
+                p.tuning.plates.hotspots = 2;
+                p.body = PlanetBody {
+                    radius_km: 3389.5,
+                    axial_tilt_deg: 25.19,
+                    rotation: Rotation::Prograde,
+                    surface_gravity_m_s2: 3.71,
+                };
+                // Wind-dominated landscapes: sparse channels, little incision.
+                for hp in [
+                    &mut p.tuning.hydraulic.fluvial_arid,
+                    &mut p.tuning.hydraulic.cratonic,
+                ] {
+                    hp.a_min *= 2;
+                    hp.erosion_iters /= 2;
+                }
+            }
+            Preset::Snowball => {
+                p.glaciation = 1.0;
+                p.water_abundance = 0.60;
+                p.climate_diversity = 0.20;
+                p.surface_age = 0.40;
+            }
+        }
+        p
+    }
+}
+
+/// Parses a preset by its [`name`](Preset::name), e.g. for command-line flags.
+impl std::str::FromStr for Preset {
+    type Err = RecipeError;
+
+    fn from_str(name: &str) -> Result<Self, RecipeError> {
+        Preset::from_name(name)
+    }
+}
+
+/// A named, validated set of parameters.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Recipe {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Preset the recipe was based on, kept for reference when written back.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<Preset>,
+    #[serde(flatten)]
+    pub params: GlobalParams,
+}
+
+impl Recipe {
+    pub fn from_preset(preset: Preset) -> Self {
+        Self {
+            name: Some(preset.title().to_owned()),
+            description: None,
+            preset: Some(preset),
+            params: preset.params(),
+        }
+    }
+
+    pub fn from_toml(text: &str) -> Result<Self, RecipeError> {
+        let value: toml::Value =
+            toml::from_str(text).map_err(|e| RecipeError::Parse(e.to_string()))?;
+        let value = serde_json::to_value(value).map_err(|e| RecipeError::Parse(e.to_string()))?;
+        Self::from_value(value)
+    }
+
+    pub fn from_json(text: &str) -> Result<Self, RecipeError> {
+        let value = serde_json::from_str(text).map_err(|e| RecipeError::Parse(e.to_string()))?;
+        Self::from_value(value)
+    }
+
+    /// Resolve a parsed recipe table against its preset and validate it.
+    pub fn from_value(value: Value) -> Result<Self, RecipeError> {
+        let Value::Object(mut table) = value else {
+            return Err(RecipeError::NotATable);
+        };
+        let name = take_string(&mut table, "name")?;
+        let description = take_string(&mut table, "description")?;
+        let preset = take_string(&mut table, "preset")?
+            .map(|name| Preset::from_name(&name))
+            .transpose()?;
+
+        let base = preset.map_or_else(GlobalParams::default, Preset::params);
+        let mut merged = serde_json::to_value(base).expect("GlobalParams serialises");
+        merge(&mut merged, table, "")?;
+        let params: GlobalParams =
+            serde_json::from_value(merged).map_err(RecipeError::InvalidValue)?;
+        params.validate()?;
+
+        Ok(Self {
+            name,
+            description,
+            preset,
+            params,
+        })
+    }
+
+    /// The recipe with every parameter spelled out, as TOML.
+    pub fn to_toml(&self) -> Result<String, RecipeError> {
+        // The toml serialiser widens f32 to f64 (`0.15000000596046448`);
+        // serde_json writes the shortest f32 text, so go through it.
+        // Seeds above `i64::MAX` have no TOML representation and fail here.
+        let json = serde_json::to_string(self).expect("Recipe serialises to JSON");
+        let value: toml::Value =
+            serde_json::from_str(&json).map_err(|e| RecipeError::Serialise(e.to_string()))?;
+        toml::to_string(&value).map_err(|e| RecipeError::Serialise(e.to_string()))
+    }
+
+    /// The recipe with every parameter spelled out, as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, RecipeError> {
+        serde_json::to_string_pretty(self).map_err(|e| RecipeError::Serialise(e.to_string()))
+    }
+}
+
+fn take_string(
+    table: &mut Map<String, Value>,
+    key: &'static str,
+) -> Result<Option<String>, RecipeError> {
+    match table.remove(key) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s)),
+        Some(_) => Err(RecipeError::NotAString { key }),
+    }
+}
+
+/// Overwrite `base` with `overrides`, descending into nested tables so a
+/// recipe can set one field of a knob group.  `path` is the dotted key of
+/// `base`, used to name unknown keys.
+fn merge(base: &mut Value, overrides: Map<String, Value>, path: &str) -> Result<(), RecipeError> {
+    let fields = base.as_object_mut().expect("only tables are merged into");
+    for (key, value) in overrides {
+        let key_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        let Some(slot) = fields.get_mut(&key) else {
+            return Err(RecipeError::UnknownKey(key_path));
+        };
+        match value {
+            Value::Object(inner) if slot.is_object() => merge(slot, inner, &key_path)?,
+            value => *slot = value,
+        }
+    }
+    Ok(())
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise::params::TerrainClass;
+
+    #[test]
+    fn presets_are_valid_and_distinct() {
+        for preset in Preset::ALL {
+            preset.params().validate().unwrap();
+            assert_eq!(Preset::from_name(preset.name()).unwrap(), preset);
+            assert_eq!(preset.name().parse::<Preset>().unwrap(), preset);
+        }
+        assert_eq!(Preset::EarthLike.params(), GlobalParams::default());
+        for (i, a) in Preset::ALL.iter().enumerate() {
+            for b in &Preset::ALL[i + 1..] {
+                assert_ne!(a.params(), b.params(), "{a:?} and {b:?} are identical");
+            }
+        }
+    }
+
+    #[test]
+    fn partial_recipe_overrides_preset() {
+        let recipe = Recipe::from_toml(
+            r#"
+            name = "Dry supercontinent"
+            preset = "pangaea"
+            seed = 7
+            water_abundance = 0.3
+
+            [tuning.plates]
+            hotspots = 6
+
+            [tuning.hydraulic.alpine]
+            erosion_iters = 45
+            "#,
+        )
+        .unwrap();
+        let pangaea = Preset::Pangaea.params();
+        let p = &recipe.params;
+        assert_eq!(recipe.name.as_deref(), Some("Dry supercontinent"));
+        assert_eq!(recipe.preset, Some(Preset::Pangaea));
+        assert_eq!(p.seed, 7);
+        assert_eq!(p.water_abundance, 0.3);
+        assert_eq!(
+            p.continental_fragmentation,
+            pangaea.continental_fragmentation
+        );
+        assert_eq!(p.tuning.plates.hotspots, 6);
+        assert_eq!(
+            p.tuning.plates.continental_coverage,
+            pangaea.tuning.plates.continental_coverage
+        );
+        let alpine = p.tuning.hydraulic.for_class(TerrainClass::Alpine);
+        assert_eq!(alpine.erosion_iters, 45);
+        assert_eq!(alpine.a_min, pangaea.tuning.hydraulic.alpine.a_min);
+    }
+
+    #[test]
+    fn toml_and_json_round_trip() {
+        let mut recipe = Recipe::from_preset(Preset::AridMars);
+        recipe.description = Some("test".into());
+        recipe.params.tuning.smoothing.climate_sigma_km = 900.0;
+        assert_eq!(
+            Recipe::from_toml(&recipe.to_toml().unwrap()).unwrap(),
+            recipe
+        );
+        assert_eq!(
+            Recipe::from_json(&recipe.to_json().unwrap()).unwrap(),
+            recipe
+        );
+
+        recipe.params.seed = u64::MAX;
+        assert!(recipe.to_toml().is_err());
+        assert_eq!(
+            Recipe::from_json(&recipe.to_json().unwrap()).unwrap(),
+            recipe
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_out_of_range_values() {
+        let err = Recipe::from_json(r#"{ "sead": 7 }"#).unwrap_err();
+        assert!(matches!(err, RecipeError::UnknownKey(k) if k == "sead"));
+
+        let err = Recipe::from_toml("[tuning.hydraulic.alpine]\nerosion_iter = 3\n").unwrap_err();
+        assert!(
+            matches!(&err, RecipeError::UnknownKey(k) if k == "tuning.hydraulic.alpine.erosion_iter"),
+            "{err}"
+        );
+
+        let err = Recipe::from_toml("preset = \"venus\"\n").unwrap_err();
+        assert!(matches!(err, RecipeError::UnknownPreset(_)));
+
+        let err = Recipe::from_toml("[tuning.plates]\ncontinental_coverage = 1.2\n").unwrap_err();
+        let RecipeError::Range(range) = err else {
+            panic!("expected a range error, got {err}");
+        };
+        assert_eq!(range.name, "tuning.plates.continental_coverage");
+        assert_eq!(
+            range.to_string(),
+            "tuning.plates.continental_coverage must be in [0.05, 0.8], got 1.2"
+        );
+
+        let err = Recipe::from_json(r#"{ "glaciation": "lots" }"#).unwrap_err();
+        assert!(matches!(err, RecipeError::InvalidValue(_)));
+    }
+}
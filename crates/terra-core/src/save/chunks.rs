@@ -0,0 +1,180 @@
+//! Chunk framing and compression for save files.
+//!
+//! A save file is the 8-byte magic `TERRASAV`, the format version as a
+//! little-endian `u32`, then a sequence of chunks:
+//!
+//! | Field   | Bytes | Meaning                              |
+//! |---------|-------|--------------------------------------|
+//! | tag     | 4     | ASCII chunk name, e.g. `PLAT`        |
+//! | codec   | 1     | 0 = stored, 1 = deflate              |
+//! | raw_len | 4     | payload length before compression    |
+//! | len     | 4     | payload length in the file           |
+//! | crc32   | 4     | CRC-32 of the uncompressed payload   |
+//! | payload | len   |                                      |
+//!
+//! The file ends with an empty `END ` chunk.  Each chunk is deflated on its
+//! own and kept stored when that does not make it smaller.  All integers are
+//! little-endian.
+
+use std::io::{Read, Write};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+use super::SaveError;
+
+const MAGIC: &[u8; 8] = b"TERRASAV";
+const END_TAG: Tag = *b"END ";
+const CODEC_STORED: u8 = 0;
+const CODEC_DEFLATE: u8 = 1;
+
+pub(super) type Tag = [u8; 4];
+
+/// Uncompressed chunk payloads in file order.
+#[derive(Debug, Default)]
+pub(super) struct Chunks {
+    entries: Vec<(Tag, Vec<u8>)>,
+}
+
+impl Chunks {
+    pub(super) fn push(&mut self, tag: Tag, payload: Vec<u8>) {
+        self.entries.push((tag, payload));
+    }
+
+    /// Swap the payload of `tag`, which must be present.
+    pub(super) fn replace(&mut self, tag: Tag, payload: Vec<u8>) {
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|(t, _)| *t == tag)
+            .expect("replaced chunk was read first");
+        entry.1 = payload;
+    }
+
+    pub(super) fn get(&self, tag: Tag) -> Result<&[u8], SaveError> {
+        self.entries
+            .iter()
+            .find(|(t, _)| *t == tag)
+            .map(|(_, payload)| payload.as_slice())
+            .ok_or_else(|| SaveError::MissingChunk(tag_name(tag)))
+    }
+}
+
+/// Printable chunk name for error messages.
+pub(super) fn tag_name(tag: Tag) -> String {
+    String::from_utf8_lossy(&tag).trim_end().to_owned()
+}
+
+/// Frame `chunks` as a complete save file of format `version`.
+pub(super) fn write(version: u32, chunks: &Chunks) -> Vec<u8> {
+    let mut out = Vec::new();
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&version.to_le_bytes());
+    for (tag, payload) in &chunks.entries {
+        write_chunk(&mut out, *tag, payload);
+    }
+    write_chunk(&mut out, END_TAG, &[]);
+    out
+}
+
+fn write_chunk(out: &mut Vec<u8>, tag: Tag, payload: &[u8]) {
+    // The float grids barely compress; the default level shrinks a 1024 × 512
+    // planet by another 7% at four times the cost.
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+    let deflated = encoder
+        .write_all(payload)
+        .and_then(|()| encoder.finish())
+        .expect("deflating into a Vec cannot fail");
+    let (codec, body) = if deflated.len() < payload.len() {
+        (CODEC_DEFLATE, deflated.as_slice())
+    } else {
+        (CODEC_STORED, payload)
+    };
+    out.extend_from_slice(&tag);
+    out.push(codec);
+    out.extend_from_slice(&chunk_len(payload.len()).to_le_bytes());
+    out.extend_from_slice(&chunk_len(body.len()).to_le_bytes());
+    out.extend_from_slice(&crc32fast::hash(payload).to_le_bytes());
+    out.extend_from_slice(body);
+}
+
+fn chunk_len(len: usize) -> u32 {
+    u32::try_from(len).expect("save chunks are limited to 4 GiB")
+}
+
+/// Split a save file into its format version and decompressed chunks.
+///
+/// Checks the magic, every chunk checksum and the `END ` terminator; the
+/// version is returned as written and left to the caller to interpret.
+pub(super) fn read(bytes: &[u8]) -> Result<(u32, Chunks), SaveError> {
+    let mut cursor = Cursor { bytes, pos: 0 };
+    if cursor.take(MAGIC.len()).ok() != Some(MAGIC.as_slice()) {
+        return Err(SaveError::NotASaveFile);
+    }
+    let version = cursor.u32()?;
+    let mut chunks = Chunks::default();
+    loop {
+        let tag: Tag = cursor.take(4)?.try_into().expect("took 4 bytes");
+        let codec = cursor.take(1)?[0];
+        let raw_len = cursor.u32()? as usize;
+        let len = cursor.u32()? as usize;
+        let crc = cursor.u32()?;
+        let body = cursor.take(len)?;
+        if tag == END_TAG {
+            return Ok((version, chunks));
+        }
+        let payload = match codec {
+            CODEC_STORED => body.to_vec(),
+            CODEC_DEFLATE => inflate(tag, body, raw_len)?,
+            other => {
+                return Err(SaveError::Malformed {
+                    chunk: tag_name(tag),
+                    reason: format!("unknown codec {other}"),
+                })
+            }
+        };
+        if payload.len() != raw_len || crc32fast::hash(&payload) != crc {
+            return Err(SaveError::Checksum(tag_name(tag)));
+        }
+        chunks.push(tag, payload);
+    }
+}
+
+fn inflate(tag: Tag, body: &[u8], raw_len: usize) -> Result<Vec<u8>, SaveError> {
+    // Read one byte past `raw_len` so an oversized stream fails the length
+    // check instead of being inflated in full.
+    let mut payload = Vec::new();
+    DeflateDecoder::new(body)
+        .take(raw_len as u64 + 1)
+        .read_to_end(&mut payload)
+        .map_err(|e| SaveError::Malformed {
+            chunk: tag_name(tag),
+            reason: e.to_string(),
+        })?;
+    Ok(payload)
+}
+
+struct Cursor<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], SaveError> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.bytes.len())
+            .ok_or(SaveError::Truncated)?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u32(&mut self) -> Result<u32, SaveError> {
+        Ok(u32::from_le_bytes(
+            self.take(4)?.try_into().expect("took 4 bytes"),
+        ))
+    }
+}
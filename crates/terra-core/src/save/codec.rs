@@ -0,0 +1,663 @@
+//! Binary encoding of the pipeline layers stored in save-file chunks.
+//!
+//! Payloads are flat little-endian sequences.  Floats are written bit for
+//! bit, so a loaded planet reproduces the saved fields exactly.  Every
+//! vector carries a `u64` length prefix; per-cell vectors must match the
+//! plate grid or the chunk is rejected.
+
+use crate::climate::ClimateLayer;
+use crate::noise::params::GlacialClass;
+use crate::planet::planet_metrics::{MetricResult, PlanetMetrics};
+use crate::planet::PlanetOverview;
+use crate::plates::boundary_curves::{BoundaryPolyline, BoundaryType, BoundaryVertex};
+use crate::plates::continents::CrustType;
+use crate::plates::grain_field::GrainField;
+use crate::plates::hotspot_tracks::{HotspotTrack, Seamount};
+use crate::plates::plate_dynamics::{BoundaryCharacter, EulerPole};
+use crate::plates::plate_generation::{compute_plate_centroids, grid_points};
+use crate::plates::regime_field::{RegimeCharacterField, RegimeField, TectonicRegime};
+use crate::plates::triple_junctions::TripleJunction;
+use crate::plates::PlateSimulation;
+use crate::sphere::Vec3;
+use crate::trace::PipelineTrace;
+
+use super::chunks::{tag_name, Tag};
+use super::SaveError;
+
+// ── Primitive writer / reader ─────────────────────────────────────────────────
+
+#[derive(Default)]
+pub(super) struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    pub(super) fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+
+    fn u8(&mut self, v: u8) {
+        self.buf.push(v);
+    }
+
+    fn u32(&mut self, v: u32) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn u64(&mut self, v: u64) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn f32(&mut self, v: f32) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn f64(&mut self, v: f64) {
+        self.buf.extend_from_slice(&v.to_le_bytes());
+    }
+
+    fn bool(&mut self, v: bool) {
+        self.u8(v as u8);
+    }
+
+    fn len(&mut self, n: usize) {
+        self.u64(n as u64);
+    }
+
+    fn str(&mut self, s: &str) {
+        self.len(s.len());
+        self.buf.extend_from_slice(s.as_bytes());
+    }
+
+    fn vec<T>(&mut self, items: &[T], mut item: impl FnMut(&mut Self, &T)) {
+        self.len(items.len());
+        for v in items {
+            item(self, v);
+        }
+    }
+
+    fn raw(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    fn f32s(&mut self, items: &[f32]) {
+        self.vec(items, |w, &v| w.f32(v));
+    }
+
+    fn bools(&mut self, items: &[bool]) {
+        self.vec(items, |w, &v| w.bool(v));
+    }
+}
+
+pub(super) struct Reader<'a> {
+    tag: Tag,
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub(super) fn new(tag: Tag, bytes: &'a [u8]) -> Self {
+        Self { tag, bytes, pos: 0 }
+    }
+
+    pub(super) fn malformed(&self, reason: impl Into<String>) -> SaveError {
+        SaveError::Malformed {
+            chunk: tag_name(self.tag),
+            reason: reason.into(),
+        }
+    }
+
+    /// Fail unless the whole payload was consumed.
+    pub(super) fn finish(self) -> Result<(), SaveError> {
+        if self.pos == self.bytes.len() {
+            Ok(())
+        } else {
+            Err(self.malformed(format!("{} trailing bytes", self.bytes.len() - self.pos)))
+        }
+    }
+
+    /// The unread rest of the payload.
+    fn rest(&mut self) -> &'a [u8] {
+        let rest = &self.bytes[self.pos..];
+        self.pos = self.bytes.len();
+        rest
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], SaveError> {
+        let bytes = self
+            .bytes
+            .get(self.pos..self.pos + N)
+            .ok_or_else(|| self.malformed("payload ends early"))?;
+        self.pos += N;
+        Ok(bytes.try_into().expect("slice of length N"))
+    }
+
+    fn u8(&mut self) -> Result<u8, SaveError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, SaveError> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64, SaveError> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Result<f32, SaveError> {
+        self.take().map(f32::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, SaveError> {
+        self.take().map(f64::from_le_bytes)
+    }
+
+    fn bool(&mut self) -> Result<bool, SaveError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            v => Err(self.malformed(format!("invalid bool {v}"))),
+        }
+    }
+
+    /// A length prefix, bounded by the bytes left so that a corrupt prefix
+    /// cannot trigger a huge allocation.
+    fn len(&mut self) -> Result<usize, SaveError> {
+        let n = self.u64()?;
+        usize::try_from(n)
+            .ok()
+            .filter(|&n| n <= self.bytes.len() - self.pos)
+            .ok_or_else(|| self.malformed(format!("length {n} exceeds payload")))
+    }
+
+    fn str(&mut self) -> Result<String, SaveError> {
+        let n = self.len()?;
+        let bytes = self.bytes[self.pos..self.pos + n].to_vec();
+        self.pos += n;
+        String::from_utf8(bytes).map_err(|e| self.malformed(e.to_string()))
+    }
+
+    fn vec<T>(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<T, SaveError>,
+    ) -> Result<Vec<T>, SaveError> {
+        let n = self.len()?;
+        (0..n).map(|_| item(self)).collect()
+    }
+
+    /// A per-cell vector, which must hold exactly `cells` items.
+    fn cells<T>(
+        &mut self,
+        cells: usize,
+        item: impl FnMut(&mut Self) -> Result<T, SaveError>,
+    ) -> Result<Vec<T>, SaveError> {
+        let items = self.vec(item)?;
+        if items.len() == cells {
+            Ok(items)
+        } else {
+            Err(self.malformed(format!("field has {} cells, expected {cells}", items.len())))
+        }
+    }
+
+    fn f32_cells(&mut self, cells: usize) -> Result<Vec<f32>, SaveError> {
+        self.cells(cells, Self::f32)
+    }
+
+    fn bool_cells(&mut self, cells: usize) -> Result<Vec<bool>, SaveError> {
+        self.cells(cells, Self::bool)
+    }
+
+    fn enum_u8<T>(&mut self, what: &str, decode: fn(u8) -> Option<T>) -> Result<T, SaveError> {
+        let v = self.u8()?;
+        decode(v).ok_or_else(|| self.malformed(format!("unknown {what} {v}")))
+    }
+}
+
+// ── Enum tags ─────────────────────────────────────────────────────────────────
+
+fn regime_from_u8(v: u8) -> Option<TectonicRegime> {
+    Some(match v {
+        0 => TectonicRegime::PassiveMargin,
+        1 => TectonicRegime::CratonicShield,
+        2 => TectonicRegime::ActiveCompressional,
+        3 => TectonicRegime::ActiveExtensional,
+        4 => TectonicRegime::VolcanicHotspot,
+        _ => return None,
+    })
+}
+
+fn glacial_from_u8(v: u8) -> Option<GlacialClass> {
+    Some(match v {
+        0 => GlacialClass::None,
+        1 => GlacialClass::Former,
+        2 => GlacialClass::Active,
+        _ => return None,
+    })
+}
+
+fn crust_from_u8(v: u8) -> Option<CrustType> {
+    Some(match v {
+        0 => CrustType::Oceanic,
+        1 => CrustType::Continental,
+        2 => CrustType::ActiveMargin,
+        3 => CrustType::PassiveMargin,
+        _ => return None,
+    })
+}
+
+fn boundary_type_from_u8(v: u8) -> Option<BoundaryType> {
+    Some(match v {
+        0 => BoundaryType::Convergent,
+        1 => BoundaryType::Divergent,
+        2 => BoundaryType::Transform,
+        _ => return None,
+    })
+}
+
+// ── Plate layer ───────────────────────────────────────────────────────────────
+
+/// `PLAT`: every plate-simulation field except the boundary polylines.
+pub(super) fn write_plates(w: &mut Writer, p: &PlateSimulation) {
+    w.len(p.width);
+    w.len(p.height);
+    w.len(p.n_plates);
+    w.vec(&p.plate_ids, |w, &id| w.u8(id));
+    w.vec(&p.euler_poles, write_euler_pole);
+    w.vec(&p.boundary_field, |w, c| {
+        w.f32(c.convergent_rate);
+        w.f32(c.transform_rate);
+        w.u8(c.neighbor_plate);
+        w.f32(c.tangent_east);
+        w.f32(c.tangent_north);
+        w.f32(c.normal_east);
+        w.f32(c.normal_north);
+        w.u8(c.overriding_plate);
+    });
+    w.bools(&p.is_boundary);
+    w.bools(&p.continental_mask);
+    w.vec(&p.crust_field, |w, &c| w.u8(c as u8));
+    w.f32s(&p.thermal_age);
+    let rc = &p.regime_character;
+    w.f32s(&rc.convergent_influence);
+    w.f32s(&rc.divergent_influence);
+    w.f32s(&rc.transform_influence);
+    w.f32s(&rc.hotspot_influence);
+    w.f32s(&rc.cratonic_stability);
+    w.vec(&p.regime_field.data, |w, &r| w.u8(r as u8));
+    w.f32s(&p.grain_field.angles);
+    w.f32s(&p.grain_field.intensities);
+    w.f32s(&p.erodibility_field);
+    w.vec(&p.hotspots, |w, h| {
+        w.f64(h.x);
+        w.f64(h.y);
+        w.f64(h.z);
+    });
+    w.f32s(&p.divergent_distance_km);
+    w.u32(p.n_continents as u32);
+}
+
+/// Inverse of `write_plates`; `boundary_polylines`, `triple_junctions` and
+/// `hotspot_tracks` are left empty.
+pub(super) fn read_plates(r: &mut Reader) -> Result<PlateSimulation, SaveError> {
+    let mut plates = read_plates_v4(r)?;
+    plates.n_continents = r.u32()? as usize;
+    Ok(plates)
+}
+
+/// `read_plates` for the `PLAT` layout of versions 2 to 4, which ends before
+/// the continent count; `n_continents` is left 0.
+pub(super) fn read_plates_v4(r: &mut Reader) -> Result<PlateSimulation, SaveError> {
+    let width = r.len()?;
+    let height = r.len()?;
+    let n = width
+        .checked_mul(height)
+        .ok_or_else(|| r.malformed("grid size overflows"))?;
+    let n_plates = r.len()?;
+    let plate_ids = r.cells(n, Reader::u8)?;
+    let euler_poles = r.vec(read_euler_pole)?;
+    if plate_ids.iter().any(|&id| usize::from(id) >= n_plates) {
+        return Err(r.malformed(format!("plate id outside 0..{n_plates}")));
+    }
+    if euler_poles.len() != n_plates {
+        return Err(r.malformed(format!("expected Euler poles for {n_plates} plates")));
+    }
+    let boundary_field = r.cells(n, |r| {
+        Ok(BoundaryCharacter {
+            convergent_rate: r.f32()?,
+            transform_rate: r.f32()?,
+            neighbor_plate: r.u8()?,
+            tangent_east: r.f32()?,
+            tangent_north: r.f32()?,
+            normal_east: r.f32()?,
+            normal_north: r.f32()?,
+            overriding_plate: r.u8()?,
+        })
+    })?;
+    let is_boundary = r.bool_cells(n)?;
+    let continental_mask = r.bool_cells(n)?;
+    let crust_field = r.cells(n, |r| r.enum_u8("crust type", crust_from_u8))?;
+    let thermal_age = r.f32_cells(n)?;
+    let regime_character = RegimeCharacterField {
+        convergent_influence: r.f32_cells(n)?,
+        divergent_influence: r.f32_cells(n)?,
+        transform_influence: r.f32_cells(n)?,
+        hotspot_influence: r.f32_cells(n)?,
+        cratonic_stability: r.f32_cells(n)?,
+        width,
+        height,
+    };
+    let regime_field = RegimeField {
+        data: r.cells(n, |r| r.enum_u8("regime", regime_from_u8))?,
+        width,
+        height,
+    };
+    let grain_field = GrainField {
+        angles: r.f32_cells(n)?,
+        intensities: r.f32_cells(n)?,
+        width,
+        height,
+    };
+    let erodibility_field = r.f32_cells(n)?;
+    let hotspots = r.vec(|r| Ok(Vec3::new(r.f64()?, r.f64()?, r.f64()?)))?;
+    let divergent_distance_km = r.f32_cells(n)?;
+    Ok(PlateSimulation {
+        plate_ids,
+        n_plates,
+        n_continents: 0,
+        euler_poles,
+        boundary_field,
+        boundary_polylines: Vec::new(),
+        triple_junctions: Vec::new(),
+        is_boundary,
+        continental_mask,
+        crust_field,
+        thermal_age,
+        regime_character,
+        regime_field,
+        grain_field,
+        erodibility_field,
+        hotspots,
+        hotspot_tracks: Vec::new(),
+        divergent_distance_km,
+        width,
+        height,
+    })
+}
+
+fn write_euler_pole(w: &mut Writer, pole: &EulerPole) {
+    w.f64(pole.axis.x);
+    w.f64(pole.axis.y);
+    w.f64(pole.axis.z);
+    w.f64(pole.rate_deg_per_myr);
+}
+
+fn read_euler_pole(r: &mut Reader) -> Result<EulerPole, SaveError> {
+    Ok(EulerPole {
+        axis: Vec3::new(r.f64()?, r.f64()?, r.f64()?),
+        rate_deg_per_myr: r.f64()?,
+    })
+}
+
+/// Rewrite a version 1 `PLAT` payload, which held one (v_east, v_north)
+/// per plate, with Euler poles on a planet of `radius_km`.
+///
+/// Each plate gets the pole 90° from its centroid that reproduces the old
+/// velocity there.  The boundary field was computed from those velocities
+/// and is carried over unchanged.
+pub(super) fn upgrade_plates_v1(r: &mut Reader, radius_km: f64) -> Result<Vec<u8>, SaveError> {
+    let width = r.len()?;
+    let height = r.len()?;
+    let n = width
+        .checked_mul(height)
+        .ok_or_else(|| r.malformed("grid size overflows"))?;
+    let n_plates = r.len()?;
+    let plate_ids = r.cells(n, Reader::u8)?;
+    let velocities = r.vec(|r| Ok((r.f32()?, r.f32()?)))?;
+    if velocities.len() != n_plates || plate_ids.iter().any(|&id| usize::from(id) >= n_plates) {
+        return Err(r.malformed(format!("expected velocities for {n_plates} plates")));
+    }
+    let centroids = compute_plate_centroids(&grid_points(width, height), &plate_ids, n_plates);
+    let poles = centroids
+        .iter()
+        .zip(&velocities)
+        .map(|(&centroid, &velocity)| EulerPole::translating(centroid, velocity, radius_km))
+        .collect::<Vec<_>>();
+
+    let mut w = Writer::default();
+    w.len(width);
+    w.len(height);
+    w.len(n_plates);
+    w.vec(&plate_ids, |w, &id| w.u8(id));
+    w.vec(&poles, write_euler_pole);
+    w.raw(r.rest());
+    Ok(w.into_bytes())
+}
+
+/// Rewrite a version 4 `PLAT` payload with `n_continents` appended.
+pub(super) fn upgrade_plates_v4(r: &mut Reader, n_continents: usize) -> Result<Vec<u8>, SaveError> {
+    let mut w = Writer::default();
+    w.raw(r.rest());
+    w.u32(n_continents as u32);
+    Ok(w.into_bytes())
+}
+
+/// `TJUN`: the triple junctions.
+pub(super) fn write_junctions(w: &mut Writer, junctions: &[TripleJunction]) {
+    w.vec(junctions, |w, j| {
+        for plate in j.plates {
+            w.u8(plate);
+        }
+        for boundary in j.boundaries {
+            w.u8(boundary as u8);
+        }
+        w.f64(j.position.x);
+        w.f64(j.position.y);
+        w.f64(j.position.z);
+        w.len(j.cell);
+    });
+}
+
+/// Inverse of `write_junctions` for a grid of `cells` cells.
+pub(super) fn read_junctions(
+    r: &mut Reader,
+    cells: usize,
+) -> Result<Vec<TripleJunction>, SaveError> {
+    r.vec(|r| {
+        let plates = [r.u8()?, r.u8()?, r.u8()?];
+        let mut boundaries = [BoundaryType::Transform; 3];
+        for boundary in &mut boundaries {
+            *boundary = r.enum_u8("boundary type", boundary_type_from_u8)?;
+        }
+        let position = Vec3::new(r.f64()?, r.f64()?, r.f64()?);
+        let cell = r.u64()?;
+        let cell = usize::try_from(cell)
+            .ok()
+            .filter(|&c| c < cells)
+            .ok_or_else(|| r.malformed(format!("junction cell {cell} is off the grid")))?;
+        Ok(TripleJunction {
+            plates,
+            boundaries,
+            position,
+            cell,
+        })
+    })
+}
+
+/// `HTRK`: the hotspot tracks.
+pub(super) fn write_hotspot_tracks(w: &mut Writer, tracks: &[HotspotTrack]) {
+    w.vec(tracks, |w, t| {
+        w.f64(t.hotspot.x);
+        w.f64(t.hotspot.y);
+        w.f64(t.hotspot.z);
+        w.u8(t.plate);
+        w.f32(t.plume_strength);
+        w.vec(&t.seamounts, |w, s| {
+            w.f64(s.position.x);
+            w.f64(s.position.y);
+            w.f64(s.position.z);
+            w.f64(s.age_myr);
+        });
+    });
+}
+
+/// Inverse of `write_hotspot_tracks`.
+pub(super) fn read_hotspot_tracks(r: &mut Reader) -> Result<Vec<HotspotTrack>, SaveError> {
+    r.vec(|r| {
+        Ok(HotspotTrack {
+            hotspot: Vec3::new(r.f64()?, r.f64()?, r.f64()?),
+            plate: r.u8()?,
+            plume_strength: r.f32()?,
+            seamounts: r.vec(|r| {
+                Ok(Seamount {
+                    position: Vec3::new(r.f64()?, r.f64()?, r.f64()?),
+                    age_myr: r.f64()?,
+                })
+            })?,
+        })
+    })
+}
+
+/// `BPLY`: the smoothed boundary polylines.
+pub(super) fn write_polylines(w: &mut Writer, polylines: &[BoundaryPolyline]) {
+    w.vec(polylines, |w, p| {
+        w.u32(p.plate_a);
+        w.u32(p.plate_b);
+        w.u8(p.dominant_character as u8);
+        w.bool(p.is_closed);
+        w.vec(&p.vertices, |w, v| {
+            w.f64(v.x);
+            w.f64(v.y);
+            w.f64(v.lat);
+            w.f64(v.lon);
+            w.f32(v.convergent_rate);
+            w.f32(v.transform_rate);
+            w.f32(v.tangent.0);
+            w.f32(v.tangent.1);
+            w.f32(v.normal.0);
+            w.f32(v.normal.1);
+        });
+        w.vec(&p.arc_lengths, |w, &s| w.f64(s));
+    });
+}
+
+pub(super) fn read_polylines(r: &mut Reader) -> Result<Vec<BoundaryPolyline>, SaveError> {
+    r.vec(|r| {
+        Ok(BoundaryPolyline {
+            plate_a: r.u32()?,
+            plate_b: r.u32()?,
+            dominant_character: r.enum_u8("boundary type", boundary_type_from_u8)?,
+            is_closed: r.bool()?,
+            vertices: r.vec(|r| {
+                Ok(BoundaryVertex {
+                    x: r.f64()?,
+                    y: r.f64()?,
+                    lat: r.f64()?,
+                    lon: r.f64()?,
+                    convergent_rate: r.f32()?,
+                    transform_rate: r.f32()?,
+                    tangent: (r.f32()?, r.f32()?),
+                    normal: (r.f32()?, r.f32()?),
+                })
+            })?,
+            arc_lengths: r.vec(Reader::f64)?,
+        })
+    })
+}
+
+// ── Climate layer ─────────────────────────────────────────────────────────────
+
+/// `CLIM`: the unsmoothed climate fields on the plate grid.
+pub(super) fn write_climate(w: &mut Writer, c: &ClimateLayer) {
+    w.f32s(&c.map_field);
+    w.f32s(&c.seasonality_field);
+    w.vec(&c.glaciation_mask, |w, &g| w.u8(g as u8));
+}
+
+pub(super) fn read_climate(
+    r: &mut Reader,
+    width: usize,
+    height: usize,
+) -> Result<ClimateLayer, SaveError> {
+    let n = width * height;
+    Ok(ClimateLayer {
+        map_field: r.f32_cells(n)?,
+        seasonality_field: r.f32_cells(n)?,
+        glaciation_mask: r.cells(n, |r| r.enum_u8("glacial class", glacial_from_u8))?,
+        width,
+        height,
+    })
+}
+
+// ── Overview ──────────────────────────────────────────────────────────────────
+
+/// `OVER`: the overview fields not already stored with the plate and
+/// climate layers.  Glaciation is the climate mask and the metrics go in
+/// `SCOR`.
+pub(super) fn write_overview(w: &mut Writer, o: &PlanetOverview) {
+    w.f32s(&o.elevations);
+    w.f32s(&o.physical_elevations);
+    w.bools(&o.ocean_mask);
+    w.f32(o.sea_level_km);
+    w.vec(&o.regimes, |w, &r| w.u8(r as u8));
+    w.f32s(&o.map_field);
+    w.f32s(&o.erodibility_field);
+}
+
+/// Inverse of `write_overview`, completed with `glaciation` and `metrics`.
+pub(super) fn read_overview(
+    r: &mut Reader,
+    width: usize,
+    height: usize,
+    glaciation: Vec<GlacialClass>,
+    planet_metrics: PlanetMetrics,
+) -> Result<PlanetOverview, SaveError> {
+    let n = width * height;
+    Ok(PlanetOverview {
+        width,
+        height,
+        elevations: r.f32_cells(n)?,
+        physical_elevations: r.f32_cells(n)?,
+        ocean_mask: r.bool_cells(n)?,
+        sea_level_km: r.f32()?,
+        regimes: r.cells(n, |r| r.enum_u8("regime", regime_from_u8))?,
+        map_field: r.f32_cells(n)?,
+        erodibility_field: r.f32_cells(n)?,
+        glaciation,
+        planet_metrics,
+        generation_time_ms: 0,
+        // Rebuilt from the pipeline once the planet is loaded.
+        trace: PipelineTrace::default(),
+    })
+}
+
+/// `SCOR`: the planet metrics as computed when the planet was saved.
+pub(super) fn write_metrics(w: &mut Writer, m: &PlanetMetrics) {
+    w.vec(&m.metrics, |w, metric| {
+        w.str(&metric.name);
+        w.f32(metric.raw_value);
+        w.f32(metric.threshold);
+        w.bool(metric.pass);
+        w.str(&metric.description);
+    });
+    w.bool(m.all_pass);
+}
+
+pub(super) fn read_metrics(r: &mut Reader) -> Result<PlanetMetrics, SaveError> {
+    let metrics = r.vec(|r| {
+        Ok(MetricResult {
+            name: r.str()?,
+            raw_value: r.f32()?,
+            threshold: r.f32()?,
+            pass: r.bool()?,
+            description: r.str()?,
+        })
+    })?;
+    let count = metrics.len();
+    let metrics = metrics
+        .try_into()
+        .map_err(|_| r.malformed(format!("{count} planet metrics, expected 6")))?;
+    Ok(PlanetMetrics {
+        metrics,
+        all_pass: r.bool()?,
+    })
+}
@@ -0,0 +1,591 @@
+//! Versioned binary save files for generated planets.
+//!
+//! A save holds everything a [`Planet`] keeps in memory: the params and grid
+//! config, the plate layer (plate ids, Euler poles, boundary field,
+//! polylines and triple junctions, regime and grain fields, hotspots and
+//! their tracks), the climate layer, every overview field and the planet
+//! metrics.  Loading rebuilds the planet from those fields without rerunning
+//! a stage, so a reopened planet matches the saved one bit for bit even if
+//! the generator has changed since.  Tiles are still generated on demand by
+//! the loading build; [`SaveInfo::matches_current_build`] tells whether they
+//! match what the saving build would have produced.
+//!
+//! The file is a sequence of independently deflated, checksummed chunks:
+//!
+//! | Chunk  | Contents                                                  |
+//! |--------|-----------------------------------------------------------|
+//! | `META` | `SaveInfo` as JSON: generator versions, params and config |
+//! | `PLAT` | plate simulation grids, Euler poles, hotspots, continents |
+//! | `BPLY` | boundary polylines                                        |
+//! | `TJUN` | triple junctions                                          |
+//! | `HTRK` | hotspot tracks                                            |
+//! | `CLIM` | climate layer                                             |
+//! | `OVER` | overview elevation, ocean, regime, MAP and erodibility    |
+//! | `SCOR` | planet metrics                                            |
+//!
+//! Any change to a chunk layout bumps `SAVE_FORMAT_VERSION` and adds a step
+//! to `MIGRATIONS`, so files from older builds keep loading:
+//!
+//! - version 2 stores an Euler pole per plate in `PLAT` where version 1
+//!   stored one east/north velocity;
+//! - version 3 adds `TJUN`, recomputed from the saved plate grids when
+//!   migrating;
+//! - version 4 adds `HTRK`, retraced from the saved hotspots and Euler
+//!   poles;
+//! - version 5 ends `PLAT` with the number of continents placed, migrated
+//!   as the count the fragmentation asked for.
+
+mod chunks;
+mod codec;
+
+use serde::{Deserialize, Serialize};
+
+use crate::generator::{GenerationConfig, GlobalParams, OUTPUT_VERSION};
+use crate::planet::{OverviewPipeline, Planet};
+use crate::plates::hotspot_tracks::trace_hotspot_tracks;
+use crate::plates::plate_generation::continent_count_from_fragmentation;
+use crate::plates::triple_junctions::detect_triple_junctions;
+use crate::seed::SEED_SCHEME_VERSION;
+
+use chunks::{Chunks, Tag};
+use codec::{Reader, Writer};
+
+/// Version of the save-file layout written by this build.
+pub const SAVE_FORMAT_VERSION: u32 = 5;
+
+/// `terra-core` version recorded in save files and manifests.
+pub(crate) const GENERATOR_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const META: Tag = *b"META";
+const PLAT: Tag = *b"PLAT";
+const BPLY: Tag = *b"BPLY";
+const TJUN: Tag = *b"TJUN";
+const HTRK: Tag = *b"HTRK";
+const CLIM: Tag = *b"CLIM";
+const OVER: Tag = *b"OVER";
+const SCOR: Tag = *b"SCOR";
+
+/// One upgrade step between consecutive format versions.
+type Migration = fn(&mut Chunks) -> Result<(), SaveError>;
+
+/// `MIGRATIONS[i]` rewrites the chunks of a version `i + 1` file into
+/// version `i + 2`.
+const MIGRATIONS: [Migration; SAVE_FORMAT_VERSION as usize - 1] = [
+    plates_to_euler_poles,
+    add_triple_junctions,
+    add_hotspot_tracks,
+    add_continent_count,
+];
+
+/// Version 1 → 2: per-plate velocities become Euler poles.
+fn plates_to_euler_poles(chunks: &mut Chunks) -> Result<(), SaveError> {
+    let info: SaveInfo = serde_json::from_slice(chunks.get(META)?)?;
+    let radius_km = info.params.body.radius_km;
+    let payload = decode(chunks, PLAT, |r| codec::upgrade_plates_v1(r, radius_km))?;
+    chunks.replace(PLAT, payload);
+    Ok(())
+}
+
+/// Version 2 → 3: triple junctions detected from the saved boundary field.
+fn add_triple_junctions(chunks: &mut Chunks) -> Result<(), SaveError> {
+    let info: SaveInfo = serde_json::from_slice(chunks.get(META)?)?;
+    let plates = decode(chunks, PLAT, codec::read_plates_v4)?;
+    let junctions = detect_triple_junctions(
+        &plates.plate_ids,
+        &plates.boundary_field,
+        &plates.is_boundary,
+        info.params.body.radius_km,
+        plates.width,
+        plates.height,
+    );
+    chunks.push(TJUN, encode(|w| codec::write_junctions(w, &junctions)));
+    Ok(())
+}
+
+/// Version 3 → 4: hotspot tracks traced from the saved hotspots and poles.
+fn add_hotspot_tracks(chunks: &mut Chunks) -> Result<(), SaveError> {
+    let info: SaveInfo = serde_json::from_slice(chunks.get(META)?)?;
+    let plates = decode(chunks, PLAT, codec::read_plates_v4)?;
+    let tracks = trace_hotspot_tracks(
+        &plates.hotspots,
+        &plates.plate_ids,
+        &plates.euler_poles,
+        info.params.tuning.plates.plume_strength,
+        info.params.body.radius_km,
+        plates.width,
+        plates.height,
+    );
+    chunks.push(HTRK, encode(|w| codec::write_hotspot_tracks(w, &tracks)));
+    Ok(())
+}
+
+/// Version 4 → 5: the continent count older builds reported, the one the
+/// fragmentation asks for.
+fn add_continent_count(chunks: &mut Chunks) -> Result<(), SaveError> {
+    let info: SaveInfo = serde_json::from_slice(chunks.get(META)?)?;
+    let n_continents = continent_count_from_fragmentation(info.params.continental_fragmentation);
+    let payload = decode(chunks, PLAT, |r| codec::upgrade_plates_v4(r, n_continents))?;
+    chunks.replace(PLAT, payload);
+    Ok(())
+}
+
+/// Error returned when reading a save file.
+#[derive(Debug, thiserror::Error)]
+pub enum SaveError {
+    #[error("not a terra save file")]
+    NotASaveFile,
+    #[error(
+        "save format version {0} is not supported (this build reads 1 to {SAVE_FORMAT_VERSION})"
+    )]
+    UnsupportedVersion(u32),
+    #[error("save file is truncated")]
+    Truncated,
+    #[error("chunk {0} is missing")]
+    MissingChunk(String),
+    #[error("chunk {0} failed its checksum")]
+    Checksum(String),
+    #[error("chunk {chunk} is malformed: {reason}")]
+    Malformed { chunk: String, reason: String },
+    #[error("invalid save metadata: {0}")]
+    Metadata(#[from] serde_json::Error),
+}
+
+/// Provenance of a save file, stored in its `META` chunk.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveInfo {
+    /// Layout version of the file as written, before any migration.
+    #[serde(skip)]
+    pub format_version: u32,
+    /// `terra-core` version that generated the planet.
+    pub generator_version: String,
+    /// `SEED_SCHEME_VERSION` of the generating build.
+    pub seed_scheme_version: u32,
+    /// `OUTPUT_VERSION` of the generating build; 0 in files written before
+    /// it was recorded.
+    #[serde(default)]
+    pub output_version: u32,
+    pub params: GlobalParams,
+    pub config: GenerationConfig,
+}
+
+impl SaveInfo {
+    /// True when this build generates the same planet from the saved params,
+    /// so tiles and slider changes on the loaded planet agree with the
+    /// stored fields.
+    pub fn matches_current_build(&self) -> bool {
+        self.generator_version == GENERATOR_VERSION
+            && self.seed_scheme_version == SEED_SCHEME_VERSION
+            && self.output_version == OUTPUT_VERSION
+    }
+}
+
+/// Serialise `planet` in the current save format.
+pub fn save_planet(planet: &Planet) -> Vec<u8> {
+    let info = SaveInfo {
+        format_version: SAVE_FORMAT_VERSION,
+        generator_version: GENERATOR_VERSION.to_owned(),
+        seed_scheme_version: SEED_SCHEME_VERSION,
+        output_version: OUTPUT_VERSION,
+        params: planet.params().clone(),
+        config: *planet.config(),
+    };
+    let plates = planet.plates();
+    let overview = planet.overview();
+    let mut chunks = Chunks::default();
+    chunks.push(
+        META,
+        serde_json::to_vec(&info).expect("save metadata is plain data"),
+    );
+    chunks.push(PLAT, encode(|w| codec::write_plates(w, plates)));
+    chunks.push(
+        BPLY,
+        encode(|w| codec::write_polylines(w, &plates.boundary_polylines)),
+    );
+    chunks.push(
+        TJUN,
+        encode(|w| codec::write_junctions(w, &plates.triple_junctions)),
+    );
+    chunks.push(
+        HTRK,
+        encode(|w| codec::write_hotspot_tracks(w, &plates.hotspot_tracks)),
+    );
+    chunks.push(CLIM, encode(|w| codec::write_climate(w, planet.climate())));
+    chunks.push(OVER, encode(|w| codec::write_overview(w, overview)));
+    chunks.push(
+        SCOR,
+        encode(|w| codec::write_metrics(w, &overview.planet_metrics)),
+    );
+    chunks::write(SAVE_FORMAT_VERSION, &chunks)
+}
+
+/// Reopen a planet written by `save_planet`, migrating older formats.
+pub fn load_planet(bytes: &[u8]) -> Result<Planet, SaveError> {
+    let (info, chunks) = read_current(bytes)?;
+    let mut plates = decode(&chunks, PLAT, codec::read_plates)?;
+    plates.boundary_polylines = decode(&chunks, BPLY, codec::read_polylines)?;
+    let (width, height) = (plates.width, plates.height);
+    plates.triple_junctions = decode(&chunks, TJUN, |r| codec::read_junctions(r, width * height))?;
+    plates.hotspot_tracks = decode(&chunks, HTRK, codec::read_hotspot_tracks)?;
+    let config = info.config;
+    if (width, height) != (config.overview_width, config.overview_height) {
+        return Err(SaveError::Malformed {
+            chunk: chunks::tag_name(PLAT),
+            reason: format!(
+                "grid is {width} × {height} but the config says {} × {}",
+                config.overview_width, config.overview_height
+            ),
+        });
+    }
+    let climate = decode(&chunks, CLIM, |r| codec::read_climate(r, width, height))?;
+    let metrics = decode(&chunks, SCOR, codec::read_metrics)?;
+    let overview = decode(&chunks, OVER, |r| {
+        codec::read_overview(r, width, height, climate.glaciation_mask.clone(), metrics)
+    })?;
+    let pipeline = OverviewPipeline::from_outputs(&info.params, config, plates, climate, overview);
+    Ok(Planet::from_pipeline(pipeline))
+}
+
+/// Read a save file's provenance without rebuilding the planet.
+pub fn read_info(bytes: &[u8]) -> Result<SaveInfo, SaveError> {
+    read_current(bytes).map(|(info, _)| info)
+}
+
+/// Rewrite a save file from any supported version in the current format.
+///
+/// The fields are carried over unchanged; files already at
+/// `SAVE_FORMAT_VERSION` come back byte for byte.
+pub fn migrate(bytes: &[u8]) -> Result<Vec<u8>, SaveError> {
+    let (_, chunks) = read_current(bytes)?;
+    Ok(chunks::write(SAVE_FORMAT_VERSION, &chunks))
+}
+
+/// Split `bytes` into chunks upgraded to the current format, plus the
+/// metadata they carry.
+fn read_current(bytes: &[u8]) -> Result<(SaveInfo, Chunks), SaveError> {
+    let (version, mut chunks) = chunks::read(bytes)?;
+    if version == 0 || version > SAVE_FORMAT_VERSION {
+        return Err(SaveError::UnsupportedVersion(version));
+    }
+    for migration in &MIGRATIONS[version as usize - 1..] {
+        migration(&mut chunks)?;
+    }
+    let mut info: SaveInfo = serde_json::from_slice(chunks.get(META)?)?;
+    info.format_version = version;
+    Ok((info, chunks))
+}
+
+fn encode(write: impl FnOnce(&mut Writer)) -> Vec<u8> {
+    let mut writer = Writer::default();
+    write(&mut writer);
+    writer.into_bytes()
+}
+
+fn decode<T>(
+    chunks: &Chunks,
+    tag: Tag,
+    read: impl FnOnce(&mut Reader) -> Result<T, SaveError>,
+) -> Result<T, SaveError> {
+    let mut reader = Reader::new(tag, chunks.get(tag)?);
+    let value = read(&mut reader)?;
+    reader.finish()?;
+    Ok(value)
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::planet::OverviewStage;
+    use crate::seed::StableRng;
+
+    /// A loaded planet holds exactly the saved fields and resumes stage
+    /// caching from the saved params.
+    #[test]
+    fn planet_round_trips_exactly() {
+        let config = GenerationConfig {
+            overview_width: 128,
+            overview_height: 64,
+            ..GenerationConfig::default()
+        };
+        let planet = Planet::with_config(&GlobalParams::default(), config);
+        let bytes = save_planet(&planet);
+        let mut loaded = load_planet(&bytes).expect("fresh save must load");
+
+        assert_eq!(
+            format!("{:?}", loaded.params()),
+            format!("{:?}", planet.params())
+        );
+        assert_eq!(loaded.config(), planet.config());
+
+        let (a, b) = (planet.plates(), loaded.plates());
+        assert_eq!(a.plate_ids, b.plate_ids);
+        assert_eq!(a.n_plates, b.n_plates);
+        assert_eq!(a.n_continents, b.n_continents);
+        assert_eq!(a.euler_poles, b.euler_poles);
+        assert_eq!(a.boundary_field, b.boundary_field);
+        assert_eq!(a.boundary_polylines, b.boundary_polylines);
+        assert_eq!(a.triple_junctions, b.triple_junctions);
+        assert_eq!(a.is_boundary, b.is_boundary);
+        assert_eq!(a.continental_mask, b.continental_mask);
+        assert_eq!(a.crust_field, b.crust_field);
+        assert_eq!(a.thermal_age, b.thermal_age);
+        assert_eq!(a.regime_character, b.regime_character);
+        assert_eq!(a.regime_field, b.regime_field);
+        assert_eq!(a.grain_field, b.grain_field);
+        assert_eq!(a.erodibility_field, b.erodibility_field);
+        assert_eq!(a.hotspots, b.hotspots);
+        assert_eq!(a.hotspot_tracks, b.hotspot_tracks);
+        assert_eq!(a.divergent_distance_km, b.divergent_distance_km);
+
+        let (a, b) = (planet.climate(), loaded.climate());
+        assert_eq!(a.map_field, b.map_field);
+        assert_eq!(a.seasonality_field, b.seasonality_field);
+        assert_eq!(a.glaciation_mask, b.glaciation_mask);
+
+        let (a, b) = (planet.overview(), loaded.overview());
+        assert_eq!(a.elevations, b.elevations);
+        assert_eq!(a.physical_elevations, b.physical_elevations);
+        assert_eq!(a.ocean_mask, b.ocean_mask);
+        assert_eq!(a.sea_level_km, b.sea_level_km);
+        assert_eq!(a.regimes, b.regimes);
+        assert_eq!(a.map_field, b.map_field);
+        assert_eq!(a.erodibility_field, b.erodibility_field);
+        assert_eq!(a.glaciation, b.glaciation);
+        assert_eq!(
+            serde_json::to_string(&a.planet_metrics).unwrap(),
+            serde_json::to_string(&b.planet_metrics).unwrap()
+        );
+
+        let info = read_info(&bytes).unwrap();
+        assert_eq!(info.format_version, SAVE_FORMAT_VERSION);
+        assert!(info.matches_current_build());
+        assert_eq!(migrate(&bytes).unwrap(), bytes);
+
+        let params = planet.params().clone();
+        assert!(loaded.set_params(&params).is_empty());
+        let ran = loaded.set_params(&GlobalParams {
+            glaciation: 0.9,
+            ..params
+        });
+        assert!(!ran.contains(&OverviewStage::Plates));
+        assert!(ran.contains(&OverviewStage::Climate));
+    }
+
+    /// A version 1 file, which stored each plate's velocity at its centroid,
+    /// loads with poles that reproduce those velocities.
+    #[test]
+    fn migrates_plate_velocities_to_euler_poles() {
+        use crate::plates::plate_generation::{compute_plate_centroids, grid_points};
+
+        let config = GenerationConfig {
+            overview_width: 64,
+            overview_height: 32,
+            ..GenerationConfig::default()
+        };
+        let planet = Planet::with_config(&GlobalParams::default(), config);
+        let plates = planet.plates();
+        let radius_km = planet.params().body.radius_km;
+        let centroids = compute_plate_centroids(
+            &grid_points(plates.width, plates.height),
+            &plates.plate_ids,
+            plates.n_plates,
+        );
+        let velocities = plates
+            .euler_poles
+            .iter()
+            .zip(&centroids)
+            .map(|(pole, &c)| pole.velocity_at(c, radius_km))
+            .collect::<Vec<_>>();
+
+        // Swap the pole list after the header and plate ids for the
+        // version 1 velocity list.
+        let (_, current) = chunks::read(&save_planet(&planet)).unwrap();
+        let mut v1 = Chunks::default();
+        for tag in [META, PLAT, BPLY, CLIM, OVER, SCOR] {
+            let mut payload = current.get(tag).unwrap().to_vec();
+            if tag == PLAT {
+                let ids_end = 4 * 8 + plates.plate_ids.len();
+                let poles_end = ids_end + 8 + 32 * plates.n_plates;
+                let mut old = payload[..ids_end].to_vec();
+                old.extend_from_slice(&(velocities.len() as u64).to_le_bytes());
+                for (east, north) in &velocities {
+                    old.extend_from_slice(&east.to_le_bytes());
+                    old.extend_from_slice(&north.to_le_bytes());
+                }
+                // Version 1 also ends before the continent count.
+                old.extend_from_slice(&payload[poles_end..payload.len() - 4]);
+                payload = old;
+            }
+            v1.push(tag, payload);
+        }
+        let v1 = chunks::write(1, &v1);
+
+        assert_eq!(read_info(&v1).unwrap().format_version, 1);
+        let loaded = load_planet(&v1).expect("version 1 save must load");
+        for ((pole, &c), &(east, north)) in loaded
+            .plates()
+            .euler_poles
+            .iter()
+            .zip(&centroids)
+            .zip(&velocities)
+        {
+            let (e, n) = pole.velocity_at(c, radius_km);
+            assert!((e - east).abs() < 1e-4 && (n - north).abs() < 1e-4);
+        }
+        assert_eq!(loaded.plates().boundary_field, plates.boundary_field);
+        assert_eq!(loaded.plates().triple_junctions, plates.triple_junctions);
+        assert_eq!(
+            loaded.plates().n_continents,
+            continent_count_from_fragmentation(planet.params().continental_fragmentation)
+        );
+        // The recovered poles carry no spin about the centroid, so the
+        // tracks are retraced from them rather than matching the originals.
+        let loaded_plates = loaded.plates();
+        let retraced = trace_hotspot_tracks(
+            &loaded_plates.hotspots,
+            &loaded_plates.plate_ids,
+            &loaded_plates.euler_poles,
+            planet.params().tuning.plates.plume_strength,
+            radius_km,
+            loaded_plates.width,
+            loaded_plates.height,
+        );
+        assert_eq!(loaded_plates.hotspot_tracks, retraced);
+        assert_eq!(loaded.overview().elevations, planet.overview().elevations);
+
+        let migrated = migrate(&v1).unwrap();
+        assert_eq!(
+            read_info(&migrated).unwrap().format_version,
+            SAVE_FORMAT_VERSION
+        );
+        assert_eq!(
+            load_planet(&migrated).unwrap().plates().euler_poles,
+            loaded.plates().euler_poles
+        );
+    }
+
+    /// A `PLAT` chunk whose plate ids or pole count disagree with its plate
+    /// count is rejected rather than indexing past the pole list later.
+    #[test]
+    fn rejects_inconsistent_plate_tables() {
+        let config = GenerationConfig {
+            overview_width: 64,
+            overview_height: 32,
+            ..GenerationConfig::default()
+        };
+        let planet = Planet::with_config(&GlobalParams::default(), config);
+        let n_plates = planet.plates().n_plates;
+        let (_, current) = chunks::read(&save_planet(&planet)).unwrap();
+        let corrupt = |edit: &dyn Fn(&mut Vec<u8>)| {
+            let mut damaged = Chunks::default();
+            for tag in [META, PLAT, BPLY, CLIM, OVER, SCOR] {
+                let mut payload = current.get(tag).unwrap().to_vec();
+                if tag == PLAT {
+                    edit(&mut payload);
+                }
+                damaged.push(tag, payload);
+            }
+            load_planet(&chunks::write(SAVE_FORMAT_VERSION, &damaged))
+        };
+
+        // The plate ids start after the width, height, plate count and the
+        // id list's own length.
+        let out_of_range = corrupt(&|payload| payload[4 * 8] = n_plates as u8);
+        assert!(matches!(
+            out_of_range,
+            Err(SaveError::Malformed { chunk, .. }) if chunk == "PLAT"
+        ));
+        let extra_plate = corrupt(&|payload| {
+            payload[16..24].copy_from_slice(&(n_plates as u64 + 1).to_le_bytes())
+        });
+        assert!(matches!(
+            extra_plate,
+            Err(SaveError::Malformed { chunk, .. }) if chunk == "PLAT"
+        ));
+    }
+
+    /// Saves from a build with other algorithms report a mismatch even when
+    /// the crate and seed scheme versions agree.
+    #[test]
+    fn output_version_is_part_of_build_identity() {
+        let mut info = SaveInfo {
+            format_version: SAVE_FORMAT_VERSION,
+            generator_version: GENERATOR_VERSION.to_owned(),
+            seed_scheme_version: SEED_SCHEME_VERSION,
+            output_version: OUTPUT_VERSION,
+            params: GlobalParams::default(),
+            config: GenerationConfig::default(),
+        };
+        assert!(info.matches_current_build());
+        info.output_version = OUTPUT_VERSION - 1;
+        assert!(!info.matches_current_build());
+
+        // Files written before the field existed predate every output change.
+        let mut json: serde_json::Value = serde_json::to_value(&info).unwrap();
+        json.as_object_mut().unwrap().remove("output_version");
+        let old: SaveInfo = serde_json::from_value(json).unwrap();
+        assert_eq!(old.output_version, 0);
+        assert!(!old.matches_current_build());
+    }
+
+    /// A file holding only metadata and an incompressible `XTRA` chunk.
+    fn meta_only_file() -> Vec<u8> {
+        let info = SaveInfo {
+            format_version: SAVE_FORMAT_VERSION,
+            generator_version: "0.0.0".to_owned(),
+            seed_scheme_version: 0,
+            output_version: 0,
+            params: GlobalParams::default(),
+            config: GenerationConfig::default(),
+        };
+        let mut rng = StableRng::new(1);
+        let mut chunks = Chunks::default();
+        chunks.push(*b"XTRA", (0..64).map(|_| rng.next_u64() as u8).collect());
+        chunks.push(META, serde_json::to_vec(&info).unwrap());
+        chunks::write(SAVE_FORMAT_VERSION, &chunks)
+    }
+
+    /// Unknown chunks are skipped and the metadata is readable on its own.
+    #[test]
+    fn reads_info_and_skips_unknown_chunks() {
+        let file = meta_only_file();
+        let info = read_info(&file).unwrap();
+        assert_eq!(info.generator_version, "0.0.0");
+        assert!(!info.matches_current_build());
+        assert!(matches!(
+            load_planet(&file),
+            Err(SaveError::MissingChunk(tag)) if tag == "PLAT"
+        ));
+    }
+
+    #[test]
+    fn rejects_damaged_files() {
+        let file = meta_only_file();
+
+        assert!(matches!(
+            read_info(b"PK\x03\x04 not a save"),
+            Err(SaveError::NotASaveFile)
+        ));
+        assert!(matches!(
+            read_info(&file[..file.len() - 3]),
+            Err(SaveError::Truncated)
+        ));
+
+        let mut newer = file.clone();
+        newer[8..12].copy_from_slice(&(SAVE_FORMAT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            read_info(&newer),
+            Err(SaveError::UnsupportedVersion(v)) if v == SAVE_FORMAT_VERSION + 1
+        ));
+
+        // XTRA is stored right after the 12-byte file header and its own
+        // 17-byte chunk header.
+        let mut flipped = file;
+        flipped[12 + 17 + 5] ^= 0x20;
+        assert!(matches!(
+            read_info(&flipped),
+            Err(SaveError::Checksum(tag)) if tag == "XTRA"
+        ));
+    }
+}
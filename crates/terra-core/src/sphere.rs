@@ -68,6 +68,19 @@ pub fn great_circle_distance_deg(lat1: f64, lon1: f64, lat2: f64, lon2: f64) ->
     great_circle_distance_rad(a, b).to_degrees()
 }
 
+/// Mean Earth radius in kilometres.
+pub const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Equatorial east-west size (km) of one cell of a global `width`-column grid
+/// on a sphere of `radius_km`.
+///
+/// Used to express grid-scale calibration constants in kilometres so that
+/// they mean the same thing at every overview resolution.
+pub const fn equatorial_cell_km(width: usize, radius_km: f64) -> f64 {
+    let width = if width == 0 { 1 } else { width };
+    2.0 * std::f64::consts::PI * radius_km / width as f64
+}
+
 /// Interpolate along a great circle arc.
 /// t=0 returns a, t=1 returns b.
 pub fn slerp(a: Vec3, b: Vec3, t: f64) -> Vec3 {
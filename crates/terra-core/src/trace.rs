@@ -0,0 +1,72 @@
+//! Per-stage timing and provenance of one generation run.
+//!
+//! `PlanetGenerator::generate` and the overview pipeline attach a
+//! `PipelineTrace` to their results: the wall time of every stage, the
+//! parameters the stages actually consumed once the sliders were resolved,
+//! and counts of the discrete features they produced.  Unlike
+//! `derive_debug_params`, which re-derives these values analytically, the
+//! trace is filled in by the pipeline as it runs and cannot drift from it.
+//!
+//! Stage times come from `progress::stage`, so a stage's name in the trace is
+//! the one progress observers see.
+
+use serde::{Deserialize, Serialize};
+
+use crate::hydraulic::HydraulicParams;
+use crate::noise::params::{GlacialClass, NoiseParams, TerrainClass};
+
+/// Wall time of one pipeline stage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageTiming {
+    /// Stage name, e.g. `"plates"` or `"hydraulic"`.
+    pub stage: String,
+    pub wall_ms: f64,
+}
+
+/// Discrete features produced by a run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeatureCounts {
+    pub plates: usize,
+    pub hotspots: usize,
+    /// Continents the placement step grew.
+    pub continents: usize,
+    /// Drainage basins delineated by hydraulic shaping; `None` for
+    /// pipelines without that stage.
+    pub basins: Option<usize>,
+}
+
+/// What one run did and what it consumed.
+///
+/// Fields a pipeline has no stage for are `None`: the overview resolves no
+/// `NoiseParams`, the tile pipeline picks no sea level.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PipelineTrace {
+    /// Stages in execution order.
+    pub stages: Vec<StageTiming>,
+    pub terrain_class: Option<TerrainClass>,
+    /// Glacial class handed to hydraulic shaping.
+    pub glacial_class: Option<GlacialClass>,
+    pub noise: Option<NoiseParams>,
+    pub hydraulic: Option<HydraulicParams>,
+    /// Multiplier applied to the synthesised noise heights.
+    pub relief_scale: Option<f32>,
+    /// Multiplier applied to the erodibility field before shaping.
+    pub erosion_factor: Option<f32>,
+    pub sea_level_km: Option<f32>,
+    pub counts: FeatureCounts,
+}
+
+impl PipelineTrace {
+    /// Sum of all stage times.
+    pub fn total_ms(&self) -> f64 {
+        self.stages.iter().map(|s| s.wall_ms).sum()
+    }
+
+    /// Wall time of `stage`, if it ran.
+    pub fn stage_ms(&self, stage: &str) -> Option<f64> {
+        self.stages
+            .iter()
+            .find(|s| s.stage == stage)
+            .map(|s| s.wall_ms)
+    }
+}
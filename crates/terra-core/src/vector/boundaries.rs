@@ -0,0 +1,125 @@
+//! Plate boundary LineStrings from the smoothed boundary polylines.
+
+use serde_json::json;
+
+use super::{line_geometry, split_antimeridian, Feature, FeatureCollection, Position};
+use crate::plates::boundary_curves::{BoundaryPolyline, BoundaryType};
+
+fn boundary_type_name(t: BoundaryType) -> &'static str {
+    match t {
+        BoundaryType::Convergent => "convergent",
+        BoundaryType::Divergent => "divergent",
+        BoundaryType::Transform => "transform",
+    }
+}
+
+/// One feature per boundary polyline.
+///
+/// Closed loops repeat their first vertex.  Properties: `plate_a`,
+/// `plate_b`, `boundary_type`, `mean_convergent_rate`,
+/// `max_convergent_rate`, `mean_transform_rate` (cm/yr) and `length_km`.
+pub fn plate_boundaries(polylines: &[BoundaryPolyline]) -> FeatureCollection {
+    let features = polylines
+        .iter()
+        .filter_map(|pl| {
+            let mut line: Vec<Position> = pl
+                .vertices
+                .iter()
+                .map(|v| [v.lon.to_degrees(), v.lat.to_degrees()])
+                .collect();
+            if pl.is_closed {
+                if let Some(&first) = line.first() {
+                    line.push(first);
+                }
+            }
+            let geometry = line_geometry(split_antimeridian(&line))?;
+
+            let n = pl.vertices.len().max(1) as f32;
+            let mean_convergent = pl.vertices.iter().map(|v| v.convergent_rate).sum::<f32>() / n;
+            let max_convergent = pl
+                .vertices
+                .iter()
+                .map(|v| v.convergent_rate)
+                .fold(f32::NEG_INFINITY, f32::max);
+            let mean_transform = pl.vertices.iter().map(|v| v.transform_rate).sum::<f32>() / n;
+
+            Some(Feature::new(
+                geometry,
+                json!({
+                    "plate_a": pl.plate_a,
+                    "plate_b": pl.plate_b,
+                    "boundary_type": boundary_type_name(pl.dominant_character),
+                    "mean_convergent_rate": mean_convergent,
+                    "max_convergent_rate": max_convergent,
+                    "mean_transform_rate": mean_transform,
+                    "length_km": pl.arc_lengths.last().copied().unwrap_or(0.0),
+                }),
+            ))
+        })
+        .collect();
+    FeatureCollection::new(features)
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plates::boundary_curves::BoundaryVertex;
+    use crate::vector::Geometry;
+
+    fn vertex(lat_deg: f64, lon_deg: f64, rate: f32) -> BoundaryVertex {
+        BoundaryVertex {
+            x: 0.0,
+            y: 0.0,
+            lat: lat_deg.to_radians(),
+            lon: lon_deg.to_radians(),
+            convergent_rate: rate,
+            transform_rate: 0.5,
+            tangent: (1.0, 0.0),
+            normal: (0.0, 1.0),
+        }
+    }
+
+    #[test]
+    fn boundary_crossing_antimeridian_is_split() {
+        let pl = BoundaryPolyline {
+            plate_a: 1,
+            plate_b: 4,
+            dominant_character: BoundaryType::Convergent,
+            vertices: vec![vertex(0.0, 170.0, 2.0), vertex(5.0, -170.0, 4.0)],
+            is_closed: false,
+            arc_lengths: vec![0.0, 2300.0],
+        };
+        let fc = plate_boundaries(&[pl]);
+        assert_eq!(fc.features.len(), 1);
+        let f = &fc.features[0];
+        assert!(matches!(&f.geometry, Geometry::MultiLineString(parts) if parts.len() == 2));
+        assert_eq!(f.properties["boundary_type"], "convergent");
+        assert_eq!(f.properties["max_convergent_rate"], 4.0);
+        assert_eq!(f.properties["mean_convergent_rate"], 3.0);
+        assert_eq!(f.properties["length_km"], 2300.0);
+    }
+
+    #[test]
+    fn closed_loop_repeats_first_vertex() {
+        let pl = BoundaryPolyline {
+            plate_a: 0,
+            plate_b: 2,
+            dominant_character: BoundaryType::Divergent,
+            vertices: vec![
+                vertex(0.0, 0.0, -1.0),
+                vertex(0.0, 10.0, -1.0),
+                vertex(10.0, 5.0, -1.0),
+            ],
+            is_closed: true,
+            arc_lengths: vec![0.0, 1100.0, 2300.0],
+        };
+        let fc = plate_boundaries(&[pl]);
+        let Geometry::LineString(coords) = &fc.features[0].geometry else {
+            panic!("expected LineString");
+        };
+        assert_eq!(coords.len(), 4);
+        assert_eq!(coords.first(), coords.last());
+    }
+}
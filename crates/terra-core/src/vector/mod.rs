@@ -0,0 +1,271 @@
+//! GeoJSON vector export of rivers, coastlines, basins and plate boundaries.
+//!
+//! Every builder returns a [`FeatureCollection`] that serialises to RFC 7946
+//! GeoJSON with `serde_json`.  Coordinates are `[lon, lat]` in degrees
+//! (EPSG:4326); anything crossing the antimeridian is split at ±180° into a
+//! `MultiLineString` / `MultiPolygon` rather than emitted with longitudes
+//! outside that range.
+
+pub mod boundaries;
+pub mod polygons;
+pub mod rivers;
+
+pub use boundaries::plate_boundaries;
+pub use polygons::{basins, coastlines};
+pub use rivers::rivers;
+
+use serde::Serialize;
+
+use crate::export::GeoBounds;
+use crate::heightfield::HeightField;
+
+/// A `[lon, lat]` position in degrees.
+pub type Position = [f64; 2];
+
+/// GeoJSON geometry (the subset the builders emit).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", content = "coordinates")]
+pub enum Geometry {
+    LineString(Vec<Position>),
+    MultiLineString(Vec<Vec<Position>>),
+    Polygon(Vec<Vec<Position>>),
+    MultiPolygon(Vec<Vec<Vec<Position>>>),
+}
+
+/// GeoJSON Feature with free-form properties.
+#[derive(Debug, Clone, Serialize)]
+pub struct Feature {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    pub geometry: Geometry,
+    pub properties: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Feature {
+    pub fn new(geometry: Geometry, properties: serde_json::Value) -> Self {
+        let properties = match properties {
+            serde_json::Value::Object(map) => map,
+            _ => serde_json::Map::new(),
+        };
+        Self {
+            kind: "Feature",
+            geometry,
+            properties,
+        }
+    }
+}
+
+/// GeoJSON FeatureCollection.
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureCollection {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    pub features: Vec<Feature>,
+}
+
+impl FeatureCollection {
+    pub fn new(features: Vec<Feature>) -> Self {
+        Self {
+            kind: "FeatureCollection",
+            features,
+        }
+    }
+
+    /// Serialise to a GeoJSON string.
+    pub fn to_geojson(&self) -> String {
+        serde_json::to_string(self).expect("GeoJSON features are always serialisable")
+    }
+}
+
+// ── Raster georeferencing ─────────────────────────────────────────────────────
+
+/// Storage order of raster rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowOrder {
+    /// Row 0 at `min_lat` (`HeightField` and everything derived from it).
+    SouthFirst,
+    /// Row 0 at `max_lat` (planet overview fields).
+    NorthFirst,
+}
+
+/// Georeferencing of a row-major raster used to place traced vertices.
+#[derive(Debug, Clone, Copy)]
+pub struct RasterGrid {
+    pub width: usize,
+    pub height: usize,
+    pub bounds: GeoBounds,
+    pub row_order: RowOrder,
+}
+
+impl RasterGrid {
+    /// Grid of a `HeightField` and of the flow / stream / basin rasters built on it.
+    pub fn for_heightfield(hf: &HeightField) -> Self {
+        Self {
+            width: hf.width,
+            height: hf.height,
+            bounds: GeoBounds::from_heightfield(hf),
+            row_order: RowOrder::SouthFirst,
+        }
+    }
+
+    /// Whole-globe equirectangular grid of the planet overview fields.
+    pub fn global(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            bounds: GeoBounds::GLOBAL,
+            row_order: RowOrder::NorthFirst,
+        }
+    }
+
+    /// Unwrapped longitude of the grid line `x` (0 = west edge, `width` = east edge).
+    fn lon_at(&self, x: f64) -> f64 {
+        let b = &self.bounds;
+        b.min_lon + x / self.width as f64 * (b.max_lon - b.min_lon)
+    }
+
+    /// Latitude of the grid line `y` (0 = edge before row 0).
+    fn lat_at(&self, y: f64) -> f64 {
+        let b = &self.bounds;
+        let t = y / self.height as f64;
+        match self.row_order {
+            RowOrder::SouthFirst => b.min_lat + t * (b.max_lat - b.min_lat),
+            RowOrder::NorthFirst => b.max_lat - t * (b.max_lat - b.min_lat),
+        }
+    }
+
+    /// Unwrapped `[lon, lat]` of the centre of cell `(row, col)`.
+    fn cell_centre(&self, row: usize, col: usize) -> Position {
+        [self.lon_at(col as f64 + 0.5), self.lat_at(row as f64 + 0.5)]
+    }
+
+    /// Grid-line index of the antimeridian, if it falls strictly inside the grid.
+    ///
+    /// Rounded to the nearest cell edge so that polygons can be cut along it.
+    fn seam_column(&self) -> Option<(usize, f64)> {
+        let b = &self.bounds;
+        let seam_lon = ((b.min_lon - 180.0) / 360.0).floor() * 360.0 + 540.0;
+        if seam_lon >= b.max_lon {
+            return None;
+        }
+        let x = ((seam_lon - b.min_lon) / (b.max_lon - b.min_lon) * self.width as f64).round();
+        let x = x as usize;
+        (x > 0 && x < self.width).then_some((x, seam_lon))
+    }
+}
+
+// ── Antimeridian handling ─────────────────────────────────────────────────────
+
+/// Wrap a longitude into [-180, 180].
+fn normalize_lon(lon: f64) -> f64 {
+    if (-180.0..=180.0).contains(&lon) {
+        lon
+    } else {
+        (lon + 180.0).rem_euclid(360.0) - 180.0
+    }
+}
+
+/// Split a line of (possibly unwrapped) positions at the antimeridian.
+///
+/// Longitudes are wrapped into [-180, 180]; wherever consecutive vertices
+/// are more than 180° apart the line is cut at the interpolated crossing,
+/// ending on one side at ±180° and resuming on the other.
+pub fn split_antimeridian(line: &[Position]) -> Vec<Vec<Position>> {
+    let mut parts: Vec<Vec<Position>> = Vec::new();
+    let mut current: Vec<Position> = Vec::new();
+    for &[lon, lat] in line {
+        let lon = normalize_lon(lon);
+        if let Some(&[prev_lon, prev_lat]) = current.last() {
+            let delta = lon - prev_lon;
+            if delta.abs() > 180.0 {
+                // Crossing eastward (delta < 0 after wrap) or westward.
+                let (edge, unwrapped) = if delta < 0.0 {
+                    (180.0, lon + 360.0)
+                } else {
+                    (-180.0, lon - 360.0)
+                };
+                let t = (edge - prev_lon) / (unwrapped - prev_lon);
+                let cross_lat = prev_lat + t * (lat - prev_lat);
+                current.push([edge, cross_lat]);
+                parts.push(std::mem::take(&mut current));
+                current.push([-edge, cross_lat]);
+            }
+        }
+        current.push([lon, lat]);
+    }
+    if !current.is_empty() {
+        parts.push(current);
+    }
+    parts.retain(|p| p.len() >= 2);
+    parts
+}
+
+/// `LineString` for a single part, `MultiLineString` for several.
+fn line_geometry(mut parts: Vec<Vec<Position>>) -> Option<Geometry> {
+    match parts.len() {
+        0 => None,
+        1 => Some(Geometry::LineString(parts.pop().unwrap())),
+        _ => Some(Geometry::MultiLineString(parts)),
+    }
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_at_antimeridian_interpolates_crossing() {
+        let parts = split_antimeridian(&[[170.0, 0.0], [190.0, 10.0]]);
+        assert_eq!(
+            parts,
+            vec![
+                vec![[170.0, 0.0], [180.0, 5.0]],
+                vec![[-180.0, 5.0], [-170.0, 10.0]]
+            ]
+        );
+        // Westward crossing from already-wrapped coordinates.
+        let parts = split_antimeridian(&[[-175.0, 0.0], [175.0, 0.0]]);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].last().unwrap()[0], -180.0);
+        assert_eq!(parts[1][0][0], 180.0);
+    }
+
+    #[test]
+    fn line_without_crossing_is_untouched() {
+        let line = vec![[10.0, 0.0], [12.0, 1.0], [11.0, 2.0]];
+        assert_eq!(split_antimeridian(&line), vec![line]);
+    }
+
+    #[test]
+    fn seam_column_located_for_unwrapped_bounds() {
+        let grid = RasterGrid {
+            width: 8,
+            height: 4,
+            bounds: GeoBounds {
+                min_lon: 176.0,
+                max_lon: 184.0,
+                min_lat: 0.0,
+                max_lat: 4.0,
+            },
+            row_order: RowOrder::SouthFirst,
+        };
+        assert_eq!(grid.seam_column(), Some((4, 180.0)));
+        assert_eq!(RasterGrid::global(8, 4).seam_column(), None);
+    }
+
+    #[test]
+    fn feature_collection_serialises_as_geojson() {
+        let fc = FeatureCollection::new(vec![Feature::new(
+            Geometry::LineString(vec![[0.0, 0.0], [1.0, 1.0]]),
+            serde_json::json!({ "order": 2 }),
+        )]);
+        let v: serde_json::Value = serde_json::from_str(&fc.to_geojson()).unwrap();
+        assert_eq!(v["type"], "FeatureCollection");
+        assert_eq!(v["features"][0]["type"], "Feature");
+        assert_eq!(v["features"][0]["geometry"]["type"], "LineString");
+        assert_eq!(v["features"][0]["geometry"]["coordinates"][1][0], 1.0);
+        assert_eq!(v["features"][0]["properties"]["order"], 2);
+    }
+}
@@ -0,0 +1,414 @@
+//! Region outlines traced from label rasters: coastlines and drainage basins.
+//!
+//! Each cell side separating two labels becomes a directed edge running
+//! clockwise around its cell (screen orientation, row index increasing
+//! downward).  Edges are chained into rings, turning right at saddle vertices
+//! so that diagonally touching cells form separate (4-connected) regions.
+//! Clockwise rings are exteriors, counter-clockwise rings are holes.  Rings
+//! are cut at the antimeridian by treating it as a region border.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde_json::json;
+
+use super::{Feature, FeatureCollection, Geometry, Position, RasterGrid};
+use crate::heightfield::HeightField;
+use crate::hydraulic::basins::{basin_labels, DrainageBasin};
+use crate::hydraulic::flow_routing::FlowField;
+
+/// Grid vertex `(x, y)`: `x` in `0..=width`, `y` in `0..=height`.
+type Vertex = (i64, i64);
+
+/// A traced polygon: exterior ring first, then holes, in grid vertices.
+struct GridPolygon {
+    rings: Vec<Vec<Vertex>>,
+    area_cells: f64,
+}
+
+/// Shoelace area in grid coordinates; positive for clockwise (screen) rings.
+fn signed_area(ring: &[Vertex]) -> f64 {
+    let n = ring.len();
+    let mut acc = 0i64;
+    for i in 0..n {
+        let (x0, y0) = ring[i];
+        let (x1, y1) = ring[(i + 1) % n];
+        acc += x0 * y1 - x1 * y0;
+    }
+    acc as f64 / 2.0
+}
+
+/// Even-odd point-in-polygon test in grid coordinates.
+fn contains(ring: &[Vertex], px: f64, py: f64) -> bool {
+    let n = ring.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (xi, yi) = (ring[i].0 as f64, ring[i].1 as f64);
+        let (xj, yj) = (
+            ring[(i + n - 1) % n].0 as f64,
+            ring[(i + n - 1) % n].1 as f64,
+        );
+        if (yi > py) != (yj > py) && px < (xj - xi) * (py - yi) / (yj - yi) + xi {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Drop vertices where the ring continues straight on.
+fn simplify(ring: Vec<Vertex>) -> Vec<Vertex> {
+    let n = ring.len();
+    (0..n)
+        .filter(|&i| {
+            let (px, py) = ring[(i + n - 1) % n];
+            let (x, y) = ring[i];
+            let (nx, ny) = ring[(i + 1) % n];
+            (x - px) * (ny - y) - (y - py) * (nx - x) != 0
+        })
+        .map(|i| ring[i])
+        .collect()
+}
+
+/// Chain one label's directed edges into closed rings.
+fn chain_rings(edges: &[(Vertex, Vertex)]) -> Vec<Vec<Vertex>> {
+    let mut outgoing: HashMap<Vertex, Vec<usize>> = HashMap::new();
+    for (i, &(a, _)) in edges.iter().enumerate() {
+        outgoing.entry(a).or_default().push(i);
+    }
+    let mut used = vec![false; edges.len()];
+    let mut rings = Vec::new();
+
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+        let mut ring = vec![edges[first].0];
+        let mut cur = first;
+        loop {
+            used[cur] = true;
+            let (a, b) = edges[cur];
+            let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+            // Preference: right turn, straight on, left turn (y points down).
+            let rank = |e: usize| {
+                let (c, d) = edges[e];
+                let (ex, ey) = (d.0 - c.0, d.1 - c.1);
+                if (ex, ey) == (-dy, dx) {
+                    0
+                } else if (ex, ey) == (dx, dy) {
+                    1
+                } else {
+                    2
+                }
+            };
+            let next = outgoing
+                .get(&b)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|&e| !used[e] || e == first)
+                .min_by_key(|&e| rank(e));
+            match next {
+                Some(e) if e == first => break,
+                Some(e) => {
+                    ring.push(b);
+                    cur = e;
+                }
+                None => break,
+            }
+        }
+        rings.push(simplify(ring));
+    }
+    rings.retain(|r| r.len() >= 4);
+    rings
+}
+
+/// Trace every region of the labels accepted by `keep`, grouped by label.
+fn trace_regions(
+    labels: &[u32],
+    grid: &RasterGrid,
+    keep: impl Fn(u32) -> bool,
+) -> BTreeMap<u32, Vec<GridPolygon>> {
+    let (w, h) = (grid.width, grid.height);
+    let seam = grid.seam_column().map(|(x, _)| x);
+    let differs = |i: usize, r: isize, c: isize| {
+        r < 0
+            || c < 0
+            || r >= h as isize
+            || c >= w as isize
+            || labels[i] != labels[r as usize * w + c as usize]
+    };
+
+    let mut edges: BTreeMap<u32, Vec<(Vertex, Vertex)>> = BTreeMap::new();
+    for r in 0..h {
+        for c in 0..w {
+            let i = r * w + c;
+            if !keep(labels[i]) {
+                continue;
+            }
+            let (ri, ci) = (r as isize, c as isize);
+            let (x, y) = (c as i64, r as i64);
+            let e = edges.entry(labels[i]).or_default();
+            if differs(i, ri - 1, ci) {
+                e.push(((x, y), (x + 1, y)));
+            }
+            if differs(i, ri, ci + 1) || seam == Some(c + 1) {
+                e.push(((x + 1, y), (x + 1, y + 1)));
+            }
+            if differs(i, ri + 1, ci) {
+                e.push(((x + 1, y + 1), (x, y + 1)));
+            }
+            if differs(i, ri, ci - 1) || seam == Some(c) {
+                e.push(((x, y + 1), (x, y)));
+            }
+        }
+    }
+
+    edges
+        .into_iter()
+        .map(|(label, label_edges)| {
+            let (exteriors, holes): (Vec<_>, Vec<_>) = chain_rings(&label_edges)
+                .into_iter()
+                .partition(|ring| signed_area(ring) > 0.0);
+            let mut polygons: Vec<GridPolygon> = exteriors
+                .into_iter()
+                .map(|ring| GridPolygon {
+                    area_cells: signed_area(&ring),
+                    rings: vec![ring],
+                })
+                .collect();
+            for hole in holes {
+                // A region cell just inside the hole's first edge (on its right).
+                let ((ax, ay), (bx, by)) = (hole[0], hole[1 % hole.len()]);
+                let len = (((bx - ax).pow(2) + (by - ay).pow(2)) as f64).sqrt();
+                let (ux, uy) = ((bx - ax) as f64 / len, (by - ay) as f64 / len);
+                let px = ax as f64 + ux * 0.5 - uy * 0.5;
+                let py = ay as f64 + uy * 0.5 + ux * 0.5;
+                let owner = polygons
+                    .iter_mut()
+                    .filter(|p| contains(&p.rings[0], px, py))
+                    .min_by(|a, b| a.area_cells.total_cmp(&b.area_cells));
+                if let Some(owner) = owner {
+                    owner.area_cells += signed_area(&hole);
+                    owner.rings.push(hole);
+                }
+            }
+            (label, polygons)
+        })
+        .collect()
+}
+
+/// Convert a traced polygon to closed `[lon, lat]` rings (RFC 7946 winding).
+///
+/// The whole polygon is shifted by a multiple of 360° so that it lands in
+/// [-180, 180]; vertices on the seam column snap exactly to ±180°.
+fn to_geo_rings(poly: &GridPolygon, grid: &RasterGrid) -> Vec<Vec<Position>> {
+    let seam = grid.seam_column();
+    let lon_of = |x: i64| match seam {
+        Some((sx, seam_lon)) if x as usize == sx => seam_lon,
+        _ => grid.lon_at(x as f64),
+    };
+    let exterior = &poly.rings[0];
+    let mean_lon = exterior.iter().map(|&(x, _)| lon_of(x)).sum::<f64>() / exterior.len() as f64;
+    let shift = -((mean_lon + 180.0) / 360.0).floor() * 360.0;
+
+    poly.rings
+        .iter()
+        .enumerate()
+        .map(|(k, ring)| {
+            let mut geo: Vec<Position> = ring
+                .iter()
+                .map(|&(x, y)| {
+                    [
+                        (lon_of(x) + shift).clamp(-180.0, 180.0),
+                        grid.lat_at(y as f64),
+                    ]
+                })
+                .collect();
+            // Exterior counter-clockwise, holes clockwise (lon/lat, y up).
+            let area: f64 = (0..geo.len())
+                .map(|i| {
+                    let [x0, y0] = geo[i];
+                    let [x1, y1] = geo[(i + 1) % geo.len()];
+                    x0 * y1 - x1 * y0
+                })
+                .sum();
+            if (k == 0) != (area > 0.0) {
+                geo.reverse();
+            }
+            geo.push(geo[0]);
+            geo
+        })
+        .collect()
+}
+
+/// Land polygons traced from an ocean mask (`true` = ocean).
+///
+/// One feature per connected landmass of at least `min_area_cells` cells,
+/// with lakes (enclosed ocean cells) as holes.  Landmasses cut by the
+/// antimeridian come out as one polygon on each side.
+/// Property: `area_cells`.
+pub fn coastlines(
+    ocean_mask: &[bool],
+    grid: &RasterGrid,
+    min_area_cells: f64,
+) -> FeatureCollection {
+    let labels: Vec<u32> = ocean_mask.iter().map(|&o| u32::from(!o)).collect();
+    let features = trace_regions(&labels, grid, |l| l == 1)
+        .into_values()
+        .flatten()
+        .filter(|p| p.area_cells >= min_area_cells)
+        .map(|p| {
+            Feature::new(
+                Geometry::Polygon(to_geo_rings(&p, grid)),
+                json!({ "area_cells": p.area_cells }),
+            )
+        })
+        .collect();
+    FeatureCollection::new(features)
+}
+
+/// Drainage basin polygons with their statistics.
+///
+/// `flow` and `basins` must come from the hydraulic pass over `hf`; basins
+/// smaller than `min_area_cells` are skipped.  Properties mirror
+/// `DrainageBasin`.
+pub fn basins(
+    hf: &HeightField,
+    flow: &FlowField,
+    basins: &[DrainageBasin],
+    min_area_cells: u32,
+) -> FeatureCollection {
+    let grid = RasterGrid::for_heightfield(hf);
+    let labels = basin_labels(flow);
+    let keep = |l: u32| {
+        basins
+            .get(l as usize)
+            .is_some_and(|b| b.area_cells >= min_area_cells)
+    };
+
+    let features = trace_regions(&labels, &grid, keep)
+        .into_iter()
+        .filter_map(|(label, polygons)| {
+            let b = &basins[label as usize];
+            let mut rings: Vec<_> = polygons.iter().map(|p| to_geo_rings(p, &grid)).collect();
+            let geometry = match rings.len() {
+                0 => return None,
+                1 => Geometry::Polygon(rings.pop().unwrap()),
+                _ => Geometry::MultiPolygon(rings),
+            };
+            Some(Feature::new(
+                geometry,
+                json!({
+                    "id": b.id,
+                    "area_cells": b.area_cells,
+                    "hypsometric_integral": b.hypsometric_integral,
+                    "elongation_ratio": b.elongation_ratio,
+                    "circularity": b.circularity,
+                    "mean_slope": b.mean_slope,
+                }),
+            ))
+        })
+        .collect();
+    FeatureCollection::new(features)
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::GeoBounds;
+    use crate::vector::RowOrder;
+
+    fn grid(w: usize, h: usize, min_lon: f64, max_lon: f64) -> RasterGrid {
+        RasterGrid {
+            width: w,
+            height: h,
+            bounds: GeoBounds {
+                min_lon,
+                max_lon,
+                min_lat: 0.0,
+                max_lat: h as f64,
+            },
+            row_order: RowOrder::NorthFirst,
+        }
+    }
+
+    fn mask(rows: &[&str]) -> Vec<bool> {
+        rows.iter()
+            .flat_map(|r| r.chars().map(|ch| ch == '.'))
+            .collect()
+    }
+
+    #[test]
+    fn island_with_lake_has_one_hole() {
+        let ocean = mask(&[
+            ".......", //
+            ".#####.", //
+            ".#...#.", //
+            ".#.#.#.", //
+            ".#...#.", //
+            ".#####.", //
+            ".......",
+        ]);
+        let fc = coastlines(&ocean, &grid(7, 7, 0.0, 7.0), 1.0);
+        // Outer ring island plus the single-cell island inside the lake.
+        assert_eq!(fc.features.len(), 2);
+        let with_hole = fc
+            .features
+            .iter()
+            .find_map(|f| match &f.geometry {
+                Geometry::Polygon(rings) if rings.len() == 2 => Some(rings.clone()),
+                _ => None,
+            })
+            .expect("ring island must carry the lake as a hole");
+        assert_eq!(with_hole[0].first(), with_hole[0].last());
+        assert_eq!(
+            with_hole[0].len(),
+            5,
+            "square exterior after simplification"
+        );
+        let areas: Vec<f64> = fc
+            .features
+            .iter()
+            .map(|f| f.properties["area_cells"].as_f64().unwrap())
+            .collect();
+        assert!(areas.contains(&16.0) && areas.contains(&1.0), "{areas:?}");
+    }
+
+    #[test]
+    fn diagonal_cells_are_separate_regions() {
+        let ocean = mask(&["#.", ".#"]);
+        let fc = coastlines(&ocean, &grid(2, 2, 0.0, 2.0), 1.0);
+        assert_eq!(fc.features.len(), 2);
+    }
+
+    #[test]
+    fn exterior_rings_are_counter_clockwise() {
+        let ocean = mask(&["....", ".##.", "...."]);
+        let fc = coastlines(&ocean, &grid(4, 3, 0.0, 4.0), 1.0);
+        let Geometry::Polygon(rings) = &fc.features[0].geometry else {
+            panic!("expected Polygon");
+        };
+        let ring = &rings[0];
+        let area: f64 = ring
+            .windows(2)
+            .map(|p| p[0][0] * p[1][1] - p[1][0] * p[0][1])
+            .sum();
+        assert!(area > 0.0, "exterior must wind counter-clockwise");
+    }
+
+    #[test]
+    fn landmass_across_antimeridian_is_split() {
+        // Grid spans 176°..184°; the seam sits between columns 3 and 4.
+        let ocean = mask(&["........", "..####..", "........"]);
+        let fc = coastlines(&ocean, &grid(8, 3, 176.0, 184.0), 1.0);
+        assert_eq!(fc.features.len(), 2);
+        for f in &fc.features {
+            let Geometry::Polygon(rings) = &f.geometry else {
+                panic!("expected Polygon");
+            };
+            assert!(rings[0].iter().all(|p| (-180.0..=180.0).contains(&p[0])));
+            assert!(rings[0].iter().any(|p| p[0].abs() == 180.0));
+        }
+    }
+}
@@ -0,0 +1,153 @@
+//! River LineStrings from the D8 stream network.
+//!
+//! The network is cut into reaches of constant Strahler order: a reach starts
+//! at a channel head or a confluence and runs downstream until the next
+//! confluence, which it includes as its final vertex so reaches connect.
+
+use serde_json::json;
+
+use super::{line_geometry, split_antimeridian, Feature, FeatureCollection, RasterGrid};
+use crate::heightfield::HeightField;
+use crate::hydraulic::flow_routing::{FlowField, D8_OFFSETS};
+use crate::hydraulic::stream_network::StreamNetwork;
+
+/// D8 receiver of cell `i`, if it flows to an in-bounds cell.
+fn receiver(flow: &FlowField, i: usize) -> Option<usize> {
+    let code = flow.direction[i];
+    if code == 0 {
+        return None;
+    }
+    let (dr, dc) = D8_OFFSETS[(code - 1) as usize];
+    let nr = (i / flow.width) as isize + dr;
+    let nc = (i % flow.width) as isize + dc;
+    if nr < 0 || nc < 0 || nr >= flow.height as isize || nc >= flow.width as isize {
+        None
+    } else {
+        Some(nr as usize * flow.width + nc as usize)
+    }
+}
+
+/// Build river reaches of Strahler order ≥ `min_order` as GeoJSON LineStrings.
+///
+/// `flow` and `network` must come from the hydraulic pass over `hf`.
+/// Properties: `strahler_order`, `accumulation_cells` (upstream area at the
+/// reach's downstream end) and `length_cells`.
+pub fn rivers(
+    hf: &HeightField,
+    flow: &FlowField,
+    network: &StreamNetwork,
+    min_order: u8,
+) -> FeatureCollection {
+    let grid = RasterGrid::for_heightfield(hf);
+    let n = flow.width * flow.height;
+    let stream = &network.stream_cells;
+
+    let mut stream_donors = vec![0u8; n];
+    for i in (0..n).filter(|&i| stream[i]) {
+        if let Some(j) = receiver(flow, i) {
+            if stream[j] {
+                stream_donors[j] = stream_donors[j].saturating_add(1);
+            }
+        }
+    }
+
+    let mut features = Vec::new();
+    for head in (0..n).filter(|&i| stream[i] && stream_donors[i] != 1) {
+        let order = network.orders[head];
+        if order < min_order {
+            continue;
+        }
+
+        let mut path = vec![head];
+        let mut outlet = head;
+        let mut cur = head;
+        while let Some(next) = receiver(flow, cur) {
+            if !stream[next] {
+                break;
+            }
+            path.push(next);
+            if stream_donors[next] != 1 || network.orders[next] != order {
+                break;
+            }
+            outlet = next;
+            cur = next;
+        }
+        if path.len() < 2 {
+            continue;
+        }
+
+        let line: Vec<_> = path
+            .iter()
+            .map(|&i| grid.cell_centre(i / flow.width, i % flow.width))
+            .collect();
+        if let Some(geometry) = line_geometry(split_antimeridian(&line)) {
+            features.push(Feature::new(
+                geometry,
+                json!({
+                    "strahler_order": order,
+                    "accumulation_cells": flow.accumulation[outlet],
+                    "length_cells": path.len() - 1,
+                }),
+            ));
+        }
+    }
+
+    FeatureCollection::new(features)
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hydraulic::flow_routing::compute_d8_flow;
+    use crate::hydraulic::stream_network::extract_stream_network;
+
+    /// V-shaped valley draining toward row 0: one trunk river down the axis.
+    fn valley(rows: usize, cols: usize) -> HeightField {
+        let mut hf = HeightField::new(cols, rows, 10.0, 11.0, 40.0, 41.0, 0.0);
+        let mid = cols / 2;
+        for r in 0..rows {
+            for c in 0..cols {
+                let across = (c as f32 - mid as f32).abs() * 5.0;
+                hf.set(r, c, r as f32 * 2.0 + across);
+            }
+        }
+        hf
+    }
+
+    #[test]
+    fn valley_yields_connected_reaches_inside_bounds() {
+        let hf = valley(32, 33);
+        let flow = compute_d8_flow(&hf);
+        let network = extract_stream_network(&flow, 20);
+        let fc = rivers(&hf, &flow, &network, 1);
+        assert!(!fc.features.is_empty(), "valley must produce river reaches");
+
+        let mut max_acc = 0u64;
+        for f in &fc.features {
+            let super::super::Geometry::LineString(coords) = &f.geometry else {
+                panic!("no antimeridian here, expected LineString");
+            };
+            assert!(coords.len() >= 2);
+            for &[lon, lat] in coords {
+                assert!((10.0..=11.0).contains(&lon) && (40.0..=41.0).contains(&lat));
+            }
+            max_acc = max_acc.max(f.properties["accumulation_cells"].as_u64().unwrap());
+        }
+        assert!(max_acc >= 20);
+    }
+
+    #[test]
+    fn min_order_filters_reaches() {
+        let hf = valley(32, 33);
+        let flow = compute_d8_flow(&hf);
+        let network = extract_stream_network(&flow, 20);
+        let all = rivers(&hf, &flow, &network, 1).features.len();
+        let none = rivers(&hf, &flow, &network, network.max_order + 1)
+            .features
+            .len();
+        assert!(all > 0);
+        assert_eq!(none, 0);
+    }
+}
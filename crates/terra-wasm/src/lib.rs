@@ -2,13 +2,21 @@
 //! Phase 7, Task P7.2.
 
 use serde::{Deserialize, Serialize};
+use terra_core::coords::TileAddr;
+use terra_core::error::{check_location, TerraError};
 use terra_core::generator::{
-    derive_debug_params, generate_at_location, GlobalParams, PlanetGenerator,
+    derive_debug_params, try_generate_at_location, GenerationConfig, GlobalParams,
+    LocationTileResult, PlanetGenerator,
 };
+use terra_core::inverse::{self, DesignTargets};
 use terra_core::metrics::score::RealismScore;
-use terra_core::noise::params::GlacialClass;
-use terra_core::planet::{generate_planet_overview, OVERVIEW_HEIGHT, OVERVIEW_WIDTH};
+use terra_core::noise::params::{GlacialClass, TerrainClass};
+use terra_core::planet::{try_generate_planet_overview, Planet, PlanetOverview};
 use terra_core::plates::regime_field::TectonicRegime;
+use terra_core::progress::{CancelToken, ProgressEvent, RunControl};
+use terra_core::recipe::{Preset, Recipe};
+use terra_core::save::{self, SaveError};
+use terra_core::trace::PipelineTrace;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen(start)]
@@ -42,6 +50,7 @@ struct PlanetResultJs {
     height: u32,
     score: RealismScoreJs,
     generation_time_ms: u64,
+    trace: PipelineTrace,
 }
 
 fn regime_to_u8(r: TectonicRegime) -> u8 {
@@ -61,36 +70,174 @@ fn score_to_js(s: RealismScore) -> RealismScoreJs {
             .metrics
             .into_iter()
             .map(|m| MetricScoreJs {
-                name: m.name.to_owned(),
+                name: m.name,
                 raw_value: m.raw_value,
                 score_0_1: m.score_0_1,
                 passed: m.passed,
-                subsystem: m.subsystem.to_owned(),
+                subsystem: m.subsystem,
             })
             .collect(),
     }
 }
 
+// ── Progress callbacks ────────────────────────────────────────────────────────
+
+/// `RunControl` forwarding progress to an optional JS callback.
+///
+/// The callback receives `{ stage, stage_index, stage_count, stage_fraction,
+/// overall }`; returning `false` cancels the run at the next stage, which then
+/// rejects with a `TerraError` of kind `"cancelled"`.  The callback runs
+/// synchronously inside the wasm call, so only state it can read without
+/// yielding to the event loop can trigger cancellation.
+fn js_run_control(on_progress: Option<js_sys::Function>) -> RunControl {
+    let Some(callback) = on_progress else {
+        return RunControl::new();
+    };
+    let token = CancelToken::new();
+    let cancel = token.clone();
+    RunControl::new()
+        .with_cancel_token(token)
+        .with_observer(move |event: &ProgressEvent| {
+            let Ok(event) = serde_wasm_bindgen::to_value(event) else {
+                return;
+            };
+            if let Ok(ret) = callback.call1(&JsValue::NULL, &event) {
+                if ret == JsValue::FALSE {
+                    cancel.cancel();
+                }
+            }
+        })
+}
+
+/// Machine-readable fields of a `TerraError`, copied onto the JS `Error`.
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum TerraErrorJs {
+    OutOfRange {
+        param: String,
+        value: f64,
+        min: f64,
+        max: f64,
+    },
+    NotFinite {
+        param: String,
+    },
+    EmptyGrid {
+        grid: &'static str,
+        width: usize,
+        height: usize,
+    },
+    FieldSizeMismatch {
+        field: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    GridMismatch {
+        field: &'static str,
+        width: usize,
+        height: usize,
+        actual_width: usize,
+        actual_height: usize,
+    },
+    InvalidLocation {
+        lat: f32,
+        lon: f32,
+    },
+    InvalidTile {
+        zoom: u32,
+        x: u32,
+        y: u32,
+    },
+    Cancelled,
+}
+
+/// A JS `Error` named `"TerraError"` carrying the message plus a `kind` and
+/// its details, e.g. `{ kind: "out_of_range", param: "glaciation", value: 1.5,
+/// min: 0, max: 1 }`.
+fn terra_error_to_js(e: TerraError) -> JsValue {
+    let error = js_sys::Error::new(&e.to_string());
+    error.set_name("TerraError");
+    let details = match e {
+        TerraError::OutOfRange(r) => TerraErrorJs::OutOfRange {
+            param: r.name,
+            value: r.value,
+            min: r.min,
+            max: r.max,
+        },
+        TerraError::NotFinite { name } => TerraErrorJs::NotFinite { param: name },
+        TerraError::EmptyGrid {
+            grid,
+            width,
+            height,
+        } => TerraErrorJs::EmptyGrid {
+            grid,
+            width,
+            height,
+        },
+        TerraError::FieldSizeMismatch {
+            field,
+            expected,
+            actual,
+            ..
+        } => TerraErrorJs::FieldSizeMismatch {
+            field,
+            expected,
+            actual,
+        },
+        TerraError::GridMismatch {
+            field,
+            width,
+            height,
+            actual_width,
+            actual_height,
+        } => TerraErrorJs::GridMismatch {
+            field,
+            width,
+            height,
+            actual_width,
+            actual_height,
+        },
+        TerraError::InvalidLocation { lat, lon } => TerraErrorJs::InvalidLocation { lat, lon },
+        TerraError::InvalidTile { zoom, x, y } => TerraErrorJs::InvalidTile { zoom, x, y },
+        TerraError::Cancelled => TerraErrorJs::Cancelled,
+    };
+    if let Ok(details) = serde_wasm_bindgen::to_value(&details) {
+        js_sys::Object::assign(&error, details.unchecked_ref());
+    }
+    error.into()
+}
+
+fn save_error_to_js(e: SaveError) -> JsValue {
+    JsValue::from_str(&format!("Invalid save file: {e}"))
+}
+
 // ── Public WASM API ───────────────────────────────────────────────────────────
 
+/// Full-globe tile pipeline.  `on_progress` is optional; see `js_run_control`.
 #[wasm_bindgen]
-pub fn generate(params_js: JsValue) -> Result<JsValue, JsValue> {
+pub fn generate(
+    params_js: JsValue,
+    on_progress: Option<js_sys::Function>,
+) -> Result<JsValue, JsValue> {
     let params: GlobalParams = serde_wasm_bindgen::from_value(params_js)
         .map_err(|e| JsValue::from_str(&format!("Invalid params: {e}")))?;
 
     // Use JS Date.now() for timing — std::time::Instant panics on wasm32.
     let t0 = js_sys::Date::now();
-    let result = PlanetGenerator::new().generate(&params);
+    let result = PlanetGenerator::new()
+        .try_generate(&params, &mut js_run_control(on_progress))
+        .map_err(terra_error_to_js)?;
     let generation_time_ms = (js_sys::Date::now() - t0) as u64;
 
     let js_result = PlanetResultJs {
         heights: result.heightfield.data,
         regimes: result.regime_field.into_iter().map(regime_to_u8).collect(),
         map_field: result.map_field,
-        width: terra_core::generator::GRID_WIDTH as u32,
-        height: terra_core::generator::GRID_HEIGHT as u32,
+        width: result.heightfield.width as u32,
+        height: result.heightfield.height as u32,
         score: score_to_js(result.score),
         generation_time_ms,
+        trace: result.trace,
     };
 
     serde_wasm_bindgen::to_value(&js_result)
@@ -129,6 +276,7 @@ struct PlanetOverviewJs {
     width: u32,
     height: u32,
     generation_time_ms: u64,
+    trace: PipelineTrace,
 }
 
 fn glacial_to_u8(g: GlacialClass) -> u8 {
@@ -139,28 +287,20 @@ fn glacial_to_u8(g: GlacialClass) -> u8 {
     }
 }
 
-// ── generate_overview binding ─────────────────────────────────────────────────
-
-/// Generate a full 1024×512 planet overview from `GlobalParams`.
-///
-/// Separate from `generate()` — the existing tile pipeline is unchanged.
-#[wasm_bindgen]
-pub fn generate_overview(params_js: JsValue) -> Result<JsValue, JsValue> {
-    let params: GlobalParams = serde_wasm_bindgen::from_value(params_js)
-        .map_err(|e| JsValue::from_str(&format!("Invalid params: {e}")))?;
-
-    let t0 = js_sys::Date::now();
-    let overview = generate_planet_overview(&params);
-    let generation_time_ms = (js_sys::Date::now() - t0) as u64;
-
-    let js = PlanetOverviewJs {
-        elevations: overview.elevations,
-        ocean_mask: overview.ocean_mask,
+fn overview_to_js(overview: &PlanetOverview, generation_time_ms: u64) -> PlanetOverviewJs {
+    PlanetOverviewJs {
+        elevations: overview.elevations.clone(),
+        ocean_mask: overview.ocean_mask.clone(),
         sea_level_km: overview.sea_level_km,
-        regimes: overview.regimes.into_iter().map(regime_to_u8).collect(),
-        map_field: overview.map_field,
-        erodibility_field: overview.erodibility_field,
-        glaciation: overview.glaciation.into_iter().map(glacial_to_u8).collect(),
+        regimes: overview.regimes.iter().copied().map(regime_to_u8).collect(),
+        map_field: overview.map_field.clone(),
+        erodibility_field: overview.erodibility_field.clone(),
+        glaciation: overview
+            .glaciation
+            .iter()
+            .copied()
+            .map(glacial_to_u8)
+            .collect(),
         planet_metrics: PlanetMetricsJs {
             all_pass: overview.planet_metrics.all_pass,
             metrics: overview
@@ -176,10 +316,37 @@ pub fn generate_overview(params_js: JsValue) -> Result<JsValue, JsValue> {
                 })
                 .collect(),
         },
-        width: OVERVIEW_WIDTH as u32,
-        height: OVERVIEW_HEIGHT as u32,
+        width: overview.width as u32,
+        height: overview.height as u32,
         generation_time_ms,
-    };
+        trace: overview.trace.clone(),
+    }
+}
+
+// ── generate_overview binding ─────────────────────────────────────────────────
+
+/// Generate a full 1024×512 planet overview from `GlobalParams`.
+///
+/// Separate from `generate()` — the existing tile pipeline is unchanged.
+/// `on_progress` is optional; see `js_run_control`.
+#[wasm_bindgen]
+pub fn generate_overview(
+    params_js: JsValue,
+    on_progress: Option<js_sys::Function>,
+) -> Result<JsValue, JsValue> {
+    let params: GlobalParams = serde_wasm_bindgen::from_value(params_js)
+        .map_err(|e| JsValue::from_str(&format!("Invalid params: {e}")))?;
+
+    let t0 = js_sys::Date::now();
+    let overview = try_generate_planet_overview(
+        &params,
+        &GenerationConfig::default(),
+        &mut js_run_control(on_progress),
+    )
+    .map_err(terra_error_to_js)?;
+    let generation_time_ms = (js_sys::Date::now() - t0) as u64;
+
+    let js = overview_to_js(&overview, generation_time_ms);
 
     serde_wasm_bindgen::to_value(&js)
         .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
@@ -212,7 +379,7 @@ struct LocationTileResultJs {
     sampled_fields: SampledFieldsJs,
 }
 
-fn terrain_class_to_str(tc: terra_core::noise::params::TerrainClass) -> String {
+fn terrain_class_to_str(tc: TerrainClass) -> String {
     format!("{tc:?}")
 }
 
@@ -236,6 +403,49 @@ fn glacial_to_str(g: GlacialClass) -> String {
     .to_owned()
 }
 
+fn sampled_fields_to_js(
+    terrain_class: TerrainClass,
+    regime: TectonicRegime,
+    map_mm: f32,
+    erodibility: f32,
+    grain_angle: f32,
+    grain_intensity: f32,
+    glaciation: GlacialClass,
+) -> SampledFieldsJs {
+    SampledFieldsJs {
+        terrain_class: terrain_class_to_str(terrain_class),
+        local_regime: regime_to_str(regime),
+        local_map_mm: map_mm,
+        local_erodibility: erodibility,
+        local_grain_angle: grain_angle,
+        local_grain_intensity: grain_intensity,
+        local_glaciation: glacial_to_str(glaciation),
+    }
+}
+
+fn location_tile_to_js(r: LocationTileResult, generation_time_ms: u64) -> LocationTileResultJs {
+    LocationTileResultJs {
+        heights: r.heightfield.data,
+        regimes: r.regime_field.into_iter().map(regime_to_u8).collect(),
+        map_field: r.map_field,
+        width: r.heightfield.width as u32,
+        height: r.heightfield.height as u32,
+        score: score_to_js(r.score),
+        generation_time_ms,
+        lat: r.lat,
+        lon: r.lon,
+        sampled_fields: sampled_fields_to_js(
+            r.terrain_class,
+            r.local_regime,
+            r.local_map_mm,
+            r.local_erodibility,
+            r.local_grain_angle,
+            r.local_grain_intensity,
+            r.local_glaciation,
+        ),
+    }
+}
+
 /// Generate a tile characterised by the planet fields at the given lat/lon.
 ///
 /// Runs the full planet simulation at 1024×512, samples spatial fields at the
@@ -250,34 +460,288 @@ pub fn generate_at_location_wasm(
         .map_err(|e| JsValue::from_str(&format!("Invalid params: {e}")))?;
 
     let t0 = js_sys::Date::now();
-    let r = generate_at_location(&params, lat, lon);
+    let r = try_generate_at_location(&params, &GenerationConfig::default(), lat, lon)
+        .map_err(terra_error_to_js)?;
     let generation_time_ms = (js_sys::Date::now() - t0) as u64;
 
-    let js = LocationTileResultJs {
-        heights: r.heightfield.data,
-        regimes: r.regime_field.into_iter().map(regime_to_u8).collect(),
-        map_field: r.map_field,
-        width: terra_core::generator::GRID_WIDTH as u32,
-        height: terra_core::generator::GRID_HEIGHT as u32,
-        score: score_to_js(r.score),
-        generation_time_ms,
-        lat: r.lat,
-        lon: r.lon,
-        sampled_fields: SampledFieldsJs {
-            terrain_class: terrain_class_to_str(r.terrain_class),
-            local_regime: regime_to_str(r.local_regime),
-            local_map_mm: r.local_map_mm,
-            local_erodibility: r.local_erodibility,
-            local_grain_angle: r.local_grain_angle,
-            local_grain_intensity: r.local_grain_intensity,
-            local_glaciation: glacial_to_str(r.local_glaciation),
-        },
-    };
+    let js = location_tile_to_js(r, generation_time_ms);
+
+    serde_wasm_bindgen::to_value(&js)
+        .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
+}
+
+// ── Planet session handle ─────────────────────────────────────────────────────
+
+#[derive(Serialize)]
+struct FieldSampleJs {
+    lat: f32,
+    lon: f32,
+    elevation_km: f32,
+    is_ocean: bool,
+    sampled_fields: SampledFieldsJs,
+}
+
+#[derive(Serialize)]
+struct AddressedTileJs {
+    /// Heights in metres relative to sea level; Web Mercator rows, south first.
+    heights: Vec<f32>,
+    width: u32,
+    height: u32,
+    zoom: u32,
+    x: u32,
+    y: u32,
+    min_lon: f64,
+    max_lon: f64,
+    min_lat: f64,
+    max_lat: f64,
+    generation_time_ms: u64,
+}
+
+/// Stateful planet handle: runs the plate + climate simulation once, then
+/// serves overview, tile and field queries from memory.
+///
+/// ```js
+/// const planet = new Planet(params);
+/// const overview = planet.overview();
+/// const bytes = planet.save();             // reopen later with Planet.load(bytes)
+/// const tile = planet.tile_at(lat, lon);   // no plate re-simulation
+/// planet.set_params({ ...params, glaciation: 0.8 }); // climate stages only
+///
+/// // Give up on an update that takes longer than 200 ms:
+/// const deadline = performance.now() + 200;
+/// planet.set_params(next, () => performance.now() < deadline);
+/// ```
+///
+/// Every call is synchronous: progress callbacks run inside it, the page
+/// does not repaint and no input events fire until it returns.  For a live
+/// progress bar, or to cancel from user input, run the planet in a Worker,
+/// `postMessage` the progress events to the page, and decide cancellation
+/// inside the callback from state the Worker can see (a deadline, or an
+/// `Atomics.load` of a flag in a `SharedArrayBuffer` the page sets).
+#[wasm_bindgen(js_name = Planet)]
+pub struct PlanetHandle {
+    inner: Planet,
+    generation_time_ms: u64,
+}
+
+#[wasm_bindgen(js_class = Planet)]
+impl PlanetHandle {
+    /// `on_progress` is optional; see `js_run_control`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        params_js: JsValue,
+        on_progress: Option<js_sys::Function>,
+    ) -> Result<PlanetHandle, JsValue> {
+        let params: GlobalParams = serde_wasm_bindgen::from_value(params_js)
+            .map_err(|e| JsValue::from_str(&format!("Invalid params: {e}")))?;
+
+        let t0 = js_sys::Date::now();
+        let inner = Planet::try_with_config(
+            &params,
+            GenerationConfig::default(),
+            &mut js_run_control(on_progress),
+        )
+        .map_err(terra_error_to_js)?;
+        let generation_time_ms = (js_sys::Date::now() - t0) as u64;
+
+        Ok(PlanetHandle {
+            inner,
+            generation_time_ms,
+        })
+    }
+
+    /// Reopen a planet from the bytes returned by `save`, without rerunning
+    /// the simulation.  Rejects with `"Invalid save file: …"` on damaged or
+    /// unsupported files.
+    pub fn load(bytes: &[u8]) -> Result<PlanetHandle, JsValue> {
+        let t0 = js_sys::Date::now();
+        let inner = save::load_planet(bytes).map_err(save_error_to_js)?;
+        let generation_time_ms = (js_sys::Date::now() - t0) as u64;
+
+        Ok(PlanetHandle {
+            inner,
+            generation_time_ms,
+        })
+    }
 
+    /// The planet in the versioned save format, as a `Uint8Array`.
+    pub fn save(&self) -> Vec<u8> {
+        save::save_planet(&self.inner)
+    }
+
+    /// Move the sliders without rebuilding the planet from scratch.
+    ///
+    /// Only the overview stages that depend on the changed params rerun;
+    /// returns their names (e.g. `["climate", "climate_smoothing", "metrics"]`).
+    /// `on_progress` is optional; see `js_run_control`.  Rejects with a
+    /// `TerraError` on invalid params (leaving the planet unchanged) or on
+    /// cancellation; a cancelled update is resumed by the next `set_params`.
+    pub fn set_params(
+        &mut self,
+        params_js: JsValue,
+        on_progress: Option<js_sys::Function>,
+    ) -> Result<JsValue, JsValue> {
+        let params: GlobalParams = serde_wasm_bindgen::from_value(params_js)
+            .map_err(|e| JsValue::from_str(&format!("Invalid params: {e}")))?;
+
+        let t0 = js_sys::Date::now();
+        let ran = self
+            .inner
+            .try_set_params(&params, &mut js_run_control(on_progress))
+            .map_err(terra_error_to_js)?;
+        self.generation_time_ms = (js_sys::Date::now() - t0) as u64;
+
+        serde_wasm_bindgen::to_value(&ran)
+            .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
+    }
+
+    /// Overview fields in the same shape as `generate_overview`.
+    pub fn overview(&self) -> Result<JsValue, JsValue> {
+        let js = overview_to_js(self.inner.overview(), self.generation_time_ms);
+        serde_wasm_bindgen::to_value(&js)
+            .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
+    }
+
+    /// Drill-down tile in the same shape as `generate_at_location_wasm`.
+    pub fn tile_at(&self, lat: f32, lon: f32) -> Result<JsValue, JsValue> {
+        check_location(lat, lon).map_err(terra_error_to_js)?;
+        let t0 = js_sys::Date::now();
+        let r = self.inner.tile_at(lat, lon);
+        let generation_time_ms = (js_sys::Date::now() - t0) as u64;
+
+        let js = location_tile_to_js(r, generation_time_ms);
+        serde_wasm_bindgen::to_value(&js)
+            .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
+    }
+
+    /// Drill-down tile built on the overview elevation, in metres relative to
+    /// sea level (negative = ocean). Same shape as `tile_at`.
+    pub fn conditioned_tile_at(&self, lat: f32, lon: f32) -> Result<JsValue, JsValue> {
+        check_location(lat, lon).map_err(terra_error_to_js)?;
+        let t0 = js_sys::Date::now();
+        let r = self.inner.conditioned_tile_at(lat, lon);
+        let generation_time_ms = (js_sys::Date::now() - t0) as u64;
+
+        let js = location_tile_to_js(r, generation_time_ms);
+        serde_wasm_bindgen::to_value(&js)
+            .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
+    }
+
+    /// Seamless slippy-map tile at `zoom/x/y`; throws a `TerraError` for
+    /// addresses off the globe.
+    pub fn tile_for_addr(&self, zoom: u32, x: u32, y: u32) -> Result<JsValue, JsValue> {
+        let t0 = js_sys::Date::now();
+        let tile = self
+            .inner
+            .try_tile_for_addr(TileAddr::new(zoom, x, y))
+            .map_err(terra_error_to_js)?;
+        let generation_time_ms = (js_sys::Date::now() - t0) as u64;
+
+        let hf = tile.heightfield;
+        let js = AddressedTileJs {
+            width: hf.width as u32,
+            height: hf.height as u32,
+            zoom,
+            x,
+            y,
+            min_lon: hf.min_lon,
+            max_lon: hf.max_lon,
+            min_lat: hf.min_lat,
+            max_lat: hf.max_lat,
+            heights: hf.data,
+            generation_time_ms,
+        };
+        serde_wasm_bindgen::to_value(&js)
+            .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
+    }
+
+    /// Planet fields at a lat/lon without generating a tile (hover readout).
+    pub fn field_at(&self, lat: f32, lon: f32) -> Result<JsValue, JsValue> {
+        check_location(lat, lon).map_err(terra_error_to_js)?;
+        let f = self.inner.field_at(lat, lon);
+        let loc = f.location;
+        let js = FieldSampleJs {
+            lat: loc.lat,
+            lon: loc.lon,
+            elevation_km: f.elevation_km,
+            is_ocean: f.is_ocean,
+            sampled_fields: sampled_fields_to_js(
+                loc.terrain_class,
+                loc.regime,
+                loc.map_mm,
+                loc.erodibility,
+                loc.grain_angle,
+                loc.grain_intensity,
+                loc.glaciation,
+            ),
+        };
+        serde_wasm_bindgen::to_value(&js)
+            .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
+    }
+}
+
+#[derive(Serialize)]
+struct SaveInfoJs {
+    format_version: u32,
+    generator_version: String,
+    seed_scheme_version: u32,
+    output_version: u32,
+    /// False when this build would generate a different planet from the
+    /// saved params; the loaded fields are still exact.
+    matches_current_build: bool,
+    params: GlobalParams,
+}
+
+/// Provenance of a save file without loading the planet, e.g. to warn that
+/// it was written by another generator version.
+#[wasm_bindgen]
+pub fn save_file_info(bytes: &[u8]) -> Result<JsValue, JsValue> {
+    let info = save::read_info(bytes).map_err(save_error_to_js)?;
+    let js = SaveInfoJs {
+        format_version: info.format_version,
+        matches_current_build: info.matches_current_build(),
+        generator_version: info.generator_version,
+        seed_scheme_version: info.seed_scheme_version,
+        output_version: info.output_version,
+        params: info.params,
+    };
     serde_wasm_bindgen::to_value(&js)
         .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
 }
 
+/// Params of a named preset (`earth-like`, `pangaea`, `archipelago`,
+/// `arid-mars`, `snowball`), ready to pass to any generation entry point.
+#[wasm_bindgen]
+pub fn preset_params(name: &str) -> Result<JsValue, JsValue> {
+    let preset = Preset::from_name(name).map_err(|e| JsValue::from_str(&e.to_string()))?;
+    serde_wasm_bindgen::to_value(&preset.params())
+        .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
+}
+
+/// Resolve a TOML recipe to params, filling gaps from the preset it names.
+#[wasm_bindgen]
+pub fn recipe_params(toml: &str) -> Result<JsValue, JsValue> {
+    let recipe =
+        Recipe::from_toml(toml).map_err(|e| JsValue::from_str(&format!("Invalid recipe: {e}")))?;
+    serde_wasm_bindgen::to_value(&recipe.params)
+        .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
+}
+
+/// Solve for params whose overview hits `targets_js`, e.g.
+/// `{ land_fraction: 0.4, continent_count: 3, mountain_fraction: 0.15 }`,
+/// starting from `base_js`.  Returns `{ params, achieved, distance,
+/// evaluations }`; invalid targets or params throw a `TerraError`.
+#[wasm_bindgen]
+pub fn solve_design(targets_js: JsValue, base_js: JsValue) -> Result<JsValue, JsValue> {
+    let targets: DesignTargets = serde_wasm_bindgen::from_value(targets_js)
+        .map_err(|e| JsValue::from_str(&format!("Invalid targets: {e}")))?;
+    let base: GlobalParams = serde_wasm_bindgen::from_value(base_js)
+        .map_err(|e| JsValue::from_str(&format!("Invalid params: {e}")))?;
+    let solution = inverse::solve(&targets, &base).map_err(terra_error_to_js)?;
+    serde_wasm_bindgen::to_value(&solution)
+        .map_err(|e| JsValue::from_str(&format!("Serialisation error: {e}")))
+}
+
 /// Resolve GlobalParams → internal DebugParams without running the full pipeline.
 ///
 /// Use this to verify slider wiring: each slider should change at least one
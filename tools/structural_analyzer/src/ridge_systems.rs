@@ -394,7 +394,7 @@ fn find_dominant_watershed_pair(
 
     // Sort by frequency descending; the two most common labels form the pair.
     let mut sorted: Vec<(u32, usize)> = label_counts.into_iter().collect();
-    sorted.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+    sorted.sort_unstable_by_key(|&(_, count)| std::cmp::Reverse(count));
 
     let wa = sorted[0].0 as usize;
     let wb = if sorted.len() >= 2 {
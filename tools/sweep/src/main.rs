@@ -0,0 +1,577 @@
+//! Batch parameter sweep and first-order sensitivity analysis.
+//!
+//! Runs the overview and tile pipelines over a full-factorial grid or a Latin
+//! hypercube of the sliders, at several seeds per design point, and records
+//! the tile's `RealismScore` (total and per metric), the overview's planet
+//! metrics and land fraction, and the wall time of both pipelines.
+//!
+//!   sweep --design lhs --samples 64 --seeds 4 -o sweep/
+//!   sweep --design lhs --samples 16 --seeds 1 --jobs 1 -o timings/
+//!   sweep --design grid --levels 5 --sliders glaciation,water_abundance
+//!   sweep --preset arid-mars --sliders surface_age,glaciation --no-tile
+//!
+//! Output directory:
+//!   results.csv       tidy: one row per run × output
+//!   runs.json         base params, design and every run's outputs
+//!   sensitivity.csv   first-order index per slider × output
+//!   sensitivity.json
+//!
+//! The first-order index S_i = Var(E[Y | X_i]) / Var(Y) is estimated by
+//! sorting the runs on X_i, cutting them into equal-count bins (one per level
+//! for a grid, about √runs for a hypercube) and comparing the variance of the
+//! bin means with the total variance.  Reseeding stays in Var(Y), so a slider
+//! that moves an output less than a new seed does scores near zero.  With few
+//! runs per bin the estimate is biased upward by roughly (bins − 1) / runs.
+//!
+//! Runs execute `--jobs` at a time, one per core by default.  Concurrent
+//! runs share cores and memory bandwidth, so `time.*` outputs are only
+//! meaningful with `--jobs 1`; the other outputs do not depend on it.
+
+use anyhow::{bail, Context, Result};
+use clap::{builder::PossibleValuesParser, Parser, ValueEnum};
+use rayon::prelude::*;
+use serde::Serialize;
+use serde_json::json;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+use terra_core::{
+    generator::{GenerationConfig, GlobalParams, PlanetGenerator},
+    planet::generate_planet_overview_with_config,
+    recipe::{Preset, Recipe},
+    seed::StableRng,
+};
+
+// ── Sliders ───────────────────────────────────────────────────────────────────
+
+type SliderSlot = fn(&mut GlobalParams) -> &mut f32;
+
+/// Every slider by its recipe key.
+const SLIDERS: [(&str, SliderSlot); 7] = [
+    ("tectonic_activity", |p| &mut p.tectonic_activity),
+    ("water_abundance", |p| &mut p.water_abundance),
+    ("surface_age", |p| &mut p.surface_age),
+    ("climate_diversity", |p| &mut p.climate_diversity),
+    ("glaciation", |p| &mut p.glaciation),
+    ("continental_fragmentation", |p| {
+        &mut p.continental_fragmentation
+    }),
+    ("mountain_prevalence", |p| &mut p.mountain_prevalence),
+];
+
+// ── CLI ───────────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Design {
+    /// Full-factorial grid of `--levels` values per slider.
+    Grid,
+    /// Latin hypercube of `--samples` points.
+    Lhs,
+}
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "sweep",
+    about = "Sweep GlobalParams over a design and estimate per-slider sensitivity"
+)]
+struct Args {
+    /// Sampling design over the swept sliders.
+    #[arg(long, value_enum, default_value_t = Design::Lhs)]
+    design: Design,
+
+    /// Sliders to vary (comma-separated; default all). The others keep
+    /// their base value.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_parser = PossibleValuesParser::new(SLIDERS.map(|(name, _)| name))
+    )]
+    sliders: Vec<String>,
+
+    /// Evenly spaced values per slider in [0, 1] for `--design grid`.
+    #[arg(long, default_value_t = 3)]
+    levels: usize,
+
+    /// Design points for `--design lhs`.
+    #[arg(long, default_value_t = 32)]
+    samples: usize,
+
+    /// Seeds per design point, consecutive from `--seed`.
+    #[arg(long, default_value_t = 3)]
+    seeds: u64,
+
+    /// First seed (default: the base params' seed).
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Seed of the hypercube's stratum shuffle and jitter.
+    #[arg(long, default_value_t = 0)]
+    design_seed: u64,
+
+    /// Recipe file (.json or .toml) with the base params.
+    #[arg(long = "params", value_name = "FILE")]
+    file: Option<PathBuf>,
+
+    /// Named preset with the base params (earth-like, pangaea, archipelago,
+    /// arid-mars, snowball).
+    #[arg(long, conflicts_with = "file")]
+    preset: Option<Preset>,
+
+    /// Planet overview grid, WIDTHxHEIGHT.
+    #[arg(long, value_name = "WxH", value_parser = parse_size, default_value = "256x128")]
+    overview_size: (usize, usize),
+
+    /// Tile grid, WIDTHxHEIGHT.
+    #[arg(long, value_name = "WxH", value_parser = parse_size, default_value = "256x128")]
+    tile_size: (usize, usize),
+
+    /// Skip the tile pipeline and its RealismScore outputs.
+    #[arg(long)]
+    no_tile: bool,
+
+    /// Bins for the sensitivity estimate (default: levels for a grid, √runs
+    /// for a hypercube).
+    #[arg(long)]
+    bins: Option<usize>,
+
+    /// Runs executed at once (default: one per core).  Use 1 when the
+    /// timings matter.
+    #[arg(long)]
+    jobs: Option<usize>,
+
+    /// Output directory (created if absent).
+    #[arg(short, long, default_value = "sweep")]
+    output: PathBuf,
+}
+
+/// Parse a `WIDTHxHEIGHT` grid size.
+fn parse_size(s: &str) -> Result<(usize, usize)> {
+    let (w, h) = s
+        .split_once(['x', 'X'])
+        .context("expected WIDTHxHEIGHT, e.g. 256x128")?;
+    let (w, h): (usize, usize) = (w.trim().parse()?, h.trim().parse()?);
+    if w < 16 || h < 8 {
+        bail!("grid {w}x{h} is too small (minimum 16x8)");
+    }
+    Ok((w, h))
+}
+
+fn base_params(args: &Args) -> Result<GlobalParams> {
+    let params = match (&args.file, args.preset) {
+        (Some(path), _) => {
+            let text =
+                fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+            let recipe = match path.extension().and_then(|e| e.to_str()) {
+                Some("toml") => Recipe::from_toml(&text),
+                _ => Recipe::from_json(&text),
+            };
+            recipe
+                .with_context(|| format!("invalid recipe {}", path.display()))?
+                .params
+        }
+        (None, Some(preset)) => preset.params(),
+        (None, None) => GlobalParams::default(),
+    };
+    params.validate()?;
+    Ok(params)
+}
+
+// ── Designs ───────────────────────────────────────────────────────────────────
+
+/// Every combination of `levels` evenly spaced values in [0, 1] (0.5 for a
+/// single level) over `dims` sliders.
+fn grid_points(dims: usize, levels: usize) -> Vec<Vec<f32>> {
+    let value = |k: usize| {
+        if levels == 1 {
+            0.5
+        } else {
+            k as f32 / (levels - 1) as f32
+        }
+    };
+    (0..levels.pow(dims as u32))
+        .map(|mut i| {
+            (0..dims)
+                .map(|_| {
+                    let k = i % levels;
+                    i /= levels;
+                    value(k)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// `n` points whose values, per slider, fall one in each of `n` equal strata
+/// of [0, 1], jittered uniformly within the stratum.
+fn latin_hypercube(dims: usize, n: usize, rng: &mut StableRng) -> Vec<Vec<f32>> {
+    let mut points = vec![vec![0.0; dims]; n];
+    for d in 0..dims {
+        let mut strata: Vec<usize> = (0..n).collect();
+        for i in (1..n).rev() {
+            strata.swap(i, rng.below(i + 1));
+        }
+        for (point, stratum) in points.iter_mut().zip(strata) {
+            point[d] = ((stratum as f64 + rng.next_f64()) / n as f64) as f32;
+        }
+    }
+    points
+}
+
+// ── Runs ──────────────────────────────────────────────────────────────────────
+
+#[derive(Serialize)]
+struct Run {
+    /// Index of the design point.
+    point: usize,
+    seed: u64,
+    /// Values of the swept sliders.
+    sliders: BTreeMap<&'static str, f32>,
+    /// Every measured output by name; NaN (null) where a metric failed.
+    outputs: BTreeMap<String, f64>,
+}
+
+/// Run the pipelines once and collect their outputs:
+/// `score.total`, `score.<metric>` (0-1) and `raw.<metric>` from the tile,
+/// `planet.<metric>` and `land_fraction` from the overview, and
+/// `time.overview_ms` / `time.tile_ms`.
+fn measure(params: &GlobalParams, config: &GenerationConfig, tile: bool) -> BTreeMap<String, f64> {
+    let mut out = BTreeMap::new();
+
+    let start = Instant::now();
+    let ov = generate_planet_overview_with_config(params, config);
+    out.insert(
+        "time.overview_ms".to_owned(),
+        start.elapsed().as_secs_f64() * 1e3,
+    );
+    let land = ov.ocean_mask.iter().filter(|&&ocean| !ocean).count();
+    out.insert(
+        "land_fraction".to_owned(),
+        land as f64 / ov.ocean_mask.len() as f64,
+    );
+    for m in &ov.planet_metrics.metrics {
+        out.insert(format!("planet.{}", m.name), m.raw_value as f64);
+    }
+
+    if tile {
+        let start = Instant::now();
+        let result = PlanetGenerator::with_config(*config).generate(params);
+        out.insert(
+            "time.tile_ms".to_owned(),
+            start.elapsed().as_secs_f64() * 1e3,
+        );
+        out.insert("score.total".to_owned(), result.score.total as f64);
+        for m in &result.score.metrics {
+            out.insert(format!("score.{}", m.name), m.score_0_1 as f64);
+            out.insert(format!("raw.{}", m.name), m.raw_value as f64);
+        }
+    }
+    out
+}
+
+// ── Sensitivity ───────────────────────────────────────────────────────────────
+
+#[derive(Serialize)]
+struct Sensitivity {
+    slider: &'static str,
+    output: String,
+    /// `None` when the output is constant or too few runs produced it.
+    first_order: Option<f64>,
+    /// Runs with a finite value of the output.
+    runs: usize,
+}
+
+/// First-order sensitivity index (correlation ratio η²) of `y` to `x`, from
+/// `bins` equal-count bins of the runs sorted by `x`.
+///
+/// Runs with a non-finite `y` are dropped.  `None` when fewer than two runs
+/// per bin remain or `y` is constant.
+fn first_order_index(x: &[f32], y: &[f64], bins: usize) -> Option<f64> {
+    let mut pairs: Vec<(f32, f64)> = x
+        .iter()
+        .copied()
+        .zip(y.iter().copied())
+        .filter(|(_, y)| y.is_finite())
+        .collect();
+    let n = pairs.len();
+    if bins < 2 || n < 2 * bins || pairs.iter().all(|p| p.1 == pairs[0].1) {
+        return None;
+    }
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mean = pairs.iter().map(|p| p.1).sum::<f64>() / n as f64;
+    let total: f64 = pairs.iter().map(|p| (p.1 - mean).powi(2)).sum();
+    let between: f64 = (0..bins)
+        .map(|b| {
+            let bin = &pairs[b * n / bins..(b + 1) * n / bins];
+            let bin_mean = bin.iter().map(|p| p.1).sum::<f64>() / bin.len() as f64;
+            bin.len() as f64 * (bin_mean - mean).powi(2)
+        })
+        .sum();
+    Some((between / total).min(1.0))
+}
+
+fn sensitivities(runs: &[Run], sliders: &[&'static str], bins: usize) -> Vec<Sensitivity> {
+    let outputs: BTreeSet<&String> = runs.iter().flat_map(|r| r.outputs.keys()).collect();
+    let mut rows = Vec::new();
+    for output in outputs {
+        let y: Vec<f64> = runs
+            .iter()
+            .map(|r| r.outputs.get(output).copied().unwrap_or(f64::NAN))
+            .collect();
+        let finite = y.iter().filter(|v| v.is_finite()).count();
+        for &slider in sliders {
+            let x: Vec<f32> = runs.iter().map(|r| r.sliders[slider]).collect();
+            rows.push(Sensitivity {
+                slider,
+                output: output.clone(),
+                first_order: first_order_index(&x, &y, bins),
+                runs: finite,
+            });
+        }
+    }
+    rows
+}
+
+// ── Output ────────────────────────────────────────────────────────────────────
+
+/// Empty cell for a missing or non-finite value.
+fn csv_value(v: Option<f64>) -> String {
+    v.filter(|v| v.is_finite())
+        .map(|v| v.to_string())
+        .unwrap_or_default()
+}
+
+fn write_results_csv(path: &Path, runs: &[Run], sliders: &[&'static str]) -> Result<()> {
+    let mut csv = format!("run,point,seed,{},output,value\n", sliders.join(","));
+    for (i, run) in runs.iter().enumerate() {
+        let values: Vec<String> = sliders.iter().map(|s| run.sliders[s].to_string()).collect();
+        for (output, &value) in &run.outputs {
+            writeln!(
+                csv,
+                "{i},{},{},{},{output},{}",
+                run.point,
+                run.seed,
+                values.join(","),
+                csv_value(Some(value))
+            )?;
+        }
+    }
+    fs::write(path, csv).with_context(|| format!("writing {}", path.display()))
+}
+
+fn write_sensitivity_csv(path: &Path, rows: &[Sensitivity]) -> Result<()> {
+    let mut csv = String::from("slider,output,first_order,runs\n");
+    for row in rows {
+        writeln!(
+            csv,
+            "{},{},{},{}",
+            row.slider,
+            row.output,
+            csv_value(row.first_order),
+            row.runs
+        )?;
+    }
+    fs::write(path, csv).with_context(|| format!("writing {}", path.display()))
+}
+
+fn write_json(path: &Path, value: &impl Serialize) -> Result<()> {
+    fs::write(path, serde_json::to_string_pretty(value)?)
+        .with_context(|| format!("writing {}", path.display()))
+}
+
+// ── main ──────────────────────────────────────────────────────────────────────
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let base = base_params(&args)?;
+
+    let mut slider_idx: Vec<usize> = Vec::new();
+    for name in &args.sliders {
+        let i = SLIDERS.iter().position(|(s, _)| s == name).unwrap();
+        if !slider_idx.contains(&i) {
+            slider_idx.push(i);
+        }
+    }
+    if slider_idx.is_empty() {
+        slider_idx = (0..SLIDERS.len()).collect();
+    }
+    let names: Vec<&'static str> = slider_idx.iter().map(|&i| SLIDERS[i].0).collect();
+
+    let points = match args.design {
+        Design::Grid => {
+            if args.levels == 0 {
+                bail!("--levels must be at least 1");
+            }
+            grid_points(names.len(), args.levels)
+        }
+        Design::Lhs => {
+            if args.samples == 0 {
+                bail!("--samples must be at least 1");
+            }
+            let mut rng = StableRng::new(args.design_seed);
+            latin_hypercube(names.len(), args.samples, &mut rng)
+        }
+    };
+    if args.seeds == 0 {
+        bail!("--seeds must be at least 1");
+    }
+    if args.jobs == Some(0) {
+        bail!("--jobs must be at least 1");
+    }
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs.unwrap_or(0))
+        .build()?;
+    let first_seed = args.seed.unwrap_or(base.seed);
+    let config = GenerationConfig {
+        overview_width: args.overview_size.0,
+        overview_height: args.overview_size.1,
+        tile_width: args.tile_size.0,
+        tile_height: args.tile_size.1,
+    };
+
+    let jobs: Vec<(usize, u64)> = (0..points.len())
+        .flat_map(|p| (0..args.seeds).map(move |k| (p, first_seed.wrapping_add(k))))
+        .collect();
+    eprintln!(
+        "Sweeping {} ({:?}): {} points × {} seeds = {} runs, {} at a time",
+        names.join(", "),
+        args.design,
+        points.len(),
+        args.seeds,
+        jobs.len(),
+        pool.current_num_threads()
+    );
+
+    let done = AtomicUsize::new(0);
+    let runs: Vec<Run> = pool.install(|| {
+        jobs.par_iter()
+            .map(|&(point, seed)| {
+                let mut params = GlobalParams {
+                    seed,
+                    ..base.clone()
+                };
+                for (&i, &v) in slider_idx.iter().zip(&points[point]) {
+                    *(SLIDERS[i].1)(&mut params) = v;
+                }
+                let outputs = measure(&params, &config, !args.no_tile);
+                let k = done.fetch_add(1, Ordering::Relaxed) + 1;
+                eprintln!("[{k}/{}] point {point} seed {seed}", jobs.len());
+                Run {
+                    point,
+                    seed,
+                    sliders: names.iter().copied().zip(points[point].clone()).collect(),
+                    outputs,
+                }
+            })
+            .collect()
+    });
+
+    let bins = args.bins.unwrap_or(match args.design {
+        Design::Grid => args.levels,
+        Design::Lhs => ((runs.len() as f64).sqrt().round() as usize).max(2),
+    });
+    let rows = sensitivities(&runs, &names, bins);
+
+    fs::create_dir_all(&args.output)
+        .with_context(|| format!("creating {}", args.output.display()))?;
+    write_results_csv(&args.output.join("results.csv"), &runs, &names)?;
+    write_sensitivity_csv(&args.output.join("sensitivity.csv"), &rows)?;
+    write_json(&args.output.join("sensitivity.json"), &rows)?;
+    write_json(
+        &args.output.join("runs.json"),
+        &json!({
+            "design": args.design,
+            "base": base,
+            "config": config,
+            "sliders": names,
+            "bins": bins,
+            "jobs": pool.current_num_threads(),
+            "runs": runs,
+        }),
+    )?;
+
+    // Most influential slider per output.
+    eprintln!("\n{:<36} {:<26} {:>6}", "Output", "Top slider", "S1");
+    eprintln!("{}", "-".repeat(70));
+    for chunk in rows.chunks(names.len()) {
+        let top = chunk
+            .iter()
+            .filter_map(|r| r.first_order.map(|s| (r.slider, s)))
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match top {
+            Some((slider, s)) => eprintln!("{:<36} {:<26} {:>6.3}", chunk[0].output, slider, s),
+            None => eprintln!("{:<36} {:<26} {:>6}", chunk[0].output, "-", "-"),
+        }
+    }
+    eprintln!("\nDone. {} runs → {}", runs.len(), args.output.display());
+    Ok(())
+}
+
+// ── Unit tests ────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cli_definition_is_consistent() {
+        use clap::CommandFactory;
+        Args::command().debug_assert();
+    }
+
+    #[test]
+    fn grid_covers_every_level_combination() {
+        let points = grid_points(2, 3);
+        assert_eq!(points.len(), 9);
+        let distinct: BTreeSet<Vec<u32>> = points
+            .iter()
+            .map(|p| p.iter().map(|v| v.to_bits()).collect())
+            .collect();
+        assert_eq!(distinct.len(), 9);
+        assert!(points.iter().flatten().all(|v| [0.0, 0.5, 1.0].contains(v)));
+        assert_eq!(grid_points(3, 1), vec![vec![0.5; 3]]);
+    }
+
+    #[test]
+    fn latin_hypercube_fills_each_stratum_once() {
+        let n = 10;
+        let points = latin_hypercube(3, n, &mut StableRng::new(7));
+        for d in 0..3 {
+            let mut strata: Vec<usize> =
+                points.iter().map(|p| (p[d] * n as f32) as usize).collect();
+            strata.sort_unstable();
+            assert_eq!(strata, (0..n).collect::<Vec<_>>(), "dimension {d}");
+        }
+    }
+
+    #[test]
+    fn first_order_index_separates_active_and_inert_inputs() {
+        let mut rng = StableRng::new(1);
+        let points = latin_hypercube(2, 400, &mut rng);
+        let x1: Vec<f32> = points.iter().map(|p| p[0]).collect();
+        let x2: Vec<f32> = points.iter().map(|p| p[1]).collect();
+        let y: Vec<f64> = points
+            .iter()
+            .map(|p| 4.0 * p[0] as f64 + 0.2 * rng.next_f64())
+            .collect();
+        let s1 = first_order_index(&x1, &y, 20).unwrap();
+        let s2 = first_order_index(&x2, &y, 20).unwrap();
+        assert!(s1 > 0.95, "active input S1 = {s1}");
+        assert!(s2 < 0.1, "inert input S1 = {s2}");
+    }
+
+    #[test]
+    fn first_order_index_skips_non_finite_and_constant_outputs() {
+        let x = [0.0, 0.0, 1.0, 1.0, 0.5];
+        let y = [1.0, 1.0, 3.0, 3.0, f64::NAN];
+        assert_eq!(first_order_index(&x, &y, 2), Some(1.0));
+        assert_eq!(first_order_index(&x, &[2.0; 5], 2), None);
+        assert_eq!(first_order_index(&x, &y, 3), None);
+    }
+}
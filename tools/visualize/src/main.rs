@@ -172,7 +172,7 @@ fn main() {
         let np = NoiseParams::default(); // FluvialHumid, h=0.75
         let tile_w = 512usize;
         let tile_h = 512usize;
-        let mut hf = generate_tile(&np, params.seed as u32, tile_w, tile_h, 0.0, 1.0, 0.0, 1.0);
+        let mut hf = generate_tile(&np, params.seed, tile_w, tile_h, 0.0, 1.0, 0.0, 1.0);
 
         println!("Applying hydraulic shaping…");
         let result = apply_hydraulic_shaping(&mut hf, np.terrain_class, &[], GlacialClass::None);